@@ -0,0 +1,158 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::appointments::RescheduleAppointmentEntity,
+    errors::DomainResult,
+    repositories::admin::AdminRepository,
+    value_objects::{
+        admin_model::{AdminAddAppointmentDto, GetAppointmentsQuery, GetAppointmentsResponseModel},
+        appointment_model::{CancelAppointmentDto, EditAppointmentDto},
+        schedule_model::ScheduleViewModel,
+        slot_model::EditSlotDto,
+    },
+};
+
+pub struct AdminUseCase<T>
+where
+    T: AdminRepository,
+{
+    admin_repository: Arc<T>,
+}
+
+impl<T> AdminUseCase<T>
+where
+    T: AdminRepository + Send + Sync,
+{
+    pub fn new(admin_repository: Arc<T>) -> Self {
+        Self { admin_repository }
+    }
+
+    pub async fn add_appointment(
+        &self,
+        staff_id: i32,
+        admin_add_appointment_dto: AdminAddAppointmentDto,
+    ) -> DomainResult<Uuid> {
+        let current_time = chrono::Utc::now().naive_utc();
+        let add_appointment_entity = admin_add_appointment_dto
+            .appointment
+            .to_entity(admin_add_appointment_dto.patient_id, current_time);
+
+        let appointment_id = self
+            .admin_repository
+            .add_appointment(add_appointment_entity, staff_id)
+            .await?;
+        Ok(appointment_id)
+    }
+
+    pub async fn edit_appointment(
+        &self,
+        appointment_id: Uuid,
+        staff_id: i32,
+        edit_appointment_dto: EditAppointmentDto,
+    ) -> DomainResult<Uuid> {
+        let current_time = chrono::Utc::now().naive_utc();
+        let reschedule_appointment_entity =
+            edit_appointment_dto
+                .slot_id
+                .map(|new_slot_id| RescheduleAppointmentEntity {
+                    slot_id: new_slot_id,
+                    updated_at: current_time,
+                });
+        let edit_appointment_entity = edit_appointment_dto.to_entity(current_time);
+
+        let appointment_id = self
+            .admin_repository
+            .edit_appointment(
+                appointment_id,
+                reschedule_appointment_entity,
+                edit_appointment_entity,
+                staff_id,
+            )
+            .await?;
+        Ok(appointment_id)
+    }
+
+    pub async fn cancel_appointment(
+        &self,
+        appointment_id: Uuid,
+        staff_id: i32,
+        cancel_appointment_dto: CancelAppointmentDto,
+    ) -> DomainResult<Uuid> {
+        let current_time = chrono::Utc::now().naive_utc();
+        let appointment_transition = cancel_appointment_dto.to_transition(current_time)?;
+
+        let result = self
+            .admin_repository
+            .cancel_appointment(appointment_id, staff_id, appointment_transition)
+            .await?;
+        Ok(result.appointment_id)
+    }
+
+    pub async fn restore_appointment(
+        &self,
+        appointment_id: Uuid,
+        staff_id: i32,
+    ) -> DomainResult<Uuid> {
+        let appointment_id = self
+            .admin_repository
+            .restore_appointment(appointment_id, staff_id)
+            .await?;
+        Ok(appointment_id)
+    }
+
+    pub async fn get_appointments(
+        &self,
+        get_appointments_query: GetAppointmentsQuery,
+    ) -> DomainResult<GetAppointmentsResponseModel> {
+        let timezone = get_appointments_query.to_timezone()?;
+        let appointment_filter = get_appointments_query.to_filter()?;
+        let (page, limit) = (appointment_filter.page, appointment_filter.limit);
+
+        let (schedules, total) = self
+            .admin_repository
+            .get_appointments(appointment_filter)
+            .await?;
+        Ok(GetAppointmentsResponseModel {
+            schedules: schedules
+                .into_iter()
+                .map(|schedule| ScheduleViewModel::from_entity(schedule, timezone))
+                .collect(),
+            total,
+            page,
+            limit,
+        })
+    }
+
+    /// Edits a slot of any doctor, reading wall-clock times in that doctor's
+    /// time zone.
+    pub async fn edit_slot(
+        &self,
+        slot_id: Uuid,
+        staff_id: i32,
+        edit_slot_dto: EditSlotDto,
+    ) -> DomainResult<Uuid> {
+        let current_time = chrono::Utc::now().naive_utc();
+        let timezone = self
+            .admin_repository
+            .get_slot_doctor_timezone(slot_id)
+            .await?;
+        let edit_slot_entity = edit_slot_dto.to_entity(timezone, current_time)?;
+
+        let slot_id = self
+            .admin_repository
+            .edit_slot(slot_id, edit_slot_entity, staff_id)
+            .await?;
+        Ok(slot_id)
+    }
+
+    pub async fn restore_slot(&self, slot_id: Uuid, staff_id: i32) -> DomainResult<Uuid> {
+        let slot_id = self
+            .admin_repository
+            .restore_slot(slot_id, staff_id)
+            .await?;
+
+        Ok(slot_id)
+    }
+}
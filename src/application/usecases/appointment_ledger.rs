@@ -1,9 +1,19 @@
 use std::sync::Arc;
 
-use anyhow::Result;
 use uuid::Uuid;
 
-use crate::domain::repositories::appointment_ledger::AppointmentLedgerRepository;
+use crate::domain::{
+    errors::DomainResult,
+    repositories::appointment_ledger::AppointmentLedgerRepository,
+    value_objects::{
+        actor::Actor,
+        appointment_model::{
+            AppointmentTransition, CancelAppointmentDto, TransitionAppointmentDto,
+            TransitionAppointmentResponseModel,
+        },
+        appointment_status::AppointmentStatus,
+    },
+};
 
 pub struct AppointmentLedgerUseCase<T>
 where
@@ -22,27 +32,136 @@ where
         }
     }
 
-    pub async fn to_ready(&self, appointment_id: Uuid) -> Result<Uuid> {
+    pub async fn to_ready(&self, appointment_id: Uuid, doctor_id: i32) -> DomainResult<Uuid> {
+        let current_time = chrono::Utc::now().naive_utc();
+        let appointment_transition =
+            AppointmentTransition::to(AppointmentStatus::Ready, current_time);
+
+        let result = self
+            .appointment_ledger_repository
+            .transition(
+                appointment_id,
+                Actor::doctor(doctor_id),
+                appointment_transition,
+            )
+            .await?;
+        Ok(result.appointment_id)
+    }
+
+    pub async fn to_waiting_for_prescription(
+        &self,
+        appointment_id: Uuid,
+        doctor_id: i32,
+    ) -> DomainResult<Uuid> {
+        let current_time = chrono::Utc::now().naive_utc();
+        let appointment_transition =
+            AppointmentTransition::to(AppointmentStatus::WaitingForPrescription, current_time);
+
         let result = self
             .appointment_ledger_repository
-            .to_ready(appointment_id)
+            .transition(
+                appointment_id,
+                Actor::doctor(doctor_id),
+                appointment_transition,
+            )
+            .await?;
+        Ok(result.appointment_id)
+    }
+
+    pub async fn to_completed(&self, appointment_id: Uuid, doctor_id: i32) -> DomainResult<Uuid> {
+        let current_time = chrono::Utc::now().naive_utc();
+        let appointment_transition =
+            AppointmentTransition::to(AppointmentStatus::Completed, current_time);
+
+        let result = self
+            .appointment_ledger_repository
+            .transition(
+                appointment_id,
+                Actor::doctor(doctor_id),
+                appointment_transition,
+            )
+            .await?;
+        Ok(result.appointment_id)
+    }
+
+    pub async fn transition(
+        &self,
+        appointment_id: Uuid,
+        doctor_id: i32,
+        transition_appointment_dto: TransitionAppointmentDto,
+    ) -> DomainResult<TransitionAppointmentResponseModel> {
+        let current_time = chrono::Utc::now().naive_utc();
+        let appointment_transition = transition_appointment_dto.to_transition(current_time)?;
+
+        let result = self
+            .appointment_ledger_repository
+            .transition(
+                appointment_id,
+                Actor::doctor(doctor_id),
+                appointment_transition,
+            )
             .await?;
         Ok(result)
     }
 
-    pub async fn to_waiting_for_prescription(&self, appointment_id: Uuid) -> Result<Uuid> {
+    /// Moves an appointment on behalf of another MedBook service, e.g. to
+    /// **Completed** once its prescription has been issued.
+    pub async fn transition_by_service(
+        &self,
+        appointment_id: Uuid,
+        service_api_key_id: i32,
+        transition_appointment_dto: TransitionAppointmentDto,
+    ) -> DomainResult<TransitionAppointmentResponseModel> {
+        let current_time = chrono::Utc::now().naive_utc();
+        let appointment_transition = transition_appointment_dto.to_transition(current_time)?;
+
         let result = self
             .appointment_ledger_repository
-            .to_waiting_for_prescription(appointment_id)
+            .transition(
+                appointment_id,
+                Actor::service(service_api_key_id),
+                appointment_transition,
+            )
             .await?;
         Ok(result)
     }
 
-    pub async fn to_completed(&self, appointment_id: Uuid) -> Result<Uuid> {
+    pub async fn revert(
+        &self,
+        appointment_id: Uuid,
+        doctor_id: i32,
+    ) -> DomainResult<TransitionAppointmentResponseModel> {
+        let current_time = chrono::Utc::now().naive_utc();
+        let appointment_transition = AppointmentTransition::revert(current_time);
+
         let result = self
             .appointment_ledger_repository
-            .to_completed(appointment_id)
+            .transition(
+                appointment_id,
+                Actor::doctor(doctor_id),
+                appointment_transition,
+            )
             .await?;
         Ok(result)
     }
+
+    pub async fn cancel(
+        &self,
+        appointment_id: Uuid,
+        doctor_id: i32,
+        cancel_appointment_dto: CancelAppointmentDto,
+    ) -> DomainResult<Uuid> {
+        let current_time = chrono::Utc::now().naive_utc();
+        let appointment_transition = cancel_appointment_dto.to_transition(current_time)?;
+
+        let result = self
+            .appointment_ledger_repository
+            .transition(
+                appointment_id,
+                Actor::doctor(doctor_id),
+                appointment_transition,
+            )
+            .await?;
+        Ok(result.appointment_id)
+    }
 }
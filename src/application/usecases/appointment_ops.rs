@@ -1,10 +1,9 @@
 use std::sync::Arc;
 
-use anyhow::Result;
 use uuid::Uuid;
 
 use crate::domain::{
-    entities::appointments::RescheduleAppointmentEntity, repositories::appointment_ops::AppointmentOpsRepository, value_objects::appointment_model::{AddAppointmentDto, EditAppointmentDto}
+    entities::appointments::RescheduleAppointmentEntity, errors::DomainResult, repositories::appointment_ops::AppointmentOpsRepository, value_objects::{appointment_model::{AddAppointmentDto, EditAppointmentDto}, idempotency_model::{IdempotencyKey, IdempotentOperation}}
 };
 
 pub struct AppointmentOpsUseCase<T>
@@ -24,22 +23,30 @@ where
         }
     }
 
-    pub async fn add(&self, add_appointment_dto: AddAppointmentDto, patient_id: i32) -> Result<Uuid> {
+    pub async fn add(&self, add_appointment_dto: AddAppointmentDto, patient_id: i32, idempotency_key: Option<String>) -> DomainResult<Uuid> {
         let current_time = chrono::Utc::now().naive_utc();
+        let idempotency_key = idempotency_key
+            .map(|key| IdempotencyKey::new(IdempotentOperation::AddAppointment, &key, &add_appointment_dto))
+            .transpose()?;
         let add_appointment_entity = add_appointment_dto.to_entity(patient_id, current_time);
 
-        let appointment_id = self.appointment_ops_repository.add(add_appointment_entity).await?;
+        let appointment_id = self.appointment_ops_repository.add(add_appointment_entity, idempotency_key).await?;
         Ok(appointment_id)
     }
 
-    pub async fn edit(&self, appointment_id: Uuid, patient_id :i32, edit_appointment_dto: EditAppointmentDto) -> Result<()> {
+    /// `idempotency_key` only guards the reschedule, since editing the other
+    /// fields gives the same result however many times it is retried.
+    pub async fn edit(&self, appointment_id: Uuid, patient_id :i32, edit_appointment_dto: EditAppointmentDto, idempotency_key: Option<String>) -> DomainResult<()> {
         let current_time = chrono::Utc::now().naive_utc();
         if let Some(new_slot_id) = edit_appointment_dto.slot_id {
+            let idempotency_key = idempotency_key
+                .map(|key| IdempotencyKey::new(IdempotentOperation::RescheduleAppointment, &key, &(appointment_id, &edit_appointment_dto)))
+                .transpose()?;
             let reschedule_appointment_entity = RescheduleAppointmentEntity {
                 slot_id: new_slot_id,
                 updated_at: current_time
             };
-            self.appointment_ops_repository.reschedule(appointment_id, patient_id, reschedule_appointment_entity).await?;
+            self.appointment_ops_repository.reschedule(appointment_id, patient_id, reschedule_appointment_entity, idempotency_key).await?;
         }
 
         let edit_appointment_entity = edit_appointment_dto.to_entity(current_time);
@@ -48,7 +55,7 @@ where
         Ok(())
     }
 
-    pub async fn remove(&self, appointment_id: Uuid, patient_id: i32) -> Result<()> {
+    pub async fn remove(&self, appointment_id: Uuid, patient_id: i32) -> DomainResult<()> {
         self.appointment_ops_repository.remove(appointment_id, patient_id).await?;
 
         Ok(())
@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use chrono::{SubsecRound, TimeDelta};
+
+use crate::domain::{
+    errors::DomainResult,
+    repositories::appointment_reminder::{AppointmentReminderRepository, ReminderNotifier},
+    value_objects::reminder_model::{
+        AppointmentReminderModel, ReminderReportModel, ReminderSchedule,
+    },
+};
+
+pub struct AppointmentReminderUseCase<T, N>
+where
+    T: AppointmentReminderRepository,
+    N: ReminderNotifier,
+{
+    appointment_reminder_repository: Arc<T>,
+    reminder_notifier: Arc<N>,
+    reminder_schedule: ReminderSchedule,
+    batch_size: i64,
+    lease_seconds: i64,
+}
+
+impl<T, N> AppointmentReminderUseCase<T, N>
+where
+    T: AppointmentReminderRepository + Send + Sync,
+    N: ReminderNotifier + Send + Sync,
+{
+    pub fn new(
+        appointment_reminder_repository: Arc<T>,
+        reminder_notifier: Arc<N>,
+        reminder_schedule: ReminderSchedule,
+        batch_size: i64,
+        lease_seconds: i64,
+    ) -> Self {
+        Self {
+            appointment_reminder_repository,
+            reminder_notifier,
+            reminder_schedule,
+            batch_size,
+            lease_seconds,
+        }
+    }
+
+    /// Sends the reminders that are due, up to one batch per offset. Each
+    /// reminder is claimed for `lease_seconds` before the notifier is called,
+    /// so other passes leave it alone. One still not sent when its lease runs
+    /// out, e.g. after a crash, is claimed again and reported as reclaimed.
+    pub async fn send_due(&self) -> DomainResult<ReminderReportModel> {
+        // Postgres keeps microseconds, so a new claim reads back the same
+        // `created_at` and one taken over from an earlier pass does not.
+        let current_time = chrono::Utc::now().trunc_subsecs(6);
+        let lease_until = current_time.naive_utc() + TimeDelta::seconds(self.lease_seconds);
+
+        let mut report = ReminderReportModel::default();
+        for (offset_minutes, after, until) in self.reminder_schedule.windows(current_time) {
+            let schedules = self
+                .appointment_reminder_repository
+                .get_due(
+                    offset_minutes,
+                    after,
+                    until,
+                    current_time.naive_utc(),
+                    self.batch_size,
+                )
+                .await?;
+
+            for schedule in schedules {
+                let appointment_reminder =
+                    AppointmentReminderModel::from_entity(schedule, offset_minutes);
+                let Some(appointment_reminder_entity) = self
+                    .appointment_reminder_repository
+                    .claim(appointment_reminder.to_entity(current_time.naive_utc(), lease_until))
+                    .await?
+                else {
+                    continue;
+                };
+                let id = appointment_reminder_entity.id;
+                if appointment_reminder_entity.created_at != current_time.naive_utc() {
+                    report
+                        .reclaimed
+                        .push((appointment_reminder.appointment_id, offset_minutes));
+                }
+
+                match self.reminder_notifier.notify(&appointment_reminder).await {
+                    Ok(()) => {
+                        self.appointment_reminder_repository
+                            .mark_sent(id, chrono::Utc::now().naive_utc())
+                            .await?;
+                        report.sent += 1;
+                    }
+                    Err(e) => {
+                        self.appointment_reminder_repository.release(id).await?;
+                        report
+                            .failed
+                            .push((appointment_reminder.appointment_id, e.to_string()));
+                    }
+                }
+            }
+        }
+
+        Ok(report)
+    }
+}
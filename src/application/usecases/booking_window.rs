@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use crate::domain::{
+    errors::DomainResult,
+    repositories::booking_window::BookingWindowRepository,
+    value_objects::booking_window::{
+        GetDoctorBookingWindowResponseModel, SetDoctorBookingWindowDto,
+    },
+};
+
+pub struct BookingWindowUseCase<T>
+where
+    T: BookingWindowRepository,
+{
+    booking_window_repository: Arc<T>,
+}
+
+impl<T> BookingWindowUseCase<T>
+where
+    T: BookingWindowRepository + Send + Sync,
+{
+    pub fn new(booking_window_repository: Arc<T>) -> Self {
+        Self {
+            booking_window_repository,
+        }
+    }
+
+    pub async fn get_doctor_booking_window(
+        &self,
+        doctor_id: i32,
+    ) -> DomainResult<GetDoctorBookingWindowResponseModel> {
+        let result = self
+            .booking_window_repository
+            .get_doctor_booking_window(doctor_id)
+            .await?;
+        Ok(result)
+    }
+
+    pub async fn set_doctor_booking_window(
+        &self,
+        doctor_id: i32,
+        set_doctor_booking_window_dto: SetDoctorBookingWindowDto,
+    ) -> DomainResult<GetDoctorBookingWindowResponseModel> {
+        let current_time = chrono::Utc::now().naive_utc();
+        let set_doctor_booking_window_entity =
+            set_doctor_booking_window_dto.to_entity(doctor_id, current_time)?;
+
+        let result = self
+            .booking_window_repository
+            .set_doctor_booking_window(set_doctor_booking_window_entity)
+            .await?;
+        Ok(result)
+    }
+}
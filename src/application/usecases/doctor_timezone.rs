@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use crate::domain::{
+    errors::DomainResult,
+    repositories::doctor_timezone::DoctorTimezoneRepository,
+    value_objects::timezone_model::{GetDoctorTimezoneResponseModel, SetDoctorTimezoneDto},
+};
+
+pub struct DoctorTimezoneUseCase<T>
+where
+    T: DoctorTimezoneRepository,
+{
+    doctor_timezone_repository: Arc<T>,
+}
+
+impl<T> DoctorTimezoneUseCase<T>
+where
+    T: DoctorTimezoneRepository + Send + Sync,
+{
+    pub fn new(doctor_timezone_repository: Arc<T>) -> Self {
+        Self {
+            doctor_timezone_repository,
+        }
+    }
+
+    pub async fn get_doctor_timezone(
+        &self,
+        doctor_id: i32,
+    ) -> DomainResult<GetDoctorTimezoneResponseModel> {
+        let result = self
+            .doctor_timezone_repository
+            .get_doctor_timezone(doctor_id)
+            .await?;
+        Ok(result)
+    }
+
+    pub async fn set_doctor_timezone(
+        &self,
+        doctor_id: i32,
+        set_doctor_timezone_dto: SetDoctorTimezoneDto,
+    ) -> DomainResult<GetDoctorTimezoneResponseModel> {
+        let current_time = chrono::Utc::now().naive_utc();
+        let set_doctor_timezone_entity =
+            set_doctor_timezone_dto.to_entity(doctor_id, current_time)?;
+
+        let result = self
+            .doctor_timezone_repository
+            .set_doctor_timezone(set_doctor_timezone_entity)
+            .await?;
+        Ok(result)
+    }
+}
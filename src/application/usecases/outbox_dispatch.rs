@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use chrono::TimeDelta;
+
+use crate::domain::{
+    errors::DomainResult,
+    repositories::outbox::{OutboxRepository, OutboxSink},
+    value_objects::outbox_model::{OutboxDispatchReportModel, OutboxMessage, OutboxRetryPolicy},
+};
+
+pub struct OutboxDispatchUseCase<T, S>
+where
+    T: OutboxRepository,
+    S: OutboxSink,
+{
+    outbox_repository: Arc<T>,
+    outbox_sink: Arc<S>,
+    retry_policy: OutboxRetryPolicy,
+    batch_size: i64,
+    lease_seconds: i64,
+}
+
+impl<T, S> OutboxDispatchUseCase<T, S>
+where
+    T: OutboxRepository + Send + Sync,
+    S: OutboxSink + Send + Sync,
+{
+    pub fn new(
+        outbox_repository: Arc<T>,
+        outbox_sink: Arc<S>,
+        retry_policy: OutboxRetryPolicy,
+        batch_size: i64,
+        lease_seconds: i64,
+    ) -> Self {
+        Self {
+            outbox_repository,
+            outbox_sink,
+            retry_policy,
+            batch_size,
+            lease_seconds,
+        }
+    }
+
+    /// Delivers one batch of due events. Each event is marked dispatched only
+    /// after the sink accepted it, so a crash in between delivers it again.
+    pub async fn dispatch_due(&self) -> DomainResult<OutboxDispatchReportModel> {
+        let current_time = chrono::Utc::now().naive_utc();
+        let lease_until = current_time + TimeDelta::seconds(self.lease_seconds);
+
+        let outbox_events = self
+            .outbox_repository
+            .claim_due(self.batch_size, current_time, lease_until)
+            .await?;
+
+        let mut report = OutboxDispatchReportModel::default();
+        for outbox_event in outbox_events {
+            let outbox_message = OutboxMessage::from_entity(outbox_event);
+            let delivery = self.outbox_sink.deliver(&outbox_message).await;
+            let current_time = chrono::Utc::now().naive_utc();
+
+            let Err(e) = delivery else {
+                self.outbox_repository
+                    .mark_dispatched(outbox_message.id, current_time)
+                    .await?;
+                report.dispatched += 1;
+                continue;
+            };
+
+            let attempts = outbox_message.attempts + 1;
+            let next_attempt_at = self
+                .retry_policy
+                .should_retry(attempts)
+                .then(|| self.retry_policy.next_attempt_at(attempts, current_time));
+            self.outbox_repository
+                .mark_failed(
+                    outbox_message.id,
+                    &e.to_string(),
+                    next_attempt_at,
+                    current_time,
+                )
+                .await?;
+
+            if next_attempt_at.is_some() {
+                report.retried += 1;
+            } else {
+                report.failed.push(outbox_message.id);
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Whether `report` used up the whole batch, so more events may be due.
+    pub fn is_full_batch(&self, report: &OutboxDispatchReportModel) -> bool {
+        let claimed = report.dispatched + report.retried + report.failed.len();
+        claimed as i64 >= self.batch_size
+    }
+}
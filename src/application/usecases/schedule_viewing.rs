@@ -1,11 +1,14 @@
 use std::sync::Arc;
 
-use anyhow::Result;
-use tracing::info;
+use uuid::Uuid;
 
 use crate::domain::{
-    entities::schedule_view::ScheduleViewEntity,
+    errors::DomainResult,
     repositories::schedule_viewing::ScheduleViewingRepository,
+    value_objects::{
+        actor::Actor, appointment_history_model::GetAppointmentHistoryResponseModel,
+        schedule_model::ScheduleViewModel, timezone_model::TimezoneQuery,
+    },
 };
 
 pub struct ScheduleViewingUseCase<T>
@@ -25,19 +28,66 @@ where
         }
     }
 
-    pub async fn get_patient_schedules(&self, patient_id: i32) -> Result<Vec<ScheduleViewEntity>> {
+    pub async fn get_patient_schedules(
+        &self,
+        patient_id: i32,
+        timezone_query: TimezoneQuery,
+    ) -> DomainResult<Vec<ScheduleViewModel>> {
+        let timezone = timezone_query.to_timezone()?;
+
         let schedules = self
             .schedule_viewing_repository
             .get_patient_schedules(patient_id)
             .await?;
-        Ok(schedules)
+        Ok(schedules
+            .into_iter()
+            .map(|schedule| ScheduleViewModel::from_entity(schedule, timezone))
+            .collect())
     }
 
-    pub async fn get_doctor_schedules(&self, doctor_id: i32) -> Result<Vec<ScheduleViewEntity>> {
+    pub async fn get_doctor_schedules(
+        &self,
+        doctor_id: i32,
+        timezone_query: TimezoneQuery,
+    ) -> DomainResult<Vec<ScheduleViewModel>> {
+        let timezone = timezone_query.to_timezone()?;
+
         let schedules = self
             .schedule_viewing_repository
             .get_doctor_schedules(doctor_id)
             .await?;
-        Ok(schedules)
+        Ok(schedules
+            .into_iter()
+            .map(|schedule| ScheduleViewModel::from_entity(schedule, timezone))
+            .collect())
+    }
+
+    pub async fn get_schedule(
+        &self,
+        appointment_id: Uuid,
+        timezone_query: TimezoneQuery,
+    ) -> DomainResult<ScheduleViewModel> {
+        let timezone = timezone_query.to_timezone()?;
+
+        let schedule = self
+            .schedule_viewing_repository
+            .get_schedule(appointment_id)
+            .await?;
+        Ok(ScheduleViewModel::from_entity(schedule, timezone))
+    }
+
+    pub async fn get_appointment_history(
+        &self,
+        appointment_id: Uuid,
+        actor: Actor,
+    ) -> DomainResult<GetAppointmentHistoryResponseModel> {
+        let history = self
+            .schedule_viewing_repository
+            .get_appointment_history(appointment_id, actor)
+            .await?;
+        Ok(GetAppointmentHistoryResponseModel {
+            appointment_id,
+            history,
+        })
     }
 }
@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use crate::domain::{
+    errors::{DomainError, DomainResult},
+    repositories::service_api_key::ServiceApiKeyRepository,
+    value_objects::service_api_key_model::{
+        CreatedServiceApiKeyModel, ServiceApiKey, ServiceCredentialModel, ServiceScope,
+    },
+};
+
+pub struct ServiceApiKeyUseCase<T>
+where
+    T: ServiceApiKeyRepository,
+{
+    service_api_key_repository: Arc<T>,
+}
+
+impl<T> ServiceApiKeyUseCase<T>
+where
+    T: ServiceApiKeyRepository + Send + Sync,
+{
+    pub fn new(service_api_key_repository: Arc<T>) -> Self {
+        Self {
+            service_api_key_repository,
+        }
+    }
+
+    /// Issues a new API key. The returned key is the only copy of it.
+    pub async fn create(
+        &self,
+        service_name: &str,
+        scopes: Vec<ServiceScope>,
+    ) -> DomainResult<CreatedServiceApiKeyModel> {
+        let service_name = service_name.trim();
+        if service_name.is_empty() {
+            return Err(DomainError::validation("Service name must not be empty"));
+        }
+        if scopes.is_empty() {
+            return Err(DomainError::validation(
+                "An API key needs at least one scope",
+            ));
+        }
+
+        let current_time = chrono::Utc::now().naive_utc();
+        let service_api_key = ServiceApiKey::generate();
+        let key_hash = service_api_key.hash()?;
+        let add_service_api_key_entity =
+            service_api_key.to_entity(service_name.to_string(), &scopes, key_hash, current_time);
+
+        let id = self
+            .service_api_key_repository
+            .add(add_service_api_key_entity)
+            .await?;
+        Ok(CreatedServiceApiKeyModel {
+            id,
+            service_name: service_name.to_string(),
+            scopes,
+            service_api_key,
+        })
+    }
+
+    pub async fn authenticate(&self, key: &str) -> DomainResult<ServiceCredentialModel> {
+        let service_api_key = ServiceApiKey::parse(key)?;
+
+        let service_api_key_entity = self
+            .service_api_key_repository
+            .get_active_by_key_prefix(&service_api_key.key_prefix)
+            .await?
+            .ok_or_else(|| DomainError::unauthorized("API key is not valid"))?;
+
+        // argon2 is deliberately slow, keep it off the async workers.
+        let key_hash = service_api_key_entity.key_hash.clone();
+        let is_valid = tokio::task::spawn_blocking(move || service_api_key.verify(&key_hash))
+            .await
+            .map_err(|e| DomainError::Internal(e.into()))?;
+        if !is_valid {
+            return Err(DomainError::unauthorized("API key is not valid"));
+        }
+
+        Ok(ServiceCredentialModel::from_entity(service_api_key_entity))
+    }
+
+    pub async fn revoke(&self, key_prefix: &str) -> DomainResult<()> {
+        let current_time = chrono::Utc::now().naive_utc();
+
+        self.service_api_key_repository
+            .revoke(key_prefix, current_time)
+            .await?;
+        Ok(())
+    }
+}
@@ -1,11 +1,15 @@
 use std::sync::Arc;
 
-use anyhow::Result;
 use uuid::Uuid;
 
 use crate::domain::{
+    errors::{DomainError, DomainResult},
     repositories::slot_ops::SlotOpsRepository,
-    value_objects::slot_model::{AddSlotDto, EditSlotDto},
+    value_objects::slot_model::{
+        AddSlotDto, AddSlotsBulkDto, AddSlotsBulkResponseModel, BulkSlotOutcome,
+        BulkSlotResultModel, EditSlotDto, ForceRemoveSlotDto, ForceShrinkSlotDto, MAX_BULK_SLOTS,
+        SlotCascadeResponseModel,
+    },
 };
 
 pub struct SlotOpsUseCase<T>
@@ -25,22 +29,107 @@ where
         }
     }
 
-    pub async fn add(&self, doctor_id: i32, add_slot_dto: AddSlotDto) -> Result<Uuid> {
+    pub async fn add(&self, doctor_id: i32, add_slot_dto: AddSlotDto) -> DomainResult<Uuid> {
         let current_time = chrono::Utc::now().naive_utc();
-        let add_slot_entity = add_slot_dto.to_entity(doctor_id, current_time);
+        let timezone = self
+            .slot_ops_repository
+            .get_doctor_timezone(doctor_id)
+            .await?;
+        let add_slot_entity = add_slot_dto.to_entity(doctor_id, timezone, current_time)?;
 
         let slot_id = self.slot_ops_repository.add(add_slot_entity).await?;
         Ok(slot_id)
     }
 
+    pub async fn add_bulk(
+        &self,
+        doctor_id: i32,
+        add_slots_bulk_dto: AddSlotsBulkDto,
+    ) -> DomainResult<AddSlotsBulkResponseModel> {
+        if add_slots_bulk_dto.slots.is_empty() {
+            return Err(DomainError::validation("slots must not be empty"));
+        }
+        if add_slots_bulk_dto.slots.len() > MAX_BULK_SLOTS {
+            return Err(DomainError::validation(format!(
+                "At most {} slots can be added at once",
+                MAX_BULK_SLOTS
+            )));
+        }
+
+        let current_time = chrono::Utc::now().naive_utc();
+        let timezone = self
+            .slot_ops_repository
+            .get_doctor_timezone(doctor_id)
+            .await?;
+        let mode = add_slots_bulk_dto.mode.unwrap_or_default();
+
+        // Items that can never be created are reported up front; only the rest
+        // reach the repository. They still do in an all-or-nothing batch with
+        // invalid items, so each reports its own outcome before the rollback.
+        let mut outcomes: Vec<Option<BulkSlotOutcome>> = add_slots_bulk_dto
+            .slots
+            .iter()
+            .map(|add_slot_dto| {
+                add_slot_dto
+                    .invalid_reason(timezone)
+                    .map(|reason| BulkSlotOutcome::Invalid { reason })
+            })
+            .collect();
+        let has_invalid_items = outcomes.iter().any(Option::is_some);
+
+        let add_slot_entities = add_slots_bulk_dto
+            .slots
+            .iter()
+            .zip(outcomes.iter())
+            .filter(|(_, outcome)| outcome.is_none())
+            .map(|(add_slot_dto, _)| add_slot_dto.to_entity(doctor_id, timezone, current_time))
+            .collect::<DomainResult<Vec<_>>>()?;
+
+        let mut repository_outcomes = self
+            .slot_ops_repository
+            .add_bulk(add_slot_entities, mode, has_invalid_items)
+            .await?
+            .into_iter();
+        for outcome in outcomes.iter_mut().filter(|outcome| outcome.is_none()) {
+            *outcome = repository_outcomes.next();
+        }
+
+        let results: Vec<BulkSlotResultModel> = add_slots_bulk_dto
+            .slots
+            .iter()
+            .zip(outcomes)
+            .enumerate()
+            .map(|(index, (add_slot_dto, outcome))| BulkSlotResultModel {
+                index,
+                start_time: add_slot_dto.start_time,
+                end_time: add_slot_dto.end_time,
+                outcome: outcome.unwrap_or(BulkSlotOutcome::NotCreated),
+            })
+            .collect();
+        let created_count = results
+            .iter()
+            .filter(|result| result.outcome.is_created())
+            .count();
+
+        Ok(AddSlotsBulkResponseModel {
+            mode,
+            created_count,
+            results,
+        })
+    }
+
     pub async fn edit(
         &self,
         slot_id: Uuid,
         doctor_id: i32,
         edit_slot_dto: EditSlotDto,
-    ) -> Result<Uuid> {
+    ) -> DomainResult<Uuid> {
         let current_time = chrono::Utc::now().naive_utc();
-        let edit_slot_entity = edit_slot_dto.to_entity(current_time);
+        let timezone = self
+            .slot_ops_repository
+            .get_doctor_timezone(doctor_id)
+            .await?;
+        let edit_slot_entity = edit_slot_dto.to_entity(timezone, current_time)?;
 
         let slot_id = self
             .slot_ops_repository
@@ -49,9 +138,48 @@ where
         Ok(slot_id)
     }
 
-    pub async fn remove(&self, slot_id: Uuid, doctor_id: i32) -> Result<()> {
+    pub async fn remove(&self, slot_id: Uuid, doctor_id: i32) -> DomainResult<()> {
         self.slot_ops_repository.remove(slot_id, doctor_id).await?;
 
         Ok(())
     }
+
+    pub async fn force_shrink(
+        &self,
+        slot_id: Uuid,
+        doctor_id: i32,
+        force_shrink_slot_dto: ForceShrinkSlotDto,
+    ) -> DomainResult<SlotCascadeResponseModel> {
+        let current_time = chrono::Utc::now().naive_utc();
+        let edit_slot_entity = force_shrink_slot_dto.to_entity(current_time)?;
+        let cascade_action = force_shrink_slot_dto.to_cascade_action(current_time)?;
+
+        let affected_appointments = self
+            .slot_ops_repository
+            .force_shrink(slot_id, doctor_id, edit_slot_entity, cascade_action)
+            .await?;
+        Ok(SlotCascadeResponseModel {
+            slot_id,
+            affected_appointments,
+        })
+    }
+
+    pub async fn force_remove(
+        &self,
+        slot_id: Uuid,
+        doctor_id: i32,
+        force_remove_slot_dto: ForceRemoveSlotDto,
+    ) -> DomainResult<SlotCascadeResponseModel> {
+        let current_time = chrono::Utc::now().naive_utc();
+        let cascade_action = force_remove_slot_dto.to_cascade_action(current_time)?;
+
+        let affected_appointments = self
+            .slot_ops_repository
+            .force_remove(slot_id, doctor_id, cascade_action)
+            .await?;
+        Ok(SlotCascadeResponseModel {
+            slot_id,
+            affected_appointments,
+        })
+    }
 }
@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use crate::domain::{
+    errors::DomainResult, repositories::slot_reconciliation::SlotReconciliationRepository,
+    value_objects::slot_reconciliation_model::SlotReconciliationReportModel,
+};
+
+pub struct SlotReconciliationUseCase<T>
+where
+    T: SlotReconciliationRepository,
+{
+    slot_reconciliation_repository: Arc<T>,
+}
+
+impl<T> SlotReconciliationUseCase<T>
+where
+    T: SlotReconciliationRepository + Send + Sync,
+{
+    pub fn new(slot_reconciliation_repository: Arc<T>) -> Self {
+        Self {
+            slot_reconciliation_repository,
+        }
+    }
+
+    /// Finds slots whose stored appointment count drifted from their
+    /// appointments, and recounts them when `repair` is set.
+    pub async fn reconcile(&self, repair: bool) -> DomainResult<SlotReconciliationReportModel> {
+        let result = self
+            .slot_reconciliation_repository
+            .reconcile_appointment_counts(repair)
+            .await?;
+        Ok(result)
+    }
+}
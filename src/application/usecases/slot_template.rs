@@ -0,0 +1,120 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::slot_templates::SlotTemplateEntity,
+    errors::DomainResult,
+    repositories::slot_template::SlotTemplateRepository,
+    value_objects::slot_template_model::{
+        AddSlotTemplateDto, EditSlotTemplateDto, MaterializeSlotTemplateResponseModel,
+        PreviewSlotTemplateResponseModel, SlotTemplateRule,
+    },
+};
+
+pub struct SlotTemplateUseCase<T>
+where
+    T: SlotTemplateRepository,
+{
+    slot_template_repository: Arc<T>,
+}
+
+impl<T> SlotTemplateUseCase<T>
+where
+    T: SlotTemplateRepository + Send + Sync,
+{
+    pub fn new(slot_template_repository: Arc<T>) -> Self {
+        Self {
+            slot_template_repository,
+        }
+    }
+
+    pub async fn get_doctor_slot_templates(
+        &self,
+        doctor_id: i32,
+    ) -> DomainResult<Vec<SlotTemplateEntity>> {
+        let slot_templates = self
+            .slot_template_repository
+            .get_doctor_slot_templates(doctor_id)
+            .await?;
+        Ok(slot_templates)
+    }
+
+    pub async fn preview(
+        &self,
+        doctor_id: i32,
+        add_slot_template_dto: AddSlotTemplateDto,
+    ) -> DomainResult<PreviewSlotTemplateResponseModel> {
+        let rule = add_slot_template_dto.to_rule()?;
+        let timezone = self
+            .slot_template_repository
+            .get_doctor_timezone(doctor_id)
+            .await?;
+
+        let occurrences = self
+            .slot_template_repository
+            .preview(doctor_id, rule.slot_times(timezone))
+            .await?;
+        Ok(PreviewSlotTemplateResponseModel { occurrences })
+    }
+
+    pub async fn add(
+        &self,
+        doctor_id: i32,
+        add_slot_template_dto: AddSlotTemplateDto,
+    ) -> DomainResult<MaterializeSlotTemplateResponseModel> {
+        let current_time = chrono::Utc::now().naive_utc();
+        let rule = add_slot_template_dto.to_rule()?;
+        let add_slot_template_entity = rule.to_add_entity(doctor_id, current_time);
+        let timezone = self
+            .slot_template_repository
+            .get_doctor_timezone(doctor_id)
+            .await?;
+
+        let result = self
+            .slot_template_repository
+            .add(add_slot_template_entity, rule.slot_times(timezone))
+            .await?;
+        Ok(result)
+    }
+
+    pub async fn edit(
+        &self,
+        template_id: Uuid,
+        doctor_id: i32,
+        edit_slot_template_dto: EditSlotTemplateDto,
+    ) -> DomainResult<MaterializeSlotTemplateResponseModel> {
+        let current_time = chrono::Utc::now().naive_utc();
+        let slot_template = self
+            .slot_template_repository
+            .get_slot_template(template_id, doctor_id)
+            .await?;
+        let current_rule = SlotTemplateRule::try_from(&slot_template)?;
+        let rule = edit_slot_template_dto.apply_to(&current_rule)?;
+        let edit_slot_template_entity = rule.to_edit_entity(current_time);
+        let timezone = self
+            .slot_template_repository
+            .get_doctor_timezone(doctor_id)
+            .await?;
+
+        let result = self
+            .slot_template_repository
+            .edit(
+                template_id,
+                doctor_id,
+                edit_slot_template_entity,
+                rule.slot_times(timezone),
+            )
+            .await?;
+        Ok(result)
+    }
+
+    pub async fn remove(&self, template_id: Uuid, doctor_id: i32) -> DomainResult<usize> {
+        let removed_slot_count = self
+            .slot_template_repository
+            .remove(template_id, doctor_id)
+            .await?;
+
+        Ok(removed_slot_count)
+    }
+}
@@ -1,8 +1,13 @@
 use std::sync::Arc;
 
-use anyhow::Result;
-
-use crate::domain::{entities::slots::SlotEntity, repositories::slot_viewing::SlotViewingRepository};
+use crate::domain::{
+    errors::DomainResult,
+    repositories::slot_viewing::SlotViewingRepository,
+    value_objects::{
+        slot_model::{GetSlotsQuery, GetSlotsResponseModel, SlotModel},
+        timezone_model::TimezoneQuery,
+    },
+};
 
 
 pub struct SlotViewingUseCase<T>
@@ -22,14 +27,39 @@ where
         }
     }
 
-    pub async fn get_slots(&self) -> Result<Vec<SlotEntity>> {
-        let schedules = self.slot_viewing_repository.get_slots().await?;
-        Ok(schedules)
+    pub async fn get_slots(
+        &self,
+        get_slots_query: GetSlotsQuery,
+    ) -> DomainResult<GetSlotsResponseModel> {
+        let current_time = chrono::Utc::now();
+        let timezone = get_slots_query.to_timezone()?;
+        let slot_filter = get_slots_query.to_filter(current_time)?;
+        let (page, limit) = (slot_filter.page, slot_filter.limit);
+
+        let (slots, total) = self.slot_viewing_repository.get_slots(slot_filter).await?;
+        Ok(GetSlotsResponseModel {
+            slots: slots
+                .into_iter()
+                .map(|slot| SlotModel::from_entity(slot, timezone))
+                .collect(),
+            total,
+            page,
+            limit,
+        })
     }
 
-    pub async fn get_doctor_slots(&self, doctor_id: i32) -> Result<Vec<SlotEntity>> {
+    pub async fn get_doctor_slots(
+        &self,
+        doctor_id: i32,
+        timezone_query: TimezoneQuery,
+    ) -> DomainResult<Vec<SlotModel>> {
+        let timezone = timezone_query.to_timezone()?;
+
         let schedules = self.slot_viewing_repository.get_doctor_slots(doctor_id).await?;
-        Ok(schedules)
+        Ok(schedules
+            .into_iter()
+            .map(|slot| SlotModel::from_entity(slot, timezone))
+            .collect())
     }
 
 }
\ No newline at end of file
@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::domain::{
+    errors::DomainResult,
+    repositories::waitlist::WaitlistRepository,
+    value_objects::{
+        appointment_model::AddAppointmentDto,
+        waitlist_model::{JoinWaitlistResponseModel, WaitlistPositionModel},
+    },
+};
+
+pub struct WaitlistUseCase<T>
+where
+    T: WaitlistRepository,
+{
+    waitlist_repository: Arc<T>,
+}
+
+impl<T> WaitlistUseCase<T>
+where
+    T: WaitlistRepository + Send + Sync,
+{
+    pub fn new(waitlist_repository: Arc<T>) -> Self {
+        Self {
+            waitlist_repository,
+        }
+    }
+
+    pub async fn join(
+        &self,
+        add_appointment_dto: AddAppointmentDto,
+        patient_id: i32,
+    ) -> DomainResult<JoinWaitlistResponseModel> {
+        let current_time = chrono::Utc::now().naive_utc();
+        let add_waitlist_entry_entity =
+            add_appointment_dto.to_waitlist_entity(patient_id, current_time);
+
+        let result = self
+            .waitlist_repository
+            .join(add_waitlist_entry_entity)
+            .await?;
+        Ok(result)
+    }
+
+    pub async fn get_patient_waitlist(
+        &self,
+        patient_id: i32,
+    ) -> DomainResult<Vec<WaitlistPositionModel>> {
+        let entries = self
+            .waitlist_repository
+            .get_patient_waitlist(patient_id)
+            .await?;
+        Ok(entries)
+    }
+
+    pub async fn leave(&self, waitlist_entry_id: Uuid, patient_id: i32) -> DomainResult<()> {
+        self.waitlist_repository
+            .leave(waitlist_entry_id, patient_id)
+            .await?;
+
+        Ok(())
+    }
+}
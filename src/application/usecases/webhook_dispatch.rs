@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use chrono::TimeDelta;
+
+use crate::domain::{
+    errors::DomainResult,
+    repositories::webhook::{WebhookDeliveryRepository, WebhookSender},
+    value_objects::{
+        outbox_model::OutboxRetryPolicy,
+        webhook_model::{WebhookDispatchReportModel, WebhookRequestModel},
+    },
+};
+
+pub struct WebhookDispatchUseCase<T, S>
+where
+    T: WebhookDeliveryRepository,
+    S: WebhookSender,
+{
+    webhook_delivery_repository: Arc<T>,
+    webhook_sender: Arc<S>,
+    retry_policy: OutboxRetryPolicy,
+    disable_after_failures: i32,
+    batch_size: i64,
+    lease_seconds: i64,
+}
+
+impl<T, S> WebhookDispatchUseCase<T, S>
+where
+    T: WebhookDeliveryRepository + Send + Sync,
+    S: WebhookSender + Send + Sync,
+{
+    pub fn new(
+        webhook_delivery_repository: Arc<T>,
+        webhook_sender: Arc<S>,
+        retry_policy: OutboxRetryPolicy,
+        disable_after_failures: i32,
+        batch_size: i64,
+        lease_seconds: i64,
+    ) -> Self {
+        Self {
+            webhook_delivery_repository,
+            webhook_sender,
+            retry_policy,
+            disable_after_failures,
+            batch_size,
+            lease_seconds,
+        }
+    }
+
+    /// Sends one batch of due deliveries, each signed at the time it is sent.
+    pub async fn dispatch_due(&self) -> DomainResult<WebhookDispatchReportModel> {
+        let current_time = chrono::Utc::now().naive_utc();
+        let lease_until = current_time + TimeDelta::seconds(self.lease_seconds);
+
+        let webhook_deliveries = self
+            .webhook_delivery_repository
+            .claim_due(self.batch_size, current_time, lease_until)
+            .await?;
+
+        let mut report = WebhookDispatchReportModel::default();
+        for (webhook_delivery, webhook_subscription) in webhook_deliveries {
+            let webhook_request = WebhookRequestModel::sign(
+                &webhook_delivery,
+                &webhook_subscription,
+                chrono::Utc::now(),
+            )?;
+            let webhook_attempt = self.webhook_sender.send(&webhook_request).await;
+            let current_time = chrono::Utc::now().naive_utc();
+
+            if webhook_attempt.error.is_none() {
+                self.webhook_delivery_repository
+                    .record_success(
+                        webhook_delivery.id,
+                        webhook_subscription.id,
+                        &webhook_attempt,
+                        current_time,
+                    )
+                    .await?;
+                report.delivered += 1;
+                continue;
+            }
+
+            let attempts = webhook_delivery.attempts + 1;
+            let next_attempt_at = self
+                .retry_policy
+                .should_retry(attempts)
+                .then(|| self.retry_policy.next_attempt_at(attempts, current_time));
+            let subscription_disabled = self
+                .webhook_delivery_repository
+                .record_failure(
+                    webhook_delivery.id,
+                    webhook_subscription.id,
+                    &webhook_attempt,
+                    next_attempt_at,
+                    self.disable_after_failures,
+                    current_time,
+                )
+                .await?;
+
+            if next_attempt_at.is_some() {
+                report.retried += 1;
+            } else {
+                report.failed.push(webhook_delivery.id);
+            }
+            if subscription_disabled {
+                report.disabled_subscriptions.push(webhook_subscription.id);
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Whether `report` used up the whole batch, so more deliveries may be due.
+    pub fn is_full_batch(&self, report: &WebhookDispatchReportModel) -> bool {
+        let claimed = report.delivered + report.retried + report.failed.len();
+        claimed as i64 >= self.batch_size
+    }
+}
@@ -0,0 +1,129 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::domain::{
+    errors::DomainResult,
+    repositories::webhook::WebhookSubscriptionRepository,
+    value_objects::webhook_model::{
+        AddWebhookSubscriptionDto, AddWebhookSubscriptionResponseModel, EditWebhookSubscriptionDto,
+        GetWebhookDeliveriesQuery, WebhookDeliveryModel, WebhookSubscriptionModel,
+    },
+};
+
+pub struct WebhookSubscriptionUseCase<T>
+where
+    T: WebhookSubscriptionRepository,
+{
+    webhook_subscription_repository: Arc<T>,
+}
+
+impl<T> WebhookSubscriptionUseCase<T>
+where
+    T: WebhookSubscriptionRepository + Send + Sync,
+{
+    pub fn new(webhook_subscription_repository: Arc<T>) -> Self {
+        Self {
+            webhook_subscription_repository,
+        }
+    }
+
+    /// The secret in the result is the only time it is shown.
+    pub async fn add(
+        &self,
+        service_api_key_id: i32,
+        add_webhook_subscription_dto: AddWebhookSubscriptionDto,
+    ) -> DomainResult<AddWebhookSubscriptionResponseModel> {
+        let current_time = chrono::Utc::now().naive_utc();
+        let add_webhook_subscription_entity =
+            add_webhook_subscription_dto.to_entity(service_api_key_id, current_time)?;
+
+        let webhook_subscription = self
+            .webhook_subscription_repository
+            .add(add_webhook_subscription_entity)
+            .await?;
+        Ok(AddWebhookSubscriptionResponseModel {
+            secret: webhook_subscription.secret.clone(),
+            subscription: WebhookSubscriptionModel::from_entity(webhook_subscription),
+        })
+    }
+
+    pub async fn get_subscriptions(
+        &self,
+        service_api_key_id: i32,
+    ) -> DomainResult<Vec<WebhookSubscriptionModel>> {
+        let webhook_subscriptions = self
+            .webhook_subscription_repository
+            .get_all_by_service_api_key_id(service_api_key_id)
+            .await?;
+
+        Ok(webhook_subscriptions
+            .into_iter()
+            .map(WebhookSubscriptionModel::from_entity)
+            .collect())
+    }
+
+    pub async fn edit(
+        &self,
+        id: Uuid,
+        service_api_key_id: i32,
+        edit_webhook_subscription_dto: EditWebhookSubscriptionDto,
+    ) -> DomainResult<WebhookSubscriptionModel> {
+        let current_time = chrono::Utc::now().naive_utc();
+        let edit_webhook_subscription_entity =
+            edit_webhook_subscription_dto.to_entity(current_time)?;
+
+        let webhook_subscription = self
+            .webhook_subscription_repository
+            .edit(id, service_api_key_id, edit_webhook_subscription_entity)
+            .await?;
+        Ok(WebhookSubscriptionModel::from_entity(webhook_subscription))
+    }
+
+    pub async fn remove(&self, id: Uuid, service_api_key_id: i32) -> DomainResult<()> {
+        let current_time = chrono::Utc::now().naive_utc();
+
+        self.webhook_subscription_repository
+            .remove(id, service_api_key_id, current_time)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn get_deliveries(
+        &self,
+        id: Uuid,
+        service_api_key_id: i32,
+        get_webhook_deliveries_query: GetWebhookDeliveriesQuery,
+    ) -> DomainResult<Vec<WebhookDeliveryModel>> {
+        let limit = get_webhook_deliveries_query.to_limit()?;
+        let status = get_webhook_deliveries_query
+            .status
+            .map(|status| status.to_string());
+
+        let webhook_deliveries = self
+            .webhook_subscription_repository
+            .get_deliveries(id, service_api_key_id, status, limit)
+            .await?;
+
+        webhook_deliveries
+            .into_iter()
+            .map(WebhookDeliveryModel::from_entity)
+            .collect()
+    }
+
+    /// Sends a delivery again, whatever its status. Subscribers can tell a
+    /// redelivery apart by its event id, which stays the same.
+    pub async fn redeliver(
+        &self,
+        delivery_id: Uuid,
+        service_api_key_id: i32,
+    ) -> DomainResult<WebhookDeliveryModel> {
+        let current_time = chrono::Utc::now().naive_utc();
+
+        let webhook_delivery = self
+            .webhook_subscription_repository
+            .redeliver(delivery_id, service_api_key_id, current_time)
+            .await?;
+        WebhookDeliveryModel::from_entity(webhook_delivery)
+    }
+}
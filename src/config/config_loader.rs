@@ -1,9 +1,19 @@
-use anyhow::Result;
+use anyhow::{Context, Result, bail};
 
-use crate::config::config_model::Frontend;
+use crate::{
+    config::config_model::Frontend,
+    domain::value_objects::{
+        booking_policy::BookingPolicy, booking_window::BookingWindow,
+        outbox_model::OutboxRetryPolicy, reminder_model::ReminderSchedule,
+    },
+};
 
 use super::{
-    config_model::{Database, DoctorsSecret, DotEnvyConfig, PatientsSecret, Server},
+    config_model::{
+        AppointmentReminders, Database, DoctorsSecret, DotEnvyConfig, JwksSource, JwtVerification,
+        OutboxDispatch, OutboxSinkKind, PatientsSecret, ReminderNotifierKind, Server, StaffSecret,
+        WebhookDispatch,
+    },
     stage::Stage,
 };
 
@@ -34,10 +44,59 @@ pub fn load() -> Result<DotEnvyConfig> {
         url: std::env::var("DATABASE_URL").expect("DATABASE_URL is invalid"),
     };
 
+    let default_booking_policy = BookingPolicy::default();
+    let booking_policy = BookingPolicy {
+        prevent_overlapping_appointments: std::env::var("BOOKING_PREVENT_OVERLAPPING_APPOINTMENTS")
+            .ok()
+            .map(|value| value.parse())
+            .transpose()?
+            .unwrap_or(default_booking_policy.prevent_overlapping_appointments),
+        one_active_appointment_per_doctor: std::env::var(
+            "BOOKING_ONE_ACTIVE_APPOINTMENT_PER_DOCTOR",
+        )
+        .ok()
+        .map(|value| value.parse())
+        .transpose()?
+        .unwrap_or(default_booking_policy.one_active_appointment_per_doctor),
+        max_open_appointments: std::env::var("BOOKING_MAX_OPEN_APPOINTMENTS")
+            .ok()
+            .map(|value| value.parse())
+            .transpose()?
+            .or(default_booking_policy.max_open_appointments),
+        booking_window: BookingWindow {
+            min_booking_lead_minutes: std::env::var("BOOKING_MIN_LEAD_MINUTES")
+                .ok()
+                .map(|value| value.parse())
+                .transpose()?
+                .unwrap_or(
+                    default_booking_policy
+                        .booking_window
+                        .min_booking_lead_minutes,
+                ),
+            max_booking_horizon_days: std::env::var("BOOKING_MAX_HORIZON_DAYS")
+                .ok()
+                .map(|value| value.parse())
+                .transpose()?
+                .or(default_booking_policy
+                    .booking_window
+                    .max_booking_horizon_days),
+            min_change_notice_minutes: std::env::var("BOOKING_MIN_CHANGE_NOTICE_MINUTES")
+                .ok()
+                .map(|value| value.parse())
+                .transpose()?
+                .unwrap_or(
+                    default_booking_policy
+                        .booking_window
+                        .min_change_notice_minutes,
+                ),
+        },
+    };
+
     Ok(DotEnvyConfig {
         server,
         frontend,
         database,
+        booking_policy,
     })
 }
 
@@ -48,6 +107,14 @@ pub fn get_stage() -> Stage {
     Stage::try_from(&stage_str).unwrap_or_default()
 }
 
+/// Name of the cookie the access token is read from when the request has no
+/// `Authorization: Bearer` header.
+pub fn get_access_token_cookie_name() -> String {
+    dotenvy::dotenv().ok();
+
+    std::env::var("JWT_ACCESS_TOKEN_COOKIE_NAME").unwrap_or("act".to_string())
+}
+
 pub fn get_patients_secret_env() -> Result<PatientsSecret> {
     dotenvy::dotenv().ok();
 
@@ -67,3 +134,227 @@ pub fn get_doctors_secret_env() -> Result<DoctorsSecret> {
             .expect("JWT_DOCTOR_REFRESH_SECRET is invalid"),
     })
 }
+
+/// `None` when `JWT_STAFF_SECRET` is not set, so deployments without staff
+/// accounts keep working.
+pub fn get_staff_secret_env() -> Result<Option<StaffSecret>> {
+    dotenvy::dotenv().ok();
+
+    Ok(std::env::var("JWT_STAFF_SECRET")
+        .ok()
+        .map(|secret| StaffSecret { secret }))
+}
+
+pub fn get_jwt_verification_env() -> Result<JwtVerification> {
+    dotenvy::dotenv().ok();
+
+    let jwks = match (
+        std::env::var("JWT_JWKS_FILE").ok(),
+        std::env::var("JWT_JWKS_URL").ok(),
+    ) {
+        (Some(_), Some(_)) => bail!("Set only one of JWT_JWKS_FILE and JWT_JWKS_URL"),
+        (Some(path), None) => Some(JwksSource::File(path)),
+        (None, Some(url)) => Some(JwksSource::Url(url)),
+        (None, None) => None,
+    };
+
+    Ok(JwtVerification {
+        jwks,
+        jwks_cache_seconds: std::env::var("JWT_JWKS_CACHE_SECONDS")
+            .ok()
+            .map(|value| value.parse())
+            .transpose()?
+            .unwrap_or(300),
+        issuer: std::env::var("JWT_ISSUER").ok(),
+        audience: std::env::var("JWT_AUDIENCE").ok(),
+    })
+}
+
+pub fn get_outbox_dispatch_env() -> Result<OutboxDispatch> {
+    dotenvy::dotenv().ok();
+
+    let sink = match std::env::var("OUTBOX_SINK")
+        .unwrap_or("log".to_string())
+        .as_str()
+    {
+        "log" => OutboxSinkKind::Log,
+        "webhook" => OutboxSinkKind::Webhook {
+            url: std::env::var("OUTBOX_WEBHOOK_URL")
+                .context("OUTBOX_WEBHOOK_URL is required when OUTBOX_SINK=webhook")?,
+            timeout_seconds: std::env::var("OUTBOX_WEBHOOK_TIMEOUT_SECONDS")
+                .ok()
+                .map(|value| value.parse())
+                .transpose()?
+                .unwrap_or(10),
+        },
+        "pg_notify" => OutboxSinkKind::PgNotify {
+            channel: std::env::var("OUTBOX_PG_NOTIFY_CHANNEL")
+                .unwrap_or("booking_events".to_string()),
+        },
+        sink => bail!("Unknown OUTBOX_SINK {sink:?}, expected log, webhook or pg_notify"),
+    };
+
+    let default_retry_policy = OutboxRetryPolicy::default();
+    let outbox_dispatch = OutboxDispatch {
+        sink,
+        poll_interval_ms: std::env::var("OUTBOX_POLL_INTERVAL_MS")
+            .ok()
+            .map(|value| value.parse())
+            .transpose()?
+            .unwrap_or(1000),
+        batch_size: std::env::var("OUTBOX_BATCH_SIZE")
+            .ok()
+            .map(|value| value.parse())
+            .transpose()?
+            .unwrap_or(20),
+        lease_seconds: std::env::var("OUTBOX_LEASE_SECONDS")
+            .ok()
+            .map(|value| value.parse())
+            .transpose()?
+            .unwrap_or(300),
+        retry_policy: OutboxRetryPolicy {
+            max_attempts: std::env::var("OUTBOX_MAX_ATTEMPTS")
+                .ok()
+                .map(|value| value.parse())
+                .transpose()?
+                .unwrap_or(default_retry_policy.max_attempts),
+            base_delay_seconds: std::env::var("OUTBOX_RETRY_BASE_SECONDS")
+                .ok()
+                .map(|value| value.parse())
+                .transpose()?
+                .unwrap_or(default_retry_policy.base_delay_seconds),
+            max_delay_seconds: std::env::var("OUTBOX_RETRY_MAX_SECONDS")
+                .ok()
+                .map(|value| value.parse())
+                .transpose()?
+                .unwrap_or(default_retry_policy.max_delay_seconds),
+        },
+    };
+
+    if outbox_dispatch.batch_size < 1 || outbox_dispatch.retry_policy.max_attempts < 1 {
+        bail!("OUTBOX_BATCH_SIZE and OUTBOX_MAX_ATTEMPTS must be at least 1");
+    }
+
+    Ok(outbox_dispatch)
+}
+
+pub fn get_webhook_dispatch_env() -> Result<WebhookDispatch> {
+    dotenvy::dotenv().ok();
+
+    let default_retry_policy = OutboxRetryPolicy::default();
+    let webhook_dispatch = WebhookDispatch {
+        poll_interval_ms: std::env::var("WEBHOOK_POLL_INTERVAL_MS")
+            .ok()
+            .map(|value| value.parse())
+            .transpose()?
+            .unwrap_or(1000),
+        batch_size: std::env::var("WEBHOOK_BATCH_SIZE")
+            .ok()
+            .map(|value| value.parse())
+            .transpose()?
+            .unwrap_or(20),
+        lease_seconds: std::env::var("WEBHOOK_LEASE_SECONDS")
+            .ok()
+            .map(|value| value.parse())
+            .transpose()?
+            .unwrap_or(300),
+        timeout_seconds: std::env::var("WEBHOOK_TIMEOUT_SECONDS")
+            .ok()
+            .map(|value| value.parse())
+            .transpose()?
+            .unwrap_or(10),
+        retry_policy: OutboxRetryPolicy {
+            max_attempts: std::env::var("WEBHOOK_MAX_ATTEMPTS")
+                .ok()
+                .map(|value| value.parse())
+                .transpose()?
+                .unwrap_or(default_retry_policy.max_attempts),
+            base_delay_seconds: std::env::var("WEBHOOK_RETRY_BASE_SECONDS")
+                .ok()
+                .map(|value| value.parse())
+                .transpose()?
+                .unwrap_or(default_retry_policy.base_delay_seconds),
+            max_delay_seconds: std::env::var("WEBHOOK_RETRY_MAX_SECONDS")
+                .ok()
+                .map(|value| value.parse())
+                .transpose()?
+                .unwrap_or(default_retry_policy.max_delay_seconds),
+        },
+        disable_after_failures: std::env::var("WEBHOOK_DISABLE_AFTER_FAILURES")
+            .ok()
+            .map(|value| value.parse())
+            .transpose()?
+            .unwrap_or(20),
+    };
+
+    if webhook_dispatch.batch_size < 1
+        || webhook_dispatch.retry_policy.max_attempts < 1
+        || webhook_dispatch.disable_after_failures < 1
+    {
+        bail!(
+            "WEBHOOK_BATCH_SIZE, WEBHOOK_MAX_ATTEMPTS and WEBHOOK_DISABLE_AFTER_FAILURES must be at least 1"
+        );
+    }
+
+    Ok(webhook_dispatch)
+}
+
+pub fn get_appointment_reminders_env() -> Result<AppointmentReminders> {
+    dotenvy::dotenv().ok();
+
+    let notifier = match std::env::var("REMINDER_NOTIFIER")
+        .unwrap_or("log".to_string())
+        .as_str()
+    {
+        "log" => ReminderNotifierKind::Log,
+        "file" => ReminderNotifierKind::File {
+            path: std::env::var("REMINDER_FILE_PATH")
+                .context("REMINDER_FILE_PATH is required when REMINDER_NOTIFIER=file")?,
+        },
+        notifier => bail!("Unknown REMINDER_NOTIFIER {notifier:?}, expected log or file"),
+    };
+
+    let schedule = match std::env::var("REMINDER_OFFSETS_MINUTES") {
+        Ok(offsets_minutes) => ReminderSchedule::new(
+            offsets_minutes
+                .split(',')
+                .map(|offset_minutes| offset_minutes.trim().parse())
+                .collect::<Result<Vec<i32>, _>>()
+                .context("REMINDER_OFFSETS_MINUTES must be a comma separated list of minutes")?,
+        ),
+        Err(_) => ReminderSchedule::default(),
+    };
+    if schedule
+        .offsets_minutes()
+        .iter()
+        .any(|offset_minutes| *offset_minutes < 1)
+    {
+        bail!("REMINDER_OFFSETS_MINUTES must all be at least 1");
+    }
+
+    let appointment_reminders = AppointmentReminders {
+        notifier,
+        schedule,
+        poll_interval_seconds: std::env::var("REMINDER_POLL_INTERVAL_SECONDS")
+            .ok()
+            .map(|value| value.parse())
+            .transpose()?
+            .unwrap_or(60),
+        batch_size: std::env::var("REMINDER_BATCH_SIZE")
+            .ok()
+            .map(|value| value.parse())
+            .transpose()?
+            .unwrap_or(100),
+        lease_seconds: std::env::var("REMINDER_LEASE_SECONDS")
+            .ok()
+            .map(|value| value.parse())
+            .transpose()?
+            .unwrap_or(300),
+    };
+
+    if appointment_reminders.batch_size < 1 || appointment_reminders.lease_seconds < 1 {
+        bail!("REMINDER_BATCH_SIZE and REMINDER_LEASE_SECONDS must be at least 1");
+    }
+
+    Ok(appointment_reminders)
+}
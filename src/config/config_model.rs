@@ -1,8 +1,14 @@
+use crate::domain::value_objects::{
+    booking_policy::BookingPolicy, outbox_model::OutboxRetryPolicy,
+    reminder_model::ReminderSchedule,
+};
+
 #[derive(Debug, Clone)]
 pub struct DotEnvyConfig {
     pub server: Server,
     pub frontend: Frontend,
     pub database: Database,
+    pub booking_policy: BookingPolicy,
 }
 
 #[derive(Debug, Clone)]
@@ -35,3 +41,71 @@ pub struct DoctorsSecret {
     pub secret: String,
     pub refresh_secret: String,
 }
+
+/// Only set when staff tokens are signed with a shared secret.
+#[derive(Debug, Clone)]
+pub struct StaffSecret {
+    pub secret: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct JwtVerification {
+    /// Public keys of asymmetrically signed access tokens. The HMAC secrets of
+    /// patients and doctors are used when it is not set.
+    pub jwks: Option<JwksSource>,
+    pub jwks_cache_seconds: u64,
+    pub issuer: Option<String>,
+    pub audience: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JwksSource {
+    File(String),
+    Url(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct OutboxDispatch {
+    pub sink: OutboxSinkKind,
+    pub poll_interval_ms: u64,
+    pub batch_size: i64,
+    /// How long a claimed event is held before another dispatcher may take it.
+    pub lease_seconds: i64,
+    pub retry_policy: OutboxRetryPolicy,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OutboxSinkKind {
+    Log,
+    Webhook { url: String, timeout_seconds: u64 },
+    PgNotify { channel: String },
+}
+
+#[derive(Debug, Clone)]
+pub struct WebhookDispatch {
+    pub poll_interval_ms: u64,
+    pub batch_size: i64,
+    /// How long a claimed delivery is held before another dispatcher may take it.
+    pub lease_seconds: i64,
+    pub timeout_seconds: u64,
+    pub retry_policy: OutboxRetryPolicy,
+    /// Failed attempts in a row after which a subscription is disabled.
+    pub disable_after_failures: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct AppointmentReminders {
+    pub notifier: ReminderNotifierKind,
+    pub schedule: ReminderSchedule,
+    pub poll_interval_seconds: u64,
+    /// Most reminders sent per offset in one pass.
+    pub batch_size: i64,
+    /// How long a claimed reminder may go unsent before it is claimed again.
+    pub lease_seconds: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReminderNotifierKind {
+    Log,
+    File { path: String },
+}
@@ -0,0 +1,27 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::infrastructure::postgres::schema::appointment_reminders;
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = appointment_reminders)]
+pub struct AppointmentReminderEntity {
+    pub id: Uuid,
+    pub appointment_id: Uuid,
+    pub offset_minutes: i32,
+    pub start_time: DateTime<Utc>,
+    pub created_at: NaiveDateTime,
+    pub sent_at: Option<NaiveDateTime>,
+    pub lease_until: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable, Queryable)]
+#[diesel(table_name = appointment_reminders)]
+pub struct AddAppointmentReminderEntity {
+    pub appointment_id: Uuid,
+    pub offset_minutes: i32,
+    pub start_time: DateTime<Utc>,
+    pub created_at: NaiveDateTime,
+    pub lease_until: NaiveDateTime,
+}
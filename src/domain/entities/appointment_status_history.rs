@@ -0,0 +1,34 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::infrastructure::postgres::schema::appointment_status_history;
+
+#[derive(Debug, Clone, Identifiable, Selectable, Serialize, Deserialize, Queryable, ToSchema)]
+#[diesel(table_name = appointment_status_history)]
+pub struct AppointmentStatusHistoryEntity {
+    pub id: Uuid,
+    pub appointment_id: Uuid,
+    pub action: String,
+    pub previous_status: Option<String>,
+    pub new_status: Option<String>,
+    pub actor_role: String,
+    pub actor_id: i32,
+    pub note: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable, Queryable)]
+#[diesel(table_name = appointment_status_history)]
+pub struct AddAppointmentStatusHistoryEntity {
+    pub appointment_id: Uuid,
+    pub action: String,
+    pub previous_status: Option<String>,
+    pub new_status: Option<String>,
+    pub actor_role: String,
+    pub actor_id: i32,
+    pub note: Option<String>,
+    pub created_at: NaiveDateTime,
+}
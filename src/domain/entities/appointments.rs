@@ -1,4 +1,4 @@
-use chrono::NaiveDateTime;
+use chrono::{DateTime, NaiveDateTime, Utc};
 use diesel::prelude::*;
 use uuid::Uuid;
 
@@ -36,4 +36,31 @@ pub struct EditAppointmentEntity {
 pub struct RescheduleAppointmentEntity {
     pub slot_id: Uuid,
     pub updated_at: NaiveDateTime,
-}
\ No newline at end of file
+}
+/// Cancellation details are cleared when the new status holds a slot place again.
+#[derive(Debug, Clone, Queryable, AsChangeset)]
+#[diesel(table_name = appointments, treat_none_as_null = true)]
+pub struct ChangeAppointmentStatusEntity {
+    pub status: String,
+    pub cancellation_reason: Option<String>,
+    pub cancelled_at: Option<NaiveDateTime>,
+    pub updated_at: NaiveDateTime,
+}
+
+/// An appointment that still holds a place in its slot.
+#[derive(Debug, Clone, Queryable)]
+pub struct SlotAppointmentEntity {
+    pub id: Uuid,
+    pub patient_id: i32,
+    pub status: String,
+}
+
+/// A patient's open appointment together with the slot it is booked in.
+#[derive(Debug, Clone, Queryable)]
+pub struct PatientOpenAppointmentEntity {
+    pub id: Uuid,
+    pub slot_id: Uuid,
+    pub doctor_id: i32,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+}
@@ -0,0 +1,28 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::infrastructure::postgres::schema::doctor_booking_windows;
+
+#[derive(Debug, Clone, Identifiable, Selectable, Serialize, Deserialize, Queryable, ToSchema)]
+#[diesel(table_name = doctor_booking_windows, primary_key(doctor_id))]
+pub struct DoctorBookingWindowEntity {
+    pub doctor_id: i32,
+    pub min_booking_lead_minutes: Option<i32>,
+    pub max_booking_horizon_days: Option<i32>,
+    pub min_change_notice_minutes: Option<i32>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Unset fields fall back to the global booking window.
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = doctor_booking_windows)]
+pub struct SetDoctorBookingWindowEntity {
+    pub doctor_id: i32,
+    pub min_booking_lead_minutes: Option<i32>,
+    pub max_booking_horizon_days: Option<i32>,
+    pub min_change_notice_minutes: Option<i32>,
+    pub updated_at: NaiveDateTime,
+}
@@ -0,0 +1,23 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::infrastructure::postgres::schema::doctor_timezones;
+
+#[derive(Debug, Clone, Identifiable, Selectable, Serialize, Deserialize, Queryable, ToSchema)]
+#[diesel(table_name = doctor_timezones, primary_key(doctor_id))]
+pub struct DoctorTimezoneEntity {
+    pub doctor_id: i32,
+    pub timezone: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = doctor_timezones)]
+pub struct SetDoctorTimezoneEntity {
+    pub doctor_id: i32,
+    pub timezone: String,
+    pub updated_at: NaiveDateTime,
+}
@@ -0,0 +1,28 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::infrastructure::postgres::schema::idempotency_keys;
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = idempotency_keys)]
+pub struct IdempotencyKeyEntity {
+    pub id: Uuid,
+    pub patient_id: i32,
+    pub operation: String,
+    pub idempotency_key: String,
+    pub request_hash: String,
+    pub response: serde_json::Value,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable, Queryable)]
+#[diesel(table_name = idempotency_keys)]
+pub struct AddIdempotencyKeyEntity {
+    pub patient_id: i32,
+    pub operation: String,
+    pub idempotency_key: String,
+    pub request_hash: String,
+    pub response: serde_json::Value,
+    pub created_at: NaiveDateTime,
+}
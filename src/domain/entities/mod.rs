@@ -1,3 +1,15 @@
+pub mod appointment_reminders;
+pub mod appointment_status_history;
 pub mod appointments;
+pub mod doctor_booking_windows;
+pub mod doctor_timezones;
+pub mod idempotency_keys;
+pub mod outbox_events;
+pub mod service_api_keys;
+pub mod slot_templates;
 pub mod slots;
+pub mod staff_actions;
+pub mod waitlist_entries;
+pub mod webhook_deliveries;
+pub mod webhook_subscriptions;
 pub mod schedule_view;
\ No newline at end of file
@@ -1,4 +1,4 @@
-use chrono::NaiveDateTime;
+use chrono::{DateTime, NaiveDateTime, Utc};
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -17,6 +17,8 @@ pub struct ScheduleViewEntity {
     pub patient_is_partner_hiv_positive: String,
     pub status: String,
     pub doctor_id: i32,
-    pub start_time: NaiveDateTime,
-    pub end_time: NaiveDateTime,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub cancellation_reason: Option<String>,
+    pub cancelled_at: Option<NaiveDateTime>,
 }
@@ -0,0 +1,53 @@
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::infrastructure::postgres::schema::slot_templates;
+
+#[derive(Debug, Clone, Identifiable, Selectable, Serialize, Deserialize, Queryable, ToSchema)]
+#[diesel(table_name = slot_templates)]
+pub struct SlotTemplateEntity {
+    pub id: Uuid,
+    pub doctor_id: i32,
+    pub weekdays: String,
+    pub day_start_time: NaiveTime,
+    pub day_end_time: NaiveTime,
+    pub slot_duration_minutes: i32,
+    pub max_appointment_count: i32,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Insertable, Queryable)]
+#[diesel(table_name = slot_templates)]
+pub struct AddSlotTemplateEntity {
+    pub doctor_id: i32,
+    pub weekdays: String,
+    pub day_start_time: NaiveTime,
+    pub day_end_time: NaiveTime,
+    pub slot_duration_minutes: i32,
+    pub max_appointment_count: i32,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Queryable, AsChangeset)]
+#[diesel(table_name = slot_templates)]
+pub struct EditSlotTemplateEntity {
+    pub weekdays: String,
+    pub day_start_time: NaiveTime,
+    pub day_end_time: NaiveTime,
+    pub slot_duration_minutes: i32,
+    pub max_appointment_count: i32,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub updated_at: NaiveDateTime,
+}
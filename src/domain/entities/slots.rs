@@ -1,4 +1,4 @@
-use chrono::NaiveDateTime;
+use chrono::{DateTime, NaiveDateTime, Utc};
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -13,11 +13,12 @@ pub struct SlotEntity {
     pub doctor_id: i32,
     pub current_appointment_count: i32,
     pub max_appointment_count: i32,
-    pub start_time: NaiveDateTime,
-    pub end_time: NaiveDateTime,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
     pub deleted_at: Option<NaiveDateTime>,
+    pub template_id: Option<Uuid>,
 }
 
 #[derive(Debug, Clone, Insertable, Queryable)]
@@ -26,17 +27,34 @@ pub struct AddSlotEntity {
     pub doctor_id: i32,
     pub current_appointment_count: i32,
     pub max_appointment_count: i32,
-    pub start_time: NaiveDateTime,
-    pub end_time: NaiveDateTime,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
     pub deleted_at: Option<NaiveDateTime>,
+    pub template_id: Option<Uuid>,
 }
 
 #[derive(Debug, Clone, Queryable, AsChangeset)]
 #[diesel(table_name = slots)]
 pub struct EditSlotEntity {
     pub max_appointment_count: Option<i32>,
-    pub end_time: Option<NaiveDateTime>,
+    pub end_time: Option<DateTime<Utc>>,
     pub updated_at: NaiveDateTime,
 }
+
+/// A slot whose stored appointment count differs from the appointments that
+/// actually hold a place in it.
+#[derive(Debug, Clone, QueryableByName)]
+pub struct SlotAppointmentCountDriftEntity {
+    #[diesel(sql_type = diesel::sql_types::Uuid)]
+    pub slot_id: Uuid,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub doctor_id: i32,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub max_appointment_count: i32,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub current_appointment_count: i32,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub actual_appointment_count: i32,
+}
@@ -0,0 +1,26 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::infrastructure::postgres::schema::staff_actions;
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = staff_actions)]
+pub struct StaffActionEntity {
+    pub id: Uuid,
+    pub staff_id: i32,
+    pub action: String,
+    pub target_id: Uuid,
+    pub note: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable, Queryable)]
+#[diesel(table_name = staff_actions)]
+pub struct AddStaffActionEntity {
+    pub staff_id: i32,
+    pub action: String,
+    pub target_id: Uuid,
+    pub note: Option<String>,
+    pub created_at: NaiveDateTime,
+}
@@ -0,0 +1,34 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::infrastructure::postgres::schema::waitlist_entries;
+
+#[derive(Debug, Clone, Identifiable, Selectable, Serialize, Deserialize, Queryable, ToSchema)]
+#[diesel(table_name = waitlist_entries)]
+pub struct WaitlistEntryEntity {
+    pub id: Uuid,
+    pub slot_id: Uuid,
+    pub patient_id: i32,
+    pub patient_abnormal_symptom: String,
+    pub patient_is_missed_medication: String,
+    pub patient_blood_test_status: String,
+    pub patient_is_overdue_medication: String,
+    pub patient_is_partner_hiv_positive: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable, Queryable)]
+#[diesel(table_name = waitlist_entries)]
+pub struct AddWaitlistEntryEntity {
+    pub slot_id: Uuid,
+    pub patient_id: i32,
+    pub patient_abnormal_symptom: String,
+    pub patient_is_missed_medication: String,
+    pub patient_blood_test_status: String,
+    pub patient_is_overdue_medication: String,
+    pub patient_is_partner_hiv_positive: String,
+    pub created_at: NaiveDateTime,
+}
@@ -0,0 +1,34 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::infrastructure::postgres::schema::webhook_deliveries;
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = webhook_deliveries)]
+pub struct WebhookDeliveryEntity {
+    pub id: Uuid,
+    pub subscription_id: Uuid,
+    pub outbox_event_id: Uuid,
+    pub event_type: String,
+    pub body: serde_json::Value,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: NaiveDateTime,
+    pub last_response_status: Option<i32>,
+    pub last_error: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub delivered_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Insertable, Queryable)]
+#[diesel(table_name = webhook_deliveries)]
+pub struct AddWebhookDeliveryEntity {
+    pub subscription_id: Uuid,
+    pub outbox_event_id: Uuid,
+    pub event_type: String,
+    pub body: serde_json::Value,
+    pub status: String,
+    pub next_attempt_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
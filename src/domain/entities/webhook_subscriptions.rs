@@ -0,0 +1,46 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::infrastructure::postgres::schema::webhook_subscriptions;
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = webhook_subscriptions)]
+pub struct WebhookSubscriptionEntity {
+    pub id: Uuid,
+    pub service_api_key_id: i32,
+    pub url: String,
+    pub event_types: Vec<String>,
+    pub secret: String,
+    pub consecutive_failures: i32,
+    pub disabled_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub doctor_ids: Vec<i32>,
+}
+
+#[derive(Debug, Clone, Insertable, Queryable)]
+#[diesel(table_name = webhook_subscriptions)]
+pub struct AddWebhookSubscriptionEntity {
+    pub service_api_key_id: i32,
+    pub url: String,
+    pub event_types: Vec<String>,
+    pub doctor_ids: Vec<i32>,
+    pub secret: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// `disabled_at` is only changed when the subscription is enabled or disabled
+/// by hand, `Some(None)` enables it again.
+#[derive(Debug, Clone, Queryable, AsChangeset)]
+#[diesel(table_name = webhook_subscriptions)]
+pub struct EditWebhookSubscriptionEntity {
+    pub url: Option<String>,
+    pub event_types: Option<Vec<String>>,
+    pub doctor_ids: Option<Vec<i32>>,
+    pub disabled_at: Option<Option<NaiveDateTime>>,
+    pub consecutive_failures: Option<i32>,
+    pub updated_at: NaiveDateTime,
+}
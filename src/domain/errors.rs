@@ -0,0 +1,123 @@
+use std::fmt;
+
+/// Errors returned by the repository traits and use cases.
+///
+/// Every variant carries a human readable message and maps to a stable,
+/// machine readable `code()` that clients can match on.
+#[derive(Debug)]
+pub enum DomainError {
+    NotFound(String),
+    Conflict(String),
+    SlotFull(String),
+    SlotOverlap(String),
+    InvalidTransition(String),
+    Unauthorized(String),
+    Forbidden(String),
+    PastTime(String),
+    BookingCutoff(String),
+    BookingHorizon(String),
+    ChangeNotice(String),
+    Validation(String),
+    Internal(anyhow::Error),
+}
+
+pub type DomainResult<T> = Result<T, DomainError>;
+
+impl DomainError {
+    pub fn not_found(message: impl Into<String>) -> Self {
+        DomainError::NotFound(message.into())
+    }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        DomainError::Conflict(message.into())
+    }
+
+    pub fn slot_full(message: impl Into<String>) -> Self {
+        DomainError::SlotFull(message.into())
+    }
+
+    pub fn slot_overlap(message: impl Into<String>) -> Self {
+        DomainError::SlotOverlap(message.into())
+    }
+
+    pub fn invalid_transition(message: impl Into<String>) -> Self {
+        DomainError::InvalidTransition(message.into())
+    }
+
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        DomainError::Unauthorized(message.into())
+    }
+
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        DomainError::Forbidden(message.into())
+    }
+
+    pub fn past_time(message: impl Into<String>) -> Self {
+        DomainError::PastTime(message.into())
+    }
+
+    pub fn booking_cutoff(message: impl Into<String>) -> Self {
+        DomainError::BookingCutoff(message.into())
+    }
+
+    pub fn booking_horizon(message: impl Into<String>) -> Self {
+        DomainError::BookingHorizon(message.into())
+    }
+
+    pub fn change_notice(message: impl Into<String>) -> Self {
+        DomainError::ChangeNotice(message.into())
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        DomainError::Validation(message.into())
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            DomainError::NotFound(_) => "NOT_FOUND",
+            DomainError::Conflict(_) => "CONFLICT",
+            DomainError::SlotFull(_) => "SLOT_FULL",
+            DomainError::SlotOverlap(_) => "SLOT_OVERLAP",
+            DomainError::InvalidTransition(_) => "INVALID_TRANSITION",
+            DomainError::Unauthorized(_) => "UNAUTHORIZED",
+            DomainError::Forbidden(_) => "FORBIDDEN",
+            DomainError::PastTime(_) => "PAST_TIME",
+            DomainError::BookingCutoff(_) => "BOOKING_CUTOFF_PASSED",
+            DomainError::BookingHorizon(_) => "BOOKING_HORIZON_EXCEEDED",
+            DomainError::ChangeNotice(_) => "CHANGE_NOTICE_TOO_SHORT",
+            DomainError::Validation(_) => "VALIDATION_ERROR",
+            DomainError::Internal(_) => "INTERNAL_ERROR",
+        }
+    }
+}
+
+impl fmt::Display for DomainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DomainError::NotFound(message)
+            | DomainError::Conflict(message)
+            | DomainError::SlotFull(message)
+            | DomainError::SlotOverlap(message)
+            | DomainError::InvalidTransition(message)
+            | DomainError::Unauthorized(message)
+            | DomainError::Forbidden(message)
+            | DomainError::PastTime(message)
+            | DomainError::BookingCutoff(message)
+            | DomainError::BookingHorizon(message)
+            | DomainError::ChangeNotice(message)
+            | DomainError::Validation(message) => write!(f, "{}", message),
+            DomainError::Internal(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for DomainError {}
+
+impl From<anyhow::Error> for DomainError {
+    fn from(e: anyhow::Error) -> Self {
+        match e.downcast::<DomainError>() {
+            Ok(domain_error) => domain_error,
+            Err(e) => DomainError::Internal(e),
+        }
+    }
+}
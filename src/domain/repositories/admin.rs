@@ -0,0 +1,57 @@
+use chrono_tz::Tz;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{
+        appointments::{AddAppointmentEntity, EditAppointmentEntity, RescheduleAppointmentEntity},
+        schedule_view::ScheduleViewEntity,
+        slots::EditSlotEntity,
+    },
+    errors::DomainResult,
+    value_objects::{
+        admin_model::AppointmentFilter,
+        appointment_model::{AppointmentTransition, TransitionAppointmentResponseModel},
+    },
+};
+
+/// Clinic staff acting on appointments and slots of any patient or doctor.
+/// Every change is recorded in the staff audit trail under `staff_id`.
+pub trait AdminRepository {
+    /// The time zone wall-clock times of the doctor owning `slot_id` are read in.
+    async fn get_slot_doctor_timezone(&self, slot_id: Uuid) -> DomainResult<Tz>;
+    async fn add_appointment(
+        &self,
+        add_appointment_entity: AddAppointmentEntity,
+        staff_id: i32,
+    ) -> DomainResult<Uuid>;
+    /// Moves the appointment first when `reschedule_appointment_entity` is set,
+    /// then edits its details, all in one transaction.
+    async fn edit_appointment(
+        &self,
+        appointment_id: Uuid,
+        reschedule_appointment_entity: Option<RescheduleAppointmentEntity>,
+        edit_appointment_entity: EditAppointmentEntity,
+        staff_id: i32,
+    ) -> DomainResult<Uuid>;
+    async fn cancel_appointment(
+        &self,
+        appointment_id: Uuid,
+        staff_id: i32,
+        appointment_transition: AppointmentTransition,
+    ) -> DomainResult<TransitionAppointmentResponseModel>;
+    /// Puts a removed appointment back into its slot.
+    async fn restore_appointment(&self, appointment_id: Uuid, staff_id: i32) -> DomainResult<Uuid>;
+    /// Returns one page of appointments of every doctor with the total count.
+    async fn get_appointments(
+        &self,
+        appointment_filter: AppointmentFilter,
+    ) -> DomainResult<(Vec<ScheduleViewEntity>, i64)>;
+    async fn edit_slot(
+        &self,
+        slot_id: Uuid,
+        edit_slot_entity: EditSlotEntity,
+        staff_id: i32,
+    ) -> DomainResult<Uuid>;
+    /// Puts a removed slot back.
+    async fn restore_slot(&self, slot_id: Uuid, staff_id: i32) -> DomainResult<Uuid>;
+}
@@ -1,7 +1,20 @@
-use anyhow::Result;
 use uuid::Uuid;
+
+use crate::domain::{
+    errors::DomainResult,
+    value_objects::{
+        actor::Actor,
+        appointment_model::{AppointmentTransition, TransitionAppointmentResponseModel},
+    },
+};
+
 pub trait AppointmentLedgerRepository {
-    async fn to_ready(&self, appointment_id: Uuid) -> Result<Uuid>;
-    async fn to_waiting_for_prescription(&self, appointment_id: Uuid) -> Result<Uuid>;
-    async fn to_completed(&self, appointment_id: Uuid) -> Result<Uuid>;
+    /// Changes the status of an appointment in one of the actor's slots, or of
+    /// any appointment when the actor is a service.
+    async fn transition(
+        &self,
+        appointment_id: Uuid,
+        actor: Actor,
+        appointment_transition: AppointmentTransition,
+    ) -> DomainResult<TransitionAppointmentResponseModel>;
 }
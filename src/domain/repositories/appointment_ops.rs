@@ -1,23 +1,31 @@
-use anyhow::Result;
 use uuid::Uuid;
 
-use crate::domain::entities::appointments::{
-    AddAppointmentEntity, EditAppointmentEntity, RescheduleAppointmentEntity,
+use crate::domain::{
+    entities::appointments::{
+        AddAppointmentEntity, EditAppointmentEntity, RescheduleAppointmentEntity,
+    },
+    errors::DomainResult,
+    value_objects::idempotency_model::IdempotencyKey,
 };
 
 pub trait AppointmentOpsRepository {
-    async fn add(&self, add_appointment_entity: AddAppointmentEntity) -> Result<Uuid>;
+    async fn add(
+        &self,
+        add_appointment_entity: AddAppointmentEntity,
+        idempotency_key: Option<IdempotencyKey>,
+    ) -> DomainResult<Uuid>;
     async fn edit(
         &self,
         appointment_id: Uuid,
         patient_id: i32,
         edit_appointment_entity: EditAppointmentEntity,
-    ) -> Result<Uuid>;
+    ) -> DomainResult<Uuid>;
     async fn reschedule(
         &self,
         appointment_id: Uuid,
         patient_id: i32,
         reschedule_appointment_entity: RescheduleAppointmentEntity,
-    ) -> Result<Uuid>;
-    async fn remove(&self, appointment_id: Uuid, patient_id: i32) -> Result<()>;
+        idempotency_key: Option<IdempotencyKey>,
+    ) -> DomainResult<Uuid>;
+    async fn remove(&self, appointment_id: Uuid, patient_id: i32) -> DomainResult<()>;
 }
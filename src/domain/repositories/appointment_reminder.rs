@@ -0,0 +1,45 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{
+        appointment_reminders::{AddAppointmentReminderEntity, AppointmentReminderEntity},
+        schedule_view::ScheduleViewEntity,
+    },
+    errors::DomainResult,
+    value_objects::reminder_model::AppointmentReminderModel,
+};
+
+pub trait AppointmentReminderRepository {
+    /// Open appointments starting after `after` and no later than `until`
+    /// that have no reminder for `offset_minutes` at their current start time,
+    /// or only one whose lease ran out at `current_time` before it was sent.
+    async fn get_due(
+        &self,
+        offset_minutes: i32,
+        after: DateTime<Utc>,
+        until: DateTime<Utc>,
+        current_time: NaiveDateTime,
+        limit: i64,
+    ) -> DomainResult<Vec<ScheduleViewEntity>>;
+
+    /// Records a reminder before it is sent, or takes over one that was not
+    /// sent before its lease ran out. Returns `None` when it was sent or is
+    /// still leased, by an earlier run or another instance.
+    async fn claim(
+        &self,
+        add_appointment_reminder_entity: AddAppointmentReminderEntity,
+    ) -> DomainResult<Option<AppointmentReminderEntity>>;
+
+    async fn mark_sent(&self, id: Uuid, current_time: NaiveDateTime) -> DomainResult<()>;
+
+    /// Forgets a claimed reminder whose notifier failed, so it is tried again.
+    async fn release(&self, id: Uuid) -> DomainResult<()>;
+}
+
+/// Where reminders are sent to. A reminder is sent once, unless the process
+/// stops between claiming and sending it. One that returns an error is tried
+/// again on the next pass.
+pub trait ReminderNotifier {
+    async fn notify(&self, appointment_reminder: &AppointmentReminderModel) -> DomainResult<()>;
+}
@@ -0,0 +1,15 @@
+use crate::domain::{
+    entities::doctor_booking_windows::SetDoctorBookingWindowEntity, errors::DomainResult,
+    value_objects::booking_window::GetDoctorBookingWindowResponseModel,
+};
+
+pub trait BookingWindowRepository {
+    async fn get_doctor_booking_window(
+        &self,
+        doctor_id: i32,
+    ) -> DomainResult<GetDoctorBookingWindowResponseModel>;
+    async fn set_doctor_booking_window(
+        &self,
+        set_doctor_booking_window_entity: SetDoctorBookingWindowEntity,
+    ) -> DomainResult<GetDoctorBookingWindowResponseModel>;
+}
@@ -0,0 +1,15 @@
+use crate::domain::{
+    entities::doctor_timezones::SetDoctorTimezoneEntity, errors::DomainResult,
+    value_objects::timezone_model::GetDoctorTimezoneResponseModel,
+};
+
+pub trait DoctorTimezoneRepository {
+    async fn get_doctor_timezone(
+        &self,
+        doctor_id: i32,
+    ) -> DomainResult<GetDoctorTimezoneResponseModel>;
+    async fn set_doctor_timezone(
+        &self,
+        set_doctor_timezone_entity: SetDoctorTimezoneEntity,
+    ) -> DomainResult<GetDoctorTimezoneResponseModel>;
+}
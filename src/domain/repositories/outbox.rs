@@ -0,0 +1,35 @@
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::outbox_events::OutboxEventEntity, errors::DomainResult,
+    value_objects::outbox_model::OutboxMessage,
+};
+
+pub trait OutboxRepository {
+    /// Takes up to `limit` due events for delivery. They are not handed out
+    /// again before `lease_until`, unless they are marked failed sooner.
+    async fn claim_due(
+        &self,
+        limit: i64,
+        current_time: NaiveDateTime,
+        lease_until: NaiveDateTime,
+    ) -> DomainResult<Vec<OutboxEventEntity>>;
+
+    async fn mark_dispatched(&self, id: Uuid, current_time: NaiveDateTime) -> DomainResult<()>;
+
+    /// `next_attempt_at` is `None` once the event is given up on.
+    async fn mark_failed(
+        &self,
+        id: Uuid,
+        error: &str,
+        next_attempt_at: Option<NaiveDateTime>,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<()>;
+}
+
+/// Where outbox events are delivered to. A delivery that returns `Ok` is never
+/// tried again, any error is retried with backoff.
+pub trait OutboxSink {
+    async fn deliver(&self, outbox_message: &OutboxMessage) -> DomainResult<()>;
+}
@@ -1,7 +1,24 @@
-use crate::domain::entities::schedule_view::ScheduleViewEntity;
-use anyhow::Result;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{
+        appointment_status_history::AppointmentStatusHistoryEntity,
+        schedule_view::ScheduleViewEntity,
+    },
+    errors::DomainResult,
+    value_objects::actor::Actor,
+};
 
 pub trait ScheduleViewingRepository {
-    async fn get_patient_schedules(&self, patient_id: i32) -> Result<Vec<ScheduleViewEntity>>;
-    async fn get_doctor_schedules(&self, doctor_id: i32) -> Result<Vec<ScheduleViewEntity>>;
+    async fn get_patient_schedules(&self, patient_id: i32)
+    -> DomainResult<Vec<ScheduleViewEntity>>;
+    async fn get_doctor_schedules(&self, doctor_id: i32) -> DomainResult<Vec<ScheduleViewEntity>>;
+    /// Any appointment, whoever its patient and doctor are.
+    async fn get_schedule(&self, appointment_id: Uuid) -> DomainResult<ScheduleViewEntity>;
+    /// History of an appointment the actor is the patient or the slot doctor of.
+    async fn get_appointment_history(
+        &self,
+        appointment_id: Uuid,
+        actor: Actor,
+    ) -> DomainResult<Vec<AppointmentStatusHistoryEntity>>;
 }
@@ -0,0 +1,16 @@
+use chrono::NaiveDateTime;
+
+use crate::domain::{
+    entities::service_api_keys::{AddServiceApiKeyEntity, ServiceApiKeyEntity},
+    errors::DomainResult,
+};
+
+pub trait ServiceApiKeyRepository {
+    async fn add(&self, add_service_api_key_entity: AddServiceApiKeyEntity) -> DomainResult<i32>;
+    /// The key with `key_prefix`, unless it has been revoked.
+    async fn get_active_by_key_prefix(
+        &self,
+        key_prefix: &str,
+    ) -> DomainResult<Option<ServiceApiKeyEntity>>;
+    async fn revoke(&self, key_prefix: &str, current_time: NaiveDateTime) -> DomainResult<()>;
+}
@@ -1,16 +1,48 @@
-use anyhow::Result;
-use async_trait::async_trait;
+use chrono_tz::Tz;
 use uuid::Uuid;
 
-use crate::domain::entities::slots::{AddSlotEntity, EditSlotEntity};
+use crate::domain::{
+    entities::slots::{AddSlotEntity, EditSlotEntity},
+    errors::DomainResult,
+    value_objects::slot_model::{
+        AffectedAppointmentModel, BulkMode, BulkSlotOutcome, SlotCascadeAction,
+    },
+};
 
 pub trait SlotOpsRepository {
-    async fn add(&self, add_slot_entity: AddSlotEntity) -> Result<Uuid>;
+    /// The time zone wall-clock slot times of the doctor are read in.
+    async fn get_doctor_timezone(&self, doctor_id: i32) -> DomainResult<Tz>;
+    async fn add(&self, add_slot_entity: AddSlotEntity) -> DomainResult<Uuid>;
+    /// Adds many slots in one transaction, returning one outcome per entity in input order.
+    /// An all-or-nothing batch is rolled back when `has_invalid_items`, i.e.
+    /// some items of the request were left out as invalid.
+    async fn add_bulk(
+        &self,
+        add_slot_entities: Vec<AddSlotEntity>,
+        mode: BulkMode,
+        has_invalid_items: bool,
+    ) -> DomainResult<Vec<BulkSlotOutcome>>;
     async fn edit(
         &self,
         slot_id: Uuid,
         doctor_id: i32,
         edit_slot_entity: EditSlotEntity,
-    ) -> Result<Uuid>;
-    async fn remove(&self, slot_id: Uuid, doctor_id: i32) -> Result<()>;
+    ) -> DomainResult<Uuid>;
+    async fn remove(&self, slot_id: Uuid, doctor_id: i32) -> DomainResult<()>;
+    /// Lowers the capacity of a booked slot, applying `cascade_action` to the
+    /// appointments that no longer fit.
+    async fn force_shrink(
+        &self,
+        slot_id: Uuid,
+        doctor_id: i32,
+        edit_slot_entity: EditSlotEntity,
+        cascade_action: SlotCascadeAction,
+    ) -> DomainResult<Vec<AffectedAppointmentModel>>;
+    /// Removes a booked slot, applying `cascade_action` to all of its appointments.
+    async fn force_remove(
+        &self,
+        slot_id: Uuid,
+        doctor_id: i32,
+        cascade_action: SlotCascadeAction,
+    ) -> DomainResult<Vec<AffectedAppointmentModel>>;
 }
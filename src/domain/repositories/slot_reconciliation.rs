@@ -0,0 +1,10 @@
+use crate::domain::{
+    errors::DomainResult, value_objects::slot_reconciliation_model::SlotReconciliationReportModel,
+};
+
+pub trait SlotReconciliationRepository {
+    async fn reconcile_appointment_counts(
+        &self,
+        repair: bool,
+    ) -> DomainResult<SlotReconciliationReportModel>;
+}
@@ -0,0 +1,45 @@
+use chrono::{DateTime, FixedOffset};
+use chrono_tz::Tz;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::slot_templates::{AddSlotTemplateEntity, EditSlotTemplateEntity, SlotTemplateEntity},
+    errors::DomainResult,
+    value_objects::slot_template_model::{
+        MaterializeSlotTemplateResponseModel, SlotOccurrenceModel,
+    },
+};
+
+pub trait SlotTemplateRepository {
+    /// The time zone the doctor's templates are laid out in.
+    async fn get_doctor_timezone(&self, doctor_id: i32) -> DomainResult<Tz>;
+    async fn get_doctor_slot_templates(
+        &self,
+        doctor_id: i32,
+    ) -> DomainResult<Vec<SlotTemplateEntity>>;
+    async fn get_slot_template(
+        &self,
+        template_id: Uuid,
+        doctor_id: i32,
+    ) -> DomainResult<SlotTemplateEntity>;
+    async fn preview(
+        &self,
+        doctor_id: i32,
+        slot_times: Vec<(DateTime<FixedOffset>, DateTime<FixedOffset>)>,
+    ) -> DomainResult<Vec<SlotOccurrenceModel>>;
+    async fn add(
+        &self,
+        add_slot_template_entity: AddSlotTemplateEntity,
+        slot_times: Vec<(DateTime<FixedOffset>, DateTime<FixedOffset>)>,
+    ) -> DomainResult<MaterializeSlotTemplateResponseModel>;
+    /// Updates the template, removes its unbooked future slots and regenerates them.
+    async fn edit(
+        &self,
+        template_id: Uuid,
+        doctor_id: i32,
+        edit_slot_template_entity: EditSlotTemplateEntity,
+        slot_times: Vec<(DateTime<FixedOffset>, DateTime<FixedOffset>)>,
+    ) -> DomainResult<MaterializeSlotTemplateResponseModel>;
+    /// Removes the template and its unbooked future slots, returning how many slots were removed.
+    async fn remove(&self, template_id: Uuid, doctor_id: i32) -> DomainResult<usize>;
+}
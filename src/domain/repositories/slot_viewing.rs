@@ -1,8 +1,9 @@
-use anyhow::Result;
-
-use crate::domain::entities::slots::SlotEntity;
+use crate::domain::{
+    entities::slots::SlotEntity, errors::DomainResult, value_objects::slot_model::SlotFilter,
+};
 
 pub trait SlotViewingRepository {
-    async fn get_slots(&self) -> Result<Vec<SlotEntity>>;
-    async fn get_doctor_slots(&self, doctor_id: i32) -> Result<Vec<SlotEntity>>;
+    /// Returns one page of slots matching `slot_filter` and the total number of matches.
+    async fn get_slots(&self, slot_filter: SlotFilter) -> DomainResult<(Vec<SlotEntity>, i64)>;
+    async fn get_doctor_slots(&self, doctor_id: i32) -> DomainResult<Vec<SlotEntity>>;
 }
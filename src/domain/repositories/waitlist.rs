@@ -0,0 +1,20 @@
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::waitlist_entries::AddWaitlistEntryEntity,
+    errors::DomainResult,
+    value_objects::waitlist_model::{JoinWaitlistResponseModel, WaitlistPositionModel},
+};
+
+pub trait WaitlistRepository {
+    /// Puts the patient in line for a slot that is currently full.
+    async fn join(
+        &self,
+        add_waitlist_entry_entity: AddWaitlistEntryEntity,
+    ) -> DomainResult<JoinWaitlistResponseModel>;
+    async fn get_patient_waitlist(
+        &self,
+        patient_id: i32,
+    ) -> DomainResult<Vec<WaitlistPositionModel>>;
+    async fn leave(&self, waitlist_entry_id: Uuid, patient_id: i32) -> DomainResult<()>;
+}
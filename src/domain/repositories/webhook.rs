@@ -0,0 +1,94 @@
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{
+        webhook_deliveries::WebhookDeliveryEntity,
+        webhook_subscriptions::{
+            AddWebhookSubscriptionEntity, EditWebhookSubscriptionEntity, WebhookSubscriptionEntity,
+        },
+    },
+    errors::DomainResult,
+    value_objects::webhook_model::{WebhookAttemptModel, WebhookRequestModel},
+};
+
+/// Subscriptions are owned by the service API key that created them, every
+/// method but `add` returns `NotFound` for a subscription of another key.
+pub trait WebhookSubscriptionRepository {
+    async fn add(
+        &self,
+        add_webhook_subscription_entity: AddWebhookSubscriptionEntity,
+    ) -> DomainResult<WebhookSubscriptionEntity>;
+    async fn get_all_by_service_api_key_id(
+        &self,
+        service_api_key_id: i32,
+    ) -> DomainResult<Vec<WebhookSubscriptionEntity>>;
+    async fn edit(
+        &self,
+        id: Uuid,
+        service_api_key_id: i32,
+        edit_webhook_subscription_entity: EditWebhookSubscriptionEntity,
+    ) -> DomainResult<WebhookSubscriptionEntity>;
+    async fn remove(
+        &self,
+        id: Uuid,
+        service_api_key_id: i32,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<()>;
+    /// Most recent deliveries first.
+    async fn get_deliveries(
+        &self,
+        id: Uuid,
+        service_api_key_id: i32,
+        status: Option<String>,
+        limit: i64,
+    ) -> DomainResult<Vec<WebhookDeliveryEntity>>;
+    /// Puts a delivery back to `Pending` with its attempts reset, due right
+    /// away. `Conflict` while its subscription is disabled.
+    async fn redeliver(
+        &self,
+        delivery_id: Uuid,
+        service_api_key_id: i32,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<WebhookDeliveryEntity>;
+}
+
+pub trait WebhookDeliveryRepository {
+    /// Takes up to `limit` due deliveries of enabled subscriptions, together
+    /// with their subscription. They are not handed out again before
+    /// `lease_until`, unless a failure is recorded sooner.
+    async fn claim_due(
+        &self,
+        limit: i64,
+        current_time: NaiveDateTime,
+        lease_until: NaiveDateTime,
+    ) -> DomainResult<Vec<(WebhookDeliveryEntity, WebhookSubscriptionEntity)>>;
+
+    /// Also resets the consecutive failures of the subscription.
+    async fn record_success(
+        &self,
+        delivery_id: Uuid,
+        subscription_id: Uuid,
+        webhook_attempt: &WebhookAttemptModel,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<()>;
+
+    /// `next_attempt_at` is `None` once the delivery is given up on. The
+    /// subscription is disabled when it reaches `disable_after_failures`
+    /// consecutive failures, returns whether this failure disabled it.
+    async fn record_failure(
+        &self,
+        delivery_id: Uuid,
+        subscription_id: Uuid,
+        webhook_attempt: &WebhookAttemptModel,
+        next_attempt_at: Option<NaiveDateTime>,
+        disable_after_failures: i32,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<bool>;
+}
+
+/// Sends a signed delivery to its subscriber. Failures are reported in the
+/// returned attempt rather than as an error, so they can be logged.
+pub trait WebhookSender {
+    async fn send(&self, webhook_request: &WebhookRequestModel) -> WebhookAttemptModel;
+}
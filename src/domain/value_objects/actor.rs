@@ -0,0 +1,68 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub enum ActorRole {
+    Patient,
+    Doctor,
+    Staff,
+    /// Another MedBook service, by the id of its API key.
+    Service,
+}
+
+impl fmt::Display for ActorRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ActorRole::Patient => write!(f, "Patient"),
+            ActorRole::Doctor => write!(f, "Doctor"),
+            ActorRole::Staff => write!(f, "Staff"),
+            ActorRole::Service => write!(f, "Service"),
+        }
+    }
+}
+
+/// Who performed an action, as identified by the authorization middleware.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Actor {
+    pub role: ActorRole,
+    pub id: i32,
+}
+
+impl Actor {
+    pub fn patient(patient_id: i32) -> Self {
+        Self {
+            role: ActorRole::Patient,
+            id: patient_id,
+        }
+    }
+
+    pub fn doctor(doctor_id: i32) -> Self {
+        Self {
+            role: ActorRole::Doctor,
+            id: doctor_id,
+        }
+    }
+
+    pub fn staff(staff_id: i32) -> Self {
+        Self {
+            role: ActorRole::Staff,
+            id: staff_id,
+        }
+    }
+
+    pub fn service(service_api_key_id: i32) -> Self {
+        Self {
+            role: ActorRole::Service,
+            id: service_api_key_id,
+        }
+    }
+
+    /// Whether the actor may act on any record rather than only their own.
+    /// True for clinic staff, and for services, which are limited by the
+    /// scopes of their API key instead.
+    pub fn is_privileged(&self) -> bool {
+        matches!(self.role, ActorRole::Staff | ActorRole::Service)
+    }
+}
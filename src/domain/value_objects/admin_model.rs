@@ -0,0 +1,182 @@
+use std::fmt;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::staff_actions::AddStaffActionEntity,
+    errors::{DomainError, DomainResult},
+    value_objects::{
+        appointment_model::AddAppointmentDto,
+        appointment_status::AppointmentStatus,
+        schedule_model::ScheduleViewModel,
+        timezone_model::{ClientDateTime, requested_timezone},
+    },
+};
+
+pub const DEFAULT_APPOINTMENTS_PAGE_LIMIT: i64 = 50;
+pub const MAX_APPOINTMENTS_PAGE_LIMIT: i64 = 200;
+
+/// What a staff user did, recorded in `staff_actions`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub enum StaffAction {
+    BookAppointment,
+    RescheduleAppointment,
+    EditAppointment,
+    CancelAppointment,
+    RestoreAppointment,
+    EditSlot,
+    RestoreSlot,
+}
+
+impl fmt::Display for StaffAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StaffAction::BookAppointment => write!(f, "BookAppointment"),
+            StaffAction::RescheduleAppointment => write!(f, "RescheduleAppointment"),
+            StaffAction::EditAppointment => write!(f, "EditAppointment"),
+            StaffAction::CancelAppointment => write!(f, "CancelAppointment"),
+            StaffAction::RestoreAppointment => write!(f, "RestoreAppointment"),
+            StaffAction::EditSlot => write!(f, "EditSlot"),
+            StaffAction::RestoreSlot => write!(f, "RestoreSlot"),
+        }
+    }
+}
+
+/// One entry of the staff audit trail, written in the same transaction as the
+/// change it describes.
+#[derive(Debug, Clone)]
+pub struct StaffActionRecord {
+    pub action: StaffAction,
+    pub staff_id: i32,
+    /// The appointment or slot the action was applied to.
+    pub target_id: Uuid,
+    pub note: Option<String>,
+}
+
+impl StaffActionRecord {
+    pub fn to_entity(&self, current_time: NaiveDateTime) -> AddStaffActionEntity {
+        AddStaffActionEntity {
+            staff_id: self.staff_id,
+            action: self.action.to_string(),
+            target_id: self.target_id,
+            note: self.note.clone(),
+            created_at: current_time,
+        }
+    }
+}
+
+/// A booking made by staff on behalf of `patient_id`.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AdminAddAppointmentDto {
+    pub patient_id: i32,
+    #[serde(flatten)]
+    pub appointment: AddAppointmentDto,
+}
+
+/// Query parameters of the clinic wide appointment list.
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GetAppointmentsQuery {
+    /// Only return appointments in slots of this doctor.
+    pub doctor_id: Option<i32>,
+    /// Only return appointments of this patient.
+    pub patient_id: Option<i32>,
+    /// Only return appointments in this status.
+    pub status: Option<AppointmentStatus>,
+    /// Only return appointments whose slot starts at or after this time.
+    #[param(value_type = Option<String>, format = DateTime)]
+    pub start_from: Option<ClientDateTime>,
+    /// Only return appointments whose slot starts at or before this time.
+    #[param(value_type = Option<String>, format = DateTime)]
+    pub start_to: Option<ClientDateTime>,
+    /// Page number, starting at 1.
+    pub page: Option<i64>,
+    /// Page size, between 1 and 200. Defaults to 50.
+    pub limit: Option<i64>,
+    /// IANA time zone that `start_from`/`start_to` without a UTC offset are
+    /// read in and that slot times are rendered in. Defaults to `UTC`.
+    pub tz: Option<String>,
+}
+
+impl GetAppointmentsQuery {
+    pub fn to_timezone(&self) -> DomainResult<Tz> {
+        requested_timezone(self.tz.as_deref())
+    }
+
+    pub fn to_filter(&self) -> DomainResult<AppointmentFilter> {
+        let page = self.page.unwrap_or(1);
+        if page < 1 {
+            return Err(DomainError::validation("page must be at least 1"));
+        }
+
+        let limit = self.limit.unwrap_or(DEFAULT_APPOINTMENTS_PAGE_LIMIT);
+        if !(1..=MAX_APPOINTMENTS_PAGE_LIMIT).contains(&limit) {
+            return Err(DomainError::validation(format!(
+                "limit must be between 1 and {}",
+                MAX_APPOINTMENTS_PAGE_LIMIT
+            )));
+        }
+
+        let timezone = self.to_timezone()?;
+        let start_from = self
+            .start_from
+            .map(|start_from| start_from.to_utc(timezone))
+            .transpose()?;
+        let start_to = self
+            .start_to
+            .map(|start_to| start_to.to_utc(timezone))
+            .transpose()?;
+
+        if let (Some(start_from), Some(start_to)) = (start_from, start_to)
+            && start_from > start_to
+        {
+            return Err(DomainError::validation(
+                "start_from must not be after start_to",
+            ));
+        }
+
+        Ok(AppointmentFilter {
+            doctor_id: self.doctor_id,
+            patient_id: self.patient_id,
+            status: self.status,
+            start_from,
+            start_to,
+            page,
+            limit,
+        })
+    }
+}
+
+/// Validated appointment list filter passed down to the repository.
+#[derive(Debug, Clone)]
+pub struct AppointmentFilter {
+    pub doctor_id: Option<i32>,
+    pub patient_id: Option<i32>,
+    pub status: Option<AppointmentStatus>,
+    pub start_from: Option<DateTime<Utc>>,
+    pub start_to: Option<DateTime<Utc>>,
+    pub page: i64,
+    pub limit: i64,
+}
+
+impl AppointmentFilter {
+    /// Rows skipped before the requested page. Refused when `page` is so large
+    /// that it does not fit in an `i64`.
+    pub fn offset(&self) -> DomainResult<i64> {
+        (self.page - 1)
+            .checked_mul(self.limit)
+            .ok_or_else(|| DomainError::validation("page is too large"))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GetAppointmentsResponseModel {
+    pub schedules: Vec<ScheduleViewModel>,
+    pub total: i64,
+    pub page: i64,
+    pub limit: i64,
+}
@@ -0,0 +1,75 @@
+use std::fmt;
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::appointment_status_history::{
+        AddAppointmentStatusHistoryEntity, AppointmentStatusHistoryEntity,
+    },
+    value_objects::{actor::Actor, appointment_status::AppointmentStatus},
+};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub enum AppointmentHistoryAction {
+    Create,
+    Edit,
+    Reschedule,
+    Transition,
+    Remove,
+    /// A removed appointment put back by clinic staff.
+    Restore,
+}
+
+impl fmt::Display for AppointmentHistoryAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppointmentHistoryAction::Create => write!(f, "Create"),
+            AppointmentHistoryAction::Edit => write!(f, "Edit"),
+            AppointmentHistoryAction::Reschedule => write!(f, "Reschedule"),
+            AppointmentHistoryAction::Transition => write!(f, "Transition"),
+            AppointmentHistoryAction::Remove => write!(f, "Remove"),
+            AppointmentHistoryAction::Restore => write!(f, "Restore"),
+        }
+    }
+}
+
+/// One entry of an appointment's timeline, written in the same transaction as
+/// the change it describes.
+#[derive(Debug, Clone)]
+pub struct AppointmentHistoryRecord {
+    pub action: AppointmentHistoryAction,
+    /// `None` when the appointment did not exist before, or was removed.
+    pub previous_status: Option<AppointmentStatus>,
+    /// `None` when the appointment was removed.
+    pub new_status: Option<AppointmentStatus>,
+    pub actor: Actor,
+    pub note: Option<String>,
+}
+
+impl AppointmentHistoryRecord {
+    pub fn to_entity(
+        &self,
+        appointment_id: Uuid,
+        current_time: NaiveDateTime,
+    ) -> AddAppointmentStatusHistoryEntity {
+        AddAppointmentStatusHistoryEntity {
+            appointment_id,
+            action: self.action.to_string(),
+            previous_status: self.previous_status.map(|status| status.to_string()),
+            new_status: self.new_status.map(|status| status.to_string()),
+            actor_role: self.actor.role.to_string(),
+            actor_id: self.actor.id,
+            note: self.note.clone(),
+            created_at: current_time,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GetAppointmentHistoryResponseModel {
+    pub appointment_id: Uuid,
+    pub history: Vec<AppointmentStatusHistoryEntity>,
+}
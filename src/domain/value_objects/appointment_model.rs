@@ -4,10 +4,18 @@ use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::domain::{
-    entities::appointments::{AddAppointmentEntity, EditAppointmentEntity},
+    entities::{
+        appointments::{
+            AddAppointmentEntity, ChangeAppointmentStatusEntity, EditAppointmentEntity,
+        },
+        waitlist_entries::AddWaitlistEntryEntity,
+    },
+    errors::{DomainError, DomainResult},
     value_objects::appointment_status::AppointmentStatus,
 };
 
+pub const MAX_CANCELLATION_REASON_LENGTH: usize = 500;
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct AddAppointmentDto {
     pub slot_id: Uuid,
@@ -34,6 +42,23 @@ impl AddAppointmentDto {
             deleted_at: None,
         }
     }
+
+    pub fn to_waitlist_entity(
+        &self,
+        patient_id: i32,
+        current_time: NaiveDateTime,
+    ) -> AddWaitlistEntryEntity {
+        AddWaitlistEntryEntity {
+            slot_id: self.slot_id,
+            patient_id,
+            patient_abnormal_symptom: self.patient_abnormal_symptom.clone(),
+            patient_is_missed_medication: self.patient_is_missed_medication.clone(),
+            patient_blood_test_status: self.patient_blood_test_status.clone(),
+            patient_is_overdue_medication: self.patient_is_overdue_medication.clone(),
+            patient_is_partner_hiv_positive: self.patient_is_partner_hiv_positive.clone(),
+            created_at: current_time,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -58,3 +83,158 @@ impl EditAppointmentDto {
         }
     }
 }
+
+fn validate_reason(reason: &str) -> DomainResult<String> {
+    let reason = reason.trim();
+    if reason.is_empty() {
+        return Err(DomainError::validation("reason must not be empty"));
+    }
+    if reason.chars().count() > MAX_CANCELLATION_REASON_LENGTH {
+        return Err(DomainError::validation(format!(
+            "reason must be at most {} characters",
+            MAX_CANCELLATION_REASON_LENGTH
+        )));
+    }
+
+    Ok(reason.to_string())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CancelAppointmentDto {
+    /// Shown to the patient in their schedule.
+    pub reason: String,
+}
+
+impl CancelAppointmentDto {
+    pub fn to_transition(
+        &self,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<AppointmentTransition> {
+        Ok(AppointmentTransition {
+            target: AppointmentTransitionTarget::Status(AppointmentStatus::Cancelled),
+            reason: Some(validate_reason(&self.reason)?),
+            current_time,
+        })
+    }
+
+    pub fn to_entity(
+        &self,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<ChangeAppointmentStatusEntity> {
+        let appointment_transition = self.to_transition(current_time)?;
+
+        Ok(appointment_transition.to_entity(AppointmentStatus::Cancelled))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TransitionAppointmentDto {
+    pub target: AppointmentStatus,
+    /// Shown to the patient when `target` is `Cancelled` or `Rejected`.
+    pub reason: Option<String>,
+}
+
+impl TransitionAppointmentDto {
+    pub fn to_transition(
+        &self,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<AppointmentTransition> {
+        let reason = match &self.reason {
+            Some(reason) => Some(validate_reason(reason)?),
+            None => None,
+        };
+
+        Ok(AppointmentTransition {
+            target: AppointmentTransitionTarget::Status(self.target),
+            reason,
+            current_time,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AppointmentTransitionTarget {
+    Status(AppointmentStatus),
+    /// The status the appointment was in before it moved into its current one,
+    /// as recorded in its history, see `AppointmentStatus::revert_targets`.
+    Revert,
+}
+
+/// A requested status change, validated against `APPOINTMENT_STATUS_TRANSITIONS`
+/// once the current status is known.
+#[derive(Debug, Clone)]
+pub struct AppointmentTransition {
+    pub target: AppointmentTransitionTarget,
+    pub reason: Option<String>,
+    pub current_time: NaiveDateTime,
+}
+
+impl AppointmentTransition {
+    pub fn to(target: AppointmentStatus, current_time: NaiveDateTime) -> Self {
+        Self {
+            target: AppointmentTransitionTarget::Status(target),
+            reason: None,
+            current_time,
+        }
+    }
+
+    pub fn revert(current_time: NaiveDateTime) -> Self {
+        Self {
+            target: AppointmentTransitionTarget::Revert,
+            reason: None,
+            current_time,
+        }
+    }
+
+    /// Returns the status an appointment in `current_status` moves to.
+    /// `status_before` is the status it was in before `current_status`, only
+    /// needed to revert.
+    pub fn resolve(
+        &self,
+        current_status: AppointmentStatus,
+        status_before: Option<AppointmentStatus>,
+    ) -> DomainResult<AppointmentStatus> {
+        let target = match self.target {
+            AppointmentTransitionTarget::Status(target) => target,
+            AppointmentTransitionTarget::Revert => status_before
+                .filter(|status_before| current_status.revert_targets().contains(status_before))
+                .ok_or_else(|| {
+                    DomainError::invalid_transition(format!(
+                        "{} cannot be reverted",
+                        current_status
+                    ))
+                })?,
+        };
+
+        if !current_status.can_transition_to(&target) {
+            return Err(DomainError::invalid_transition(format!(
+                "Cannot change status from {} to {}",
+                current_status, target
+            )));
+        }
+
+        Ok(target)
+    }
+
+    pub fn to_entity(&self, target: AppointmentStatus) -> ChangeAppointmentStatusEntity {
+        let (cancellation_reason, cancelled_at) = if target.holds_slot_place() {
+            (None, None)
+        } else {
+            (self.reason.clone(), Some(self.current_time))
+        };
+
+        ChangeAppointmentStatusEntity {
+            status: target.to_string(),
+            cancellation_reason,
+            cancelled_at,
+            updated_at: self.current_time,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TransitionAppointmentResponseModel {
+    pub appointment_id: Uuid,
+    pub previous_status: AppointmentStatus,
+    pub status: AppointmentStatus,
+}
@@ -1,14 +1,111 @@
-use std::fmt;
+use std::{fmt, str::FromStr};
 
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
+use crate::domain::errors::DomainError;
+
+#[derive(Default, Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
 pub enum AppointmentStatus {
     #[default]
     Waiting,
     Ready,
     WaitingForPrescription,
     Completed,
+    Cancelled,
+    Rejected,
+    NoShow,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionKind {
+    Forward,
+    /// Undoes a mistaken forward transition, back to the status the
+    /// appointment was in before it.
+    Revert,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatusTransition {
+    pub from: AppointmentStatus,
+    pub to: AppointmentStatus,
+    pub kind: TransitionKind,
+}
+
+const fn forward(from: AppointmentStatus, to: AppointmentStatus) -> StatusTransition {
+    StatusTransition {
+        from,
+        to,
+        kind: TransitionKind::Forward,
+    }
+}
+
+const fn revert(from: AppointmentStatus, to: AppointmentStatus) -> StatusTransition {
+    StatusTransition {
+        from,
+        to,
+        kind: TransitionKind::Revert,
+    }
+}
+
+/// Every status change an appointment is allowed to make.
+pub const APPOINTMENT_STATUS_TRANSITIONS: &[StatusTransition] = {
+    use AppointmentStatus::*;
+
+    &[
+        forward(Waiting, Ready),
+        forward(Ready, WaitingForPrescription),
+        forward(WaitingForPrescription, Completed),
+        forward(Waiting, Cancelled),
+        forward(Ready, Cancelled),
+        forward(Waiting, Rejected),
+        forward(Waiting, NoShow),
+        forward(Ready, NoShow),
+        revert(Ready, Waiting),
+        revert(WaitingForPrescription, Ready),
+        revert(Completed, WaitingForPrescription),
+        revert(Cancelled, Waiting),
+        revert(Cancelled, Ready),
+        revert(Rejected, Waiting),
+        revert(NoShow, Waiting),
+        revert(NoShow, Ready),
+    ]
+};
+
+/// Statuses in which the patient is still waiting for, or in the middle of,
+/// their appointment.
+pub const OPEN_APPOINTMENT_STATUSES: &[AppointmentStatus] = &[
+    AppointmentStatus::Waiting,
+    AppointmentStatus::Ready,
+    AppointmentStatus::WaitingForPrescription,
+];
+
+impl AppointmentStatus {
+    pub fn can_transition_to(&self, target: &AppointmentStatus) -> bool {
+        APPOINTMENT_STATUS_TRANSITIONS
+            .iter()
+            .any(|transition| transition.from == *self && transition.to == *target)
+    }
+
+    /// The statuses a mistaken transition into `self` may have come from.
+    pub fn revert_targets(&self) -> Vec<AppointmentStatus> {
+        APPOINTMENT_STATUS_TRANSITIONS
+            .iter()
+            .filter(|transition| {
+                transition.from == *self && transition.kind == TransitionKind::Revert
+            })
+            .map(|transition| transition.to)
+            .collect()
+    }
+
+    /// Whether an appointment in this status counts towards its slot's
+    /// `current_appointment_count`.
+    pub fn holds_slot_place(&self) -> bool {
+        !matches!(
+            self,
+            AppointmentStatus::Cancelled | AppointmentStatus::Rejected
+        )
+    }
 }
 
 //finding a way to derive string from this enum
@@ -20,6 +117,29 @@ impl fmt::Display for AppointmentStatus {
             AppointmentStatus::Ready => write!(f, "Ready"),
             AppointmentStatus::WaitingForPrescription => write!(f, "WaitingForPrescription"),
             AppointmentStatus::Completed => write!(f, "Completed"),
+            AppointmentStatus::Cancelled => write!(f, "Cancelled"),
+            AppointmentStatus::Rejected => write!(f, "Rejected"),
+            AppointmentStatus::NoShow => write!(f, "NoShow"),
+        }
+    }
+}
+
+impl FromStr for AppointmentStatus {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Waiting" => Ok(AppointmentStatus::Waiting),
+            "Ready" => Ok(AppointmentStatus::Ready),
+            "WaitingForPrescription" => Ok(AppointmentStatus::WaitingForPrescription),
+            "Completed" => Ok(AppointmentStatus::Completed),
+            "Cancelled" => Ok(AppointmentStatus::Cancelled),
+            "Rejected" => Ok(AppointmentStatus::Rejected),
+            "NoShow" => Ok(AppointmentStatus::NoShow),
+            _ => Err(DomainError::Internal(anyhow::anyhow!(
+                "Unknown appointment status: {}",
+                s
+            ))),
         }
     }
 }
@@ -0,0 +1,80 @@
+use crate::domain::{
+    entities::{appointments::PatientOpenAppointmentEntity, slots::SlotEntity},
+    errors::{DomainError, DomainResult},
+    value_objects::booking_window::BookingWindow,
+};
+
+/// Limits on how many appointments a single patient may hold at once.
+///
+/// Booking the same slot twice is always refused; the other rules can be
+/// turned off through the environment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BookingPolicy {
+    pub prevent_overlapping_appointments: bool,
+    pub one_active_appointment_per_doctor: bool,
+    /// `None` means no limit.
+    pub max_open_appointments: Option<usize>,
+    /// Applies to doctors who have not set their own.
+    pub booking_window: BookingWindow,
+}
+
+impl Default for BookingPolicy {
+    fn default() -> Self {
+        Self {
+            prevent_overlapping_appointments: true,
+            one_active_appointment_per_doctor: true,
+            max_open_appointments: None,
+            booking_window: BookingWindow::default(),
+        }
+    }
+}
+
+impl BookingPolicy {
+    /// Checks whether a patient holding `open_appointments` may book `slot`.
+    /// When rescheduling, the appointment being moved must not be included.
+    pub fn check(
+        &self,
+        slot: &SlotEntity,
+        open_appointments: &[PatientOpenAppointmentEntity],
+    ) -> DomainResult<()> {
+        if open_appointments
+            .iter()
+            .any(|appointment| appointment.slot_id == slot.id)
+        {
+            return Err(DomainError::conflict(
+                "You already have an appointment in this slot",
+            ));
+        }
+
+        if self.prevent_overlapping_appointments
+            && open_appointments.iter().any(|appointment| {
+                appointment.start_time < slot.end_time && slot.start_time < appointment.end_time
+            })
+        {
+            return Err(DomainError::conflict(
+                "You already have an appointment that overlaps this slot",
+            ));
+        }
+
+        if self.one_active_appointment_per_doctor
+            && open_appointments
+                .iter()
+                .any(|appointment| appointment.doctor_id == slot.doctor_id)
+        {
+            return Err(DomainError::conflict(
+                "You already have an active appointment with this doctor",
+            ));
+        }
+
+        if let Some(max_open_appointments) = self.max_open_appointments
+            && open_appointments.len() >= max_open_appointments
+        {
+            return Err(DomainError::conflict(format!(
+                "You can have at most {} open appointments",
+                max_open_appointments
+            )));
+        }
+
+        Ok(())
+    }
+}
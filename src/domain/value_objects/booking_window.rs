@@ -0,0 +1,141 @@
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain::{
+    entities::doctor_booking_windows::{DoctorBookingWindowEntity, SetDoctorBookingWindowEntity},
+    errors::{DomainError, DomainResult},
+};
+
+/// When, relative to a slot's start, patients may book it and change their
+/// appointment in it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct BookingWindow {
+    /// Booking closes this many minutes before the slot starts.
+    pub min_booking_lead_minutes: i32,
+    /// How many days ahead a slot can be booked. `None` means no limit.
+    pub max_booking_horizon_days: Option<i32>,
+    /// Cancelling or rescheduling closes this many minutes before the
+    /// appointment starts.
+    pub min_change_notice_minutes: i32,
+}
+
+impl BookingWindow {
+    /// The window of a doctor who has overridden some of the global settings.
+    pub fn with_doctor_override(
+        &self,
+        doctor_booking_window: Option<&DoctorBookingWindowEntity>,
+    ) -> Self {
+        let Some(doctor_booking_window) = doctor_booking_window else {
+            return *self;
+        };
+
+        Self {
+            min_booking_lead_minutes: doctor_booking_window
+                .min_booking_lead_minutes
+                .unwrap_or(self.min_booking_lead_minutes),
+            max_booking_horizon_days: doctor_booking_window
+                .max_booking_horizon_days
+                .or(self.max_booking_horizon_days),
+            min_change_notice_minutes: doctor_booking_window
+                .min_change_notice_minutes
+                .unwrap_or(self.min_change_notice_minutes),
+        }
+    }
+
+    pub fn check_booking(
+        &self,
+        start_time: DateTime<Utc>,
+        current_time: DateTime<Utc>,
+    ) -> DomainResult<()> {
+        let cutoff = start_time - Duration::minutes(self.min_booking_lead_minutes.into());
+        if current_time > cutoff {
+            return Err(DomainError::booking_cutoff(format!(
+                "Booking for this slot closed at {}",
+                cutoff.format("%Y-%m-%d %H:%M")
+            )));
+        }
+
+        if let Some(max_booking_horizon_days) = self.max_booking_horizon_days
+            && start_time > current_time + Duration::days(max_booking_horizon_days.into())
+        {
+            return Err(DomainError::booking_horizon(format!(
+                "Slots can be booked at most {} days ahead",
+                max_booking_horizon_days
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Checks that an appointment starting at `start_time` can still be
+    /// cancelled or rescheduled.
+    pub fn check_change(
+        &self,
+        start_time: DateTime<Utc>,
+        current_time: DateTime<Utc>,
+    ) -> DomainResult<()> {
+        let cutoff = start_time - Duration::minutes(self.min_change_notice_minutes.into());
+        if current_time > cutoff {
+            return Err(DomainError::change_notice(format!(
+                "This appointment can no longer be changed, the deadline was {}",
+                cutoff.format("%Y-%m-%d %H:%M")
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SetDoctorBookingWindowDto {
+    pub min_booking_lead_minutes: Option<i32>,
+    pub max_booking_horizon_days: Option<i32>,
+    pub min_change_notice_minutes: Option<i32>,
+}
+
+impl SetDoctorBookingWindowDto {
+    pub fn to_entity(
+        &self,
+        doctor_id: i32,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<SetDoctorBookingWindowEntity> {
+        if self
+            .min_booking_lead_minutes
+            .is_some_and(|minutes| minutes < 0)
+        {
+            return Err(DomainError::validation(
+                "min_booking_lead_minutes must not be negative",
+            ));
+        }
+        if self.max_booking_horizon_days.is_some_and(|days| days < 1) {
+            return Err(DomainError::validation(
+                "max_booking_horizon_days must be at least 1",
+            ));
+        }
+        if self
+            .min_change_notice_minutes
+            .is_some_and(|minutes| minutes < 0)
+        {
+            return Err(DomainError::validation(
+                "min_change_notice_minutes must not be negative",
+            ));
+        }
+
+        Ok(SetDoctorBookingWindowEntity {
+            doctor_id,
+            min_booking_lead_minutes: self.min_booking_lead_minutes,
+            max_booking_horizon_days: self.max_booking_horizon_days,
+            min_change_notice_minutes: self.min_change_notice_minutes,
+            updated_at: current_time,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GetDoctorBookingWindowResponseModel {
+    /// The doctor's own settings, `None` when the doctor has not set any.
+    pub doctor_booking_window: Option<DoctorBookingWindowEntity>,
+    /// What is actually enforced for the doctor's slots.
+    pub effective_booking_window: BookingWindow,
+}
@@ -0,0 +1,89 @@
+use std::fmt;
+
+use chrono::{NaiveDateTime, TimeDelta};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::domain::{
+    entities::idempotency_keys::AddIdempotencyKeyEntity,
+    errors::{DomainError, DomainResult},
+};
+
+pub const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 255;
+
+/// How long a stored `Idempotency-Key` answers retries, after that the key may
+/// be used for a new request.
+pub const IDEMPOTENCY_KEY_TTL_HOURS: i64 = 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdempotentOperation {
+    AddAppointment,
+    RescheduleAppointment,
+}
+
+impl fmt::Display for IdempotentOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IdempotentOperation::AddAppointment => write!(f, "AddAppointment"),
+            IdempotentOperation::RescheduleAppointment => write!(f, "RescheduleAppointment"),
+        }
+    }
+}
+
+/// A client supplied `Idempotency-Key` together with a hash of the request it
+/// was sent with, so a retry can be told apart from a reused key.
+#[derive(Debug, Clone)]
+pub struct IdempotencyKey {
+    pub operation: IdempotentOperation,
+    pub key: String,
+    pub request_hash: String,
+}
+
+impl IdempotencyKey {
+    pub fn new(
+        operation: IdempotentOperation,
+        key: &str,
+        request: &impl Serialize,
+    ) -> DomainResult<Self> {
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(DomainError::validation("Idempotency-Key must not be empty"));
+        }
+        if key.chars().count() > MAX_IDEMPOTENCY_KEY_LENGTH {
+            return Err(DomainError::validation(format!(
+                "Idempotency-Key must be at most {} characters",
+                MAX_IDEMPOTENCY_KEY_LENGTH
+            )));
+        }
+
+        let request = serde_json::to_vec(request).map_err(|e| DomainError::Internal(e.into()))?;
+        let request_hash = format!("{:x}", Sha256::digest(request));
+
+        Ok(Self {
+            operation,
+            key: key.to_string(),
+            request_hash,
+        })
+    }
+
+    /// Keys created before this time have expired.
+    pub fn expired_before(current_time: NaiveDateTime) -> NaiveDateTime {
+        current_time - TimeDelta::hours(IDEMPOTENCY_KEY_TTL_HOURS)
+    }
+
+    pub fn to_entity(
+        &self,
+        patient_id: i32,
+        response: serde_json::Value,
+        current_time: NaiveDateTime,
+    ) -> AddIdempotencyKeyEntity {
+        AddIdempotencyKeyEntity {
+            patient_id,
+            operation: self.operation.to_string(),
+            idempotency_key: self.key.clone(),
+            request_hash: self.request_hash.clone(),
+            response,
+            created_at: current_time,
+        }
+    }
+}
@@ -1,4 +1,18 @@
+pub mod actor;
+pub mod admin_model;
+pub mod appointment_history_model;
 pub mod appointment_model;
 pub mod appointment_status;
+pub mod booking_policy;
+pub mod booking_window;
+pub mod idempotency_model;
+pub mod outbox_model;
+pub mod reminder_model;
+pub mod service_api_key_model;
 pub mod slot_model;
+pub mod slot_reconciliation_model;
+pub mod slot_template_model;
+pub mod timezone_model;
+pub mod waitlist_model;
+pub mod webhook_model;
 pub mod schedule_model;
\ No newline at end of file
@@ -0,0 +1,244 @@
+use chrono::{DateTime, NaiveDateTime, TimeDelta, Utc};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::outbox_events::{AddOutboxEventEntity, OutboxEventEntity},
+    errors::{DomainError, DomainResult},
+    value_objects::{actor::Actor, appointment_status::AppointmentStatus},
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AppointmentBooked {
+    pub appointment_id: Uuid,
+    pub slot_id: Uuid,
+    pub patient_id: i32,
+    pub actor: Actor,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AppointmentEdited {
+    pub appointment_id: Uuid,
+    pub patient_id: i32,
+    pub actor: Actor,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AppointmentRescheduled {
+    pub appointment_id: Uuid,
+    pub patient_id: i32,
+    pub previous_slot_id: Uuid,
+    pub slot_id: Uuid,
+    pub actor: Actor,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AppointmentStatusChanged {
+    pub appointment_id: Uuid,
+    pub slot_id: Uuid,
+    pub patient_id: i32,
+    pub previous_status: AppointmentStatus,
+    pub status: AppointmentStatus,
+    pub reason: Option<String>,
+    pub actor: Actor,
+}
+
+/// An appointment removed by its patient.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AppointmentRemoved {
+    pub appointment_id: Uuid,
+    pub slot_id: Uuid,
+    pub patient_id: i32,
+    pub actor: Actor,
+}
+
+/// A removed appointment put back by clinic staff.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AppointmentRestored {
+    pub appointment_id: Uuid,
+    pub slot_id: Uuid,
+    pub patient_id: i32,
+    pub status: AppointmentStatus,
+    pub actor: Actor,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SlotRemoved {
+    pub slot_id: Uuid,
+    pub doctor_id: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SlotRestored {
+    pub slot_id: Uuid,
+    pub doctor_id: i32,
+    pub actor: Actor,
+}
+
+/// Every `event_type` an outbox event can have.
+pub const DOMAIN_EVENT_TYPES: [&str; 8] = [
+    "AppointmentBooked",
+    "AppointmentEdited",
+    "AppointmentRescheduled",
+    "AppointmentStatusChanged",
+    "AppointmentRemoved",
+    "AppointmentRestored",
+    "SlotRemoved",
+    "SlotRestored",
+];
+
+/// A change other services may react to, written to `outbox_events` in the
+/// same transaction as the change itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DomainEvent {
+    AppointmentBooked(AppointmentBooked),
+    AppointmentEdited(AppointmentEdited),
+    AppointmentRescheduled(AppointmentRescheduled),
+    AppointmentStatusChanged(AppointmentStatusChanged),
+    AppointmentRemoved(AppointmentRemoved),
+    AppointmentRestored(AppointmentRestored),
+    SlotRemoved(SlotRemoved),
+    SlotRestored(SlotRestored),
+}
+
+impl DomainEvent {
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            DomainEvent::AppointmentBooked(_) => "AppointmentBooked",
+            DomainEvent::AppointmentEdited(_) => "AppointmentEdited",
+            DomainEvent::AppointmentRescheduled(_) => "AppointmentRescheduled",
+            DomainEvent::AppointmentStatusChanged(_) => "AppointmentStatusChanged",
+            DomainEvent::AppointmentRemoved(_) => "AppointmentRemoved",
+            DomainEvent::AppointmentRestored(_) => "AppointmentRestored",
+            DomainEvent::SlotRemoved(_) => "SlotRemoved",
+            DomainEvent::SlotRestored(_) => "SlotRestored",
+        }
+    }
+
+    /// The appointment or slot the event is about.
+    pub fn aggregate_id(&self) -> Uuid {
+        match self {
+            DomainEvent::AppointmentBooked(event) => event.appointment_id,
+            DomainEvent::AppointmentEdited(event) => event.appointment_id,
+            DomainEvent::AppointmentRescheduled(event) => event.appointment_id,
+            DomainEvent::AppointmentStatusChanged(event) => event.appointment_id,
+            DomainEvent::AppointmentRemoved(event) => event.appointment_id,
+            DomainEvent::AppointmentRestored(event) => event.appointment_id,
+            DomainEvent::SlotRemoved(event) => event.slot_id,
+            DomainEvent::SlotRestored(event) => event.slot_id,
+        }
+    }
+
+    fn payload(&self) -> serde_json::Result<serde_json::Value> {
+        match self {
+            DomainEvent::AppointmentBooked(event) => serde_json::to_value(event),
+            DomainEvent::AppointmentEdited(event) => serde_json::to_value(event),
+            DomainEvent::AppointmentRescheduled(event) => serde_json::to_value(event),
+            DomainEvent::AppointmentStatusChanged(event) => serde_json::to_value(event),
+            DomainEvent::AppointmentRemoved(event) => serde_json::to_value(event),
+            DomainEvent::AppointmentRestored(event) => serde_json::to_value(event),
+            DomainEvent::SlotRemoved(event) => serde_json::to_value(event),
+            DomainEvent::SlotRestored(event) => serde_json::to_value(event),
+        }
+    }
+
+    pub fn to_entity(&self, current_time: NaiveDateTime) -> DomainResult<AddOutboxEventEntity> {
+        let payload = self
+            .payload()
+            .map_err(|e| DomainError::Internal(e.into()))?;
+
+        Ok(AddOutboxEventEntity {
+            event_type: self.event_type().to_string(),
+            aggregate_id: self.aggregate_id(),
+            payload,
+            created_at: current_time,
+            next_attempt_at: current_time,
+        })
+    }
+}
+
+/// What a sink receives for one outbox event. `id` stays the same across
+/// redeliveries, so consumers can drop duplicates.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboxMessage {
+    pub id: Uuid,
+    pub event_type: String,
+    pub aggregate_id: Uuid,
+    pub occurred_at: DateTime<Utc>,
+    /// How many times delivery was tried before, 0 on the first try.
+    pub attempts: i32,
+    pub payload: serde_json::Value,
+}
+
+impl OutboxMessage {
+    /// The slots named in the payload, both slots of a reschedule. Empty for
+    /// an `AppointmentEdited`, whose slot is the appointment's.
+    pub fn slot_ids(&self) -> Vec<Uuid> {
+        ["slot_id", "previous_slot_id"]
+            .iter()
+            .filter_map(|key| self.payload.get(key)?.as_str()?.parse::<Uuid>().ok())
+            .collect()
+    }
+
+    pub fn from_entity(outbox_event_entity: OutboxEventEntity) -> Self {
+        Self {
+            id: outbox_event_entity.id,
+            event_type: outbox_event_entity.event_type,
+            aggregate_id: outbox_event_entity.aggregate_id,
+            occurred_at: outbox_event_entity.created_at.and_utc(),
+            attempts: outbox_event_entity.attempts,
+            payload: outbox_event_entity.payload,
+        }
+    }
+}
+
+/// How often and how long a failed delivery is retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutboxRetryPolicy {
+    /// Deliveries tried before the event is given up on and marked failed.
+    pub max_attempts: i32,
+    pub base_delay_seconds: u64,
+    pub max_delay_seconds: u64,
+}
+
+impl Default for OutboxRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 10,
+            base_delay_seconds: 5,
+            max_delay_seconds: 3600,
+        }
+    }
+}
+
+impl OutboxRetryPolicy {
+    /// Whether an event that already failed `attempts` times should be tried
+    /// again.
+    pub fn should_retry(&self, attempts: i32) -> bool {
+        attempts < self.max_attempts
+    }
+
+    /// Exponential backoff with up to 20% jitter, so events that failed
+    /// together are not all retried at the same moment.
+    pub fn next_attempt_at(&self, attempts: i32, current_time: NaiveDateTime) -> NaiveDateTime {
+        let exponent = attempts.saturating_sub(1).clamp(0, 30) as u32;
+        let delay_seconds = self
+            .base_delay_seconds
+            .saturating_mul(1 << exponent)
+            .min(self.max_delay_seconds);
+        let jitter_seconds = rand::rng().random_range(0..=delay_seconds / 5);
+
+        current_time + TimeDelta::seconds((delay_seconds + jitter_seconds) as i64)
+    }
+}
+
+/// Outcome of one dispatcher pass.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OutboxDispatchReportModel {
+    pub dispatched: usize,
+    /// Deliveries that failed and are scheduled to be tried again.
+    pub retried: usize,
+    /// Events given up on after their last attempt.
+    pub failed: Vec<Uuid>,
+}
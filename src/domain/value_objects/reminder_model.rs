@@ -0,0 +1,106 @@
+use chrono::{DateTime, NaiveDateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::entities::{
+    appointment_reminders::AddAppointmentReminderEntity, schedule_view::ScheduleViewEntity,
+};
+
+/// How long before `start_time` patients are reminded, e.g. 24 hours and
+/// 1 hour.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReminderSchedule {
+    offsets_minutes: Vec<i32>,
+}
+
+impl Default for ReminderSchedule {
+    fn default() -> Self {
+        Self::new(vec![24 * 60, 60])
+    }
+}
+
+impl ReminderSchedule {
+    pub fn new(mut offsets_minutes: Vec<i32>) -> Self {
+        offsets_minutes.sort_unstable();
+        offsets_minutes.dedup();
+        Self { offsets_minutes }
+    }
+
+    pub fn offsets_minutes(&self) -> &[i32] {
+        &self.offsets_minutes
+    }
+
+    /// The start times each offset is due for at `current_time`, as
+    /// `(offset_minutes, after, until)`. An appointment gets the reminder of
+    /// the smallest offset it is within, so one booked 2 hours ahead gets the
+    /// 24 hour reminder once and the 1 hour reminder later, never both at once.
+    pub fn windows(&self, current_time: DateTime<Utc>) -> Vec<(i32, DateTime<Utc>, DateTime<Utc>)> {
+        let mut after = current_time;
+        self.offsets_minutes
+            .iter()
+            .map(|&offset_minutes| {
+                let until = current_time + TimeDelta::minutes(offset_minutes.into());
+                let window = (offset_minutes, after, until);
+                after = until;
+                window
+            })
+            .collect()
+    }
+}
+
+/// What a notifier receives for one reminder.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppointmentReminderModel {
+    pub appointment_id: Uuid,
+    pub slot_id: Uuid,
+    pub patient_id: i32,
+    pub doctor_id: i32,
+    pub status: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    /// The offset this reminder was scheduled for. The appointment may start
+    /// sooner than that if it was booked late.
+    pub offset_minutes: i32,
+}
+
+impl AppointmentReminderModel {
+    pub fn from_entity(schedule_view_entity: ScheduleViewEntity, offset_minutes: i32) -> Self {
+        Self {
+            appointment_id: schedule_view_entity.id,
+            slot_id: schedule_view_entity.slot_id,
+            patient_id: schedule_view_entity.patient_id,
+            doctor_id: schedule_view_entity.doctor_id,
+            status: schedule_view_entity.status,
+            start_time: schedule_view_entity.start_time,
+            end_time: schedule_view_entity.end_time,
+            offset_minutes,
+        }
+    }
+
+    pub fn to_entity(
+        &self,
+        current_time: NaiveDateTime,
+        lease_until: NaiveDateTime,
+    ) -> AddAppointmentReminderEntity {
+        AddAppointmentReminderEntity {
+            appointment_id: self.appointment_id,
+            offset_minutes: self.offset_minutes,
+            start_time: self.start_time,
+            created_at: current_time,
+            lease_until,
+        }
+    }
+}
+
+/// Outcome of one reminder scheduler pass.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReminderReportModel {
+    pub sent: usize,
+    /// Appointments whose notifier failed with its error, they are tried
+    /// again on the next pass.
+    pub failed: Vec<(Uuid, String)>,
+    /// Reminders as `(appointment_id, offset_minutes)` that an earlier pass
+    /// claimed but did not send before its lease ran out, e.g. because the
+    /// process stopped. They were tried again and may have been sent twice.
+    pub reclaimed: Vec<(Uuid, i32)>,
+}
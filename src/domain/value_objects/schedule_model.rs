@@ -1,14 +1,59 @@
+use chrono::{DateTime, FixedOffset, NaiveDateTime};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use uuid::Uuid;
 
-use crate::domain::entities::schedule_view::ScheduleViewEntity;
+use crate::domain::{
+    entities::schedule_view::ScheduleViewEntity, value_objects::timezone_model::in_timezone,
+};
+
+/// An appointment with its slot times rendered in a requested time zone.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ScheduleViewModel {
+    pub id: Uuid,
+    pub slot_id: Uuid,
+    pub patient_id: i32,
+    pub patient_abnormal_symptom: String,
+    pub patient_is_missed_medication: String,
+    pub patient_blood_test_status: String,
+    pub patient_is_overdue_medication: String,
+    pub patient_is_partner_hiv_positive: String,
+    pub status: String,
+    pub doctor_id: i32,
+    pub start_time: DateTime<FixedOffset>,
+    pub end_time: DateTime<FixedOffset>,
+    pub cancellation_reason: Option<String>,
+    pub cancelled_at: Option<NaiveDateTime>,
+}
+
+impl ScheduleViewModel {
+    pub fn from_entity(schedule_view_entity: ScheduleViewEntity, timezone: Tz) -> Self {
+        Self {
+            id: schedule_view_entity.id,
+            slot_id: schedule_view_entity.slot_id,
+            patient_id: schedule_view_entity.patient_id,
+            patient_abnormal_symptom: schedule_view_entity.patient_abnormal_symptom,
+            patient_is_missed_medication: schedule_view_entity.patient_is_missed_medication,
+            patient_blood_test_status: schedule_view_entity.patient_blood_test_status,
+            patient_is_overdue_medication: schedule_view_entity.patient_is_overdue_medication,
+            patient_is_partner_hiv_positive: schedule_view_entity.patient_is_partner_hiv_positive,
+            status: schedule_view_entity.status,
+            doctor_id: schedule_view_entity.doctor_id,
+            start_time: in_timezone(schedule_view_entity.start_time, timezone),
+            end_time: in_timezone(schedule_view_entity.end_time, timezone),
+            cancellation_reason: schedule_view_entity.cancellation_reason,
+            cancelled_at: schedule_view_entity.cancelled_at,
+        }
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct GetDoctorScheduleResponseModel {
-    pub schedules: Vec<ScheduleViewEntity>,
+    pub schedules: Vec<ScheduleViewModel>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct GetPatientScheduleResponseModel {
-    pub schedules: Vec<ScheduleViewEntity>,
+    pub schedules: Vec<ScheduleViewModel>,
 }
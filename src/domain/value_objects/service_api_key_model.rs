@@ -0,0 +1,183 @@
+use std::{fmt, str::FromStr};
+
+use argon2::{
+    Argon2, PasswordHash, PasswordHasher, PasswordVerifier,
+    password_hash::{SaltString, rand_core::OsRng},
+};
+use chrono::NaiveDateTime;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain::{
+    entities::service_api_keys::{AddServiceApiKeyEntity, ServiceApiKeyEntity},
+    errors::{DomainError, DomainResult},
+};
+
+const SERVICE_API_KEY_TAG: &str = "mbs";
+const KEY_PREFIX_BYTES: usize = 4;
+const KEY_SECRET_BYTES: usize = 32;
+
+/// What another MedBook service is allowed to do with its API key.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub enum ServiceScope {
+    #[serde(rename = "appointments:read")]
+    AppointmentsRead,
+    #[serde(rename = "ledger:write")]
+    LedgerWrite,
+    #[serde(rename = "webhooks:write")]
+    WebhooksWrite,
+}
+
+impl fmt::Display for ServiceScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServiceScope::AppointmentsRead => write!(f, "appointments:read"),
+            ServiceScope::LedgerWrite => write!(f, "ledger:write"),
+            ServiceScope::WebhooksWrite => write!(f, "webhooks:write"),
+        }
+    }
+}
+
+impl FromStr for ServiceScope {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "appointments:read" => Ok(ServiceScope::AppointmentsRead),
+            "ledger:write" => Ok(ServiceScope::LedgerWrite),
+            "webhooks:write" => Ok(ServiceScope::WebhooksWrite),
+            _ => Err(DomainError::validation(format!(
+                "Unknown service scope: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// An API key as sent by another service, `mbs_<key prefix>_<secret>`.
+///
+/// The key prefix finds the stored key, only an argon2 hash of the whole key
+/// is kept.
+#[derive(Clone)]
+pub struct ServiceApiKey {
+    pub key_prefix: String,
+    key: String,
+}
+
+impl fmt::Debug for ServiceApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ServiceApiKey")
+            .field("key_prefix", &self.key_prefix)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ServiceApiKey {
+    pub fn generate() -> Self {
+        let mut rng = rand::rng();
+        let key_prefix = to_hex(&rng.random::<[u8; KEY_PREFIX_BYTES]>());
+        let secret = to_hex(&rng.random::<[u8; KEY_SECRET_BYTES]>());
+
+        Self {
+            key: format!("{}_{}_{}", SERVICE_API_KEY_TAG, key_prefix, secret),
+            key_prefix,
+        }
+    }
+
+    pub fn parse(key: &str) -> DomainResult<Self> {
+        let malformed = || DomainError::unauthorized("API key is malformed");
+
+        let mut parts = key.trim().splitn(3, '_');
+        if parts.next() != Some(SERVICE_API_KEY_TAG) {
+            return Err(malformed());
+        }
+        let key_prefix = parts
+            .next()
+            .filter(|key_prefix| key_prefix.len() == KEY_PREFIX_BYTES * 2)
+            .ok_or_else(malformed)?;
+        parts
+            .next()
+            .filter(|secret| !secret.is_empty())
+            .ok_or_else(malformed)?;
+
+        Ok(Self {
+            key_prefix: key_prefix.to_string(),
+            key: key.trim().to_string(),
+        })
+    }
+
+    /// The whole key, to hand to the service once when it is created.
+    pub fn reveal(&self) -> &str {
+        &self.key
+    }
+
+    pub fn hash(&self) -> DomainResult<String> {
+        let salt = SaltString::generate(&mut OsRng);
+        let key_hash = Argon2::default()
+            .hash_password(self.key.as_bytes(), &salt)
+            .map_err(|e| DomainError::Internal(anyhow::anyhow!("Failed to hash API key: {e}")))?;
+
+        Ok(key_hash.to_string())
+    }
+
+    pub fn verify(&self, key_hash: &str) -> bool {
+        PasswordHash::new(key_hash).is_ok_and(|key_hash| {
+            Argon2::default()
+                .verify_password(self.key.as_bytes(), &key_hash)
+                .is_ok()
+        })
+    }
+
+    pub fn to_entity(
+        &self,
+        service_name: String,
+        scopes: &[ServiceScope],
+        key_hash: String,
+        current_time: NaiveDateTime,
+    ) -> AddServiceApiKeyEntity {
+        AddServiceApiKeyEntity {
+            service_name,
+            key_prefix: self.key_prefix.clone(),
+            key_hash,
+            scopes: scopes.iter().map(|scope| scope.to_string()).collect(),
+            created_at: current_time,
+        }
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// A service whose API key has been verified.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceCredentialModel {
+    pub id: i32,
+    pub service_name: String,
+    pub scopes: Vec<ServiceScope>,
+}
+
+impl ServiceCredentialModel {
+    pub fn from_entity(service_api_key_entity: ServiceApiKeyEntity) -> Self {
+        Self {
+            id: service_api_key_entity.id,
+            service_name: service_api_key_entity.service_name,
+            // Scopes are checked when the key is created, one this version no
+            // longer knows grants nothing.
+            scopes: service_api_key_entity
+                .scopes
+                .iter()
+                .filter_map(|scope| scope.parse().ok())
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CreatedServiceApiKeyModel {
+    pub id: i32,
+    pub service_name: String,
+    pub scopes: Vec<ServiceScope>,
+    pub service_api_key: ServiceApiKey,
+}
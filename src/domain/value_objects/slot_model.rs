@@ -1,53 +1,420 @@
-use chrono::NaiveDateTime;
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 
-use crate::domain::entities::slots::{AddSlotEntity, EditSlotEntity, SlotEntity};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{
+        appointments::ChangeAppointmentStatusEntity,
+        slots::{AddSlotEntity, EditSlotEntity, SlotEntity},
+    },
+    errors::{DomainError, DomainResult},
+    value_objects::{
+        appointment_model::CancelAppointmentDto,
+        timezone_model::{ClientDateTime, in_timezone, requested_timezone},
+    },
+};
+
+pub const DEFAULT_SLOTS_PAGE_LIMIT: i64 = 20;
+pub const MAX_SLOTS_PAGE_LIMIT: i64 = 100;
+pub const MAX_BULK_SLOTS: usize = 500;
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct AddSlotDto {
     pub max_appointment_count: i32,
-    pub start_time: NaiveDateTime,
-    pub end_time: NaiveDateTime,
+    /// With a UTC offset, or wall-clock time in the doctor's time zone.
+    #[schema(value_type = String, format = DateTime)]
+    pub start_time: ClientDateTime,
+    /// With a UTC offset, or wall-clock time in the doctor's time zone.
+    #[schema(value_type = String, format = DateTime)]
+    pub end_time: ClientDateTime,
 }
 
 impl AddSlotDto {
-    pub fn to_entity(&self, doctor_id: i32, current_time: NaiveDateTime) -> AddSlotEntity {
-        AddSlotEntity {
+    pub fn to_entity(
+        &self,
+        doctor_id: i32,
+        timezone: Tz,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<AddSlotEntity> {
+        Ok(AddSlotEntity {
             doctor_id,
             current_appointment_count: 0,
             max_appointment_count: self.max_appointment_count,
-            start_time: self.start_time,
-            end_time: self.end_time,
+            start_time: self.start_time.to_utc(timezone)?,
+            end_time: self.end_time.to_utc(timezone)?,
             created_at: current_time,
             updated_at: current_time,
             deleted_at: None,
+            template_id: None,
+        })
+    }
+}
+
+impl AddSlotDto {
+    /// Returns why this slot can never be created, if anything is wrong with it.
+    pub fn invalid_reason(&self, timezone: Tz) -> Option<String> {
+        let start_time = match self.start_time.to_utc(timezone) {
+            Ok(start_time) => start_time,
+            Err(e) => return Some(e.to_string()),
+        };
+        let end_time = match self.end_time.to_utc(timezone) {
+            Ok(end_time) => end_time,
+            Err(e) => return Some(e.to_string()),
+        };
+        if end_time <= start_time {
+            return Some("end_time must be after start_time".to_string());
         }
+        if self.max_appointment_count < 1 {
+            return Some("max_appointment_count must be at least 1".to_string());
+        }
+
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkMode {
+    /// Create every slot or none of them.
+    #[default]
+    AllOrNothing,
+    /// Create every slot that can be created and report the rest.
+    BestEffort,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AddSlotsBulkDto {
+    pub mode: Option<BulkMode>,
+    pub slots: Vec<AddSlotDto>,
+}
+
+/// Outcome of one item of a bulk slot creation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "status")]
+pub enum BulkSlotOutcome {
+    Created {
+        slot_id: Uuid,
+    },
+    /// Overlaps another slot of the doctor, possibly one created earlier in the same batch.
+    /// `conflicting_slot_id` is `None` when that slot was rolled back with an all-or-nothing
+    /// batch, as it does not exist.
+    Overlapping {
+        conflicting_slot_id: Option<Uuid>,
+    },
+    Past,
+    Invalid {
+        reason: String,
+    },
+    /// Valid, but not created because another item of an all-or-nothing batch failed.
+    NotCreated,
+}
+
+impl BulkSlotOutcome {
+    pub fn is_created(&self) -> bool {
+        matches!(self, BulkSlotOutcome::Created { .. })
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BulkSlotResultModel {
+    pub index: usize,
+    /// As sent in the request.
+    #[schema(value_type = String, format = DateTime)]
+    pub start_time: ClientDateTime,
+    /// As sent in the request.
+    #[schema(value_type = String, format = DateTime)]
+    pub end_time: ClientDateTime,
+    pub outcome: BulkSlotOutcome,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AddSlotsBulkResponseModel {
+    pub mode: BulkMode,
+    pub created_count: usize,
+    pub results: Vec<BulkSlotResultModel>,
+}
+
 #[derive(Serialize, Deserialize, ToSchema)]
 pub struct EditSlotDto {
     pub max_appointment_count: Option<i32>,
-    pub end_time: Option<NaiveDateTime>,
+    /// With a UTC offset, or wall-clock time in the doctor's time zone.
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub end_time: Option<ClientDateTime>,
 }
 
 impl EditSlotDto {
-    pub fn to_entity(&self, current_time: NaiveDateTime) -> EditSlotEntity {
-        EditSlotEntity {
+    pub fn to_entity(
+        &self,
+        timezone: Tz,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<EditSlotEntity> {
+        Ok(EditSlotEntity {
             max_appointment_count: self.max_appointment_count,
-            end_time: self.end_time,
+            end_time: self
+                .end_time
+                .map(|end_time| end_time.to_utc(timezone))
+                .transpose()?,
             updated_at: current_time,
+        })
+    }
+}
+
+/// What happens to the appointments of a slot that is force removed or shrunk.
+#[derive(Debug, Clone)]
+pub enum SlotCascadeAction {
+    Cancel(ChangeAppointmentStatusEntity),
+    Move { slot_id: Uuid },
+}
+
+fn to_cascade_action(
+    reason: &Option<String>,
+    move_to_slot_id: Option<Uuid>,
+    current_time: NaiveDateTime,
+) -> DomainResult<SlotCascadeAction> {
+    match (move_to_slot_id, reason) {
+        (Some(slot_id), _) => Ok(SlotCascadeAction::Move { slot_id }),
+        (None, Some(reason)) => {
+            let cancel_appointment_dto = CancelAppointmentDto {
+                reason: reason.clone(),
+            };
+            Ok(SlotCascadeAction::Cancel(
+                cancel_appointment_dto.to_entity(current_time)?,
+            ))
+        }
+        (None, None) => Err(DomainError::validation(
+            "reason is required when appointments are cancelled",
+        )),
+    }
+}
+
+/// Removes a slot even if it is booked.
+///
+/// Affected appointments are moved to `move_to_slot_id` when it is set, or
+/// cancelled with `reason` otherwise.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ForceRemoveSlotDto {
+    pub reason: Option<String>,
+    pub move_to_slot_id: Option<Uuid>,
+}
+
+impl ForceRemoveSlotDto {
+    pub fn to_cascade_action(
+        &self,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<SlotCascadeAction> {
+        to_cascade_action(&self.reason, self.move_to_slot_id, current_time)
+    }
+}
+
+/// Lowers a slot's capacity below its current appointment count.
+///
+/// The most recently booked appointments are moved to `move_to_slot_id` when it
+/// is set, or cancelled with `reason` otherwise.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ForceShrinkSlotDto {
+    pub max_appointment_count: i32,
+    pub reason: Option<String>,
+    pub move_to_slot_id: Option<Uuid>,
+}
+
+impl ForceShrinkSlotDto {
+    pub fn to_entity(&self, current_time: NaiveDateTime) -> DomainResult<EditSlotEntity> {
+        if self.max_appointment_count < 1 {
+            return Err(DomainError::validation(
+                "max_appointment_count must be at least 1",
+            ));
+        }
+
+        Ok(EditSlotEntity {
+            max_appointment_count: Some(self.max_appointment_count),
+            end_time: None,
+            updated_at: current_time,
+        })
+    }
+
+    pub fn to_cascade_action(
+        &self,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<SlotCascadeAction> {
+        to_cascade_action(&self.reason, self.move_to_slot_id, current_time)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "status")]
+pub enum AffectedAppointmentOutcome {
+    Cancelled,
+    Moved { slot_id: Uuid },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AffectedAppointmentModel {
+    pub appointment_id: Uuid,
+    pub patient_id: i32,
+    pub outcome: AffectedAppointmentOutcome,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SlotCascadeResponseModel {
+    pub slot_id: Uuid,
+    pub affected_appointments: Vec<AffectedAppointmentModel>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SlotSortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// Query parameters of the public slot search.
+///
+/// `only_available` and `only_future` default to `true` so patients only see
+/// slots they can still book.
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GetSlotsQuery {
+    /// Only return slots of this doctor.
+    pub doctor_id: Option<i32>,
+    /// Only return slots starting at or after this time.
+    #[param(value_type = Option<String>, format = DateTime)]
+    pub start_from: Option<ClientDateTime>,
+    /// Only return slots starting at or before this time.
+    #[param(value_type = Option<String>, format = DateTime)]
+    pub start_to: Option<ClientDateTime>,
+    /// Only return slots that still have free capacity. Defaults to `true`.
+    pub only_available: Option<bool>,
+    /// Only return slots that have not started yet. Defaults to `true`.
+    pub only_future: Option<bool>,
+    /// Page number, starting at 1.
+    pub page: Option<i64>,
+    /// Page size, between 1 and 100. Defaults to 20.
+    pub limit: Option<i64>,
+    /// Sort by `start_time`, either `asc` or `desc`. Defaults to `asc`.
+    pub sort: Option<SlotSortOrder>,
+    /// IANA time zone that `start_from`/`start_to` without a UTC offset are
+    /// read in and that slot times are rendered in. Defaults to `UTC`.
+    pub tz: Option<String>,
+}
+
+impl GetSlotsQuery {
+    pub fn to_timezone(&self) -> DomainResult<Tz> {
+        requested_timezone(self.tz.as_deref())
+    }
+
+    pub fn to_filter(&self, current_time: DateTime<Utc>) -> DomainResult<SlotFilter> {
+        let page = self.page.unwrap_or(1);
+        if page < 1 {
+            return Err(DomainError::validation("page must be at least 1"));
+        }
+
+        let limit = self.limit.unwrap_or(DEFAULT_SLOTS_PAGE_LIMIT);
+        if !(1..=MAX_SLOTS_PAGE_LIMIT).contains(&limit) {
+            return Err(DomainError::validation(format!(
+                "limit must be between 1 and {}",
+                MAX_SLOTS_PAGE_LIMIT
+            )));
+        }
+
+        let timezone = self.to_timezone()?;
+        let start_from = self
+            .start_from
+            .map(|start_from| start_from.to_utc(timezone))
+            .transpose()?;
+        let start_to = self
+            .start_to
+            .map(|start_to| start_to.to_utc(timezone))
+            .transpose()?;
+
+        if let (Some(start_from), Some(start_to)) = (start_from, start_to)
+            && start_from > start_to
+        {
+            return Err(DomainError::validation(
+                "start_from must not be after start_to",
+            ));
+        }
+
+        Ok(SlotFilter {
+            doctor_id: self.doctor_id,
+            start_from,
+            start_to,
+            only_available: self.only_available.unwrap_or(true),
+            starts_after: self.only_future.unwrap_or(true).then_some(current_time),
+            page,
+            limit,
+            sort: self.sort.unwrap_or_default(),
+        })
+    }
+}
+
+/// Validated slot search filter passed down to the repository.
+#[derive(Debug, Clone)]
+pub struct SlotFilter {
+    pub doctor_id: Option<i32>,
+    pub start_from: Option<DateTime<Utc>>,
+    pub start_to: Option<DateTime<Utc>>,
+    pub only_available: bool,
+    pub starts_after: Option<DateTime<Utc>>,
+    pub page: i64,
+    pub limit: i64,
+    pub sort: SlotSortOrder,
+}
+
+impl SlotFilter {
+    /// Rows skipped before the requested page. Refused when `page` is so large
+    /// that it does not fit in an `i64`.
+    pub fn offset(&self) -> DomainResult<i64> {
+        (self.page - 1)
+            .checked_mul(self.limit)
+            .ok_or_else(|| DomainError::validation("page is too large"))
+    }
+}
+
+/// A slot with its times rendered in a requested time zone.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SlotModel {
+    pub id: Uuid,
+    pub doctor_id: i32,
+    pub current_appointment_count: i32,
+    pub max_appointment_count: i32,
+    pub start_time: DateTime<FixedOffset>,
+    pub end_time: DateTime<FixedOffset>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub template_id: Option<Uuid>,
+}
+
+impl SlotModel {
+    pub fn from_entity(slot_entity: SlotEntity, timezone: Tz) -> Self {
+        Self {
+            id: slot_entity.id,
+            doctor_id: slot_entity.doctor_id,
+            current_appointment_count: slot_entity.current_appointment_count,
+            max_appointment_count: slot_entity.max_appointment_count,
+            start_time: in_timezone(slot_entity.start_time, timezone),
+            end_time: in_timezone(slot_entity.end_time, timezone),
+            created_at: slot_entity.created_at,
+            updated_at: slot_entity.updated_at,
+            deleted_at: slot_entity.deleted_at,
+            template_id: slot_entity.template_id,
         }
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct GetSlotsResponseModel {
-    pub slots: Vec<SlotEntity>,
+    pub slots: Vec<SlotModel>,
+    pub total: i64,
+    pub page: i64,
+    pub limit: i64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct GetDoctorSlotsResponseModel {
-    pub slots: Vec<SlotEntity>,
+    pub slots: Vec<SlotModel>,
 }
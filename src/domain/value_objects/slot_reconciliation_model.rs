@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::entities::slots::SlotAppointmentCountDriftEntity;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SlotAppointmentCountDriftOutcome {
+    /// Reported only, nothing was written.
+    Detected,
+    /// `current_appointment_count` now matches the slot's appointments.
+    Repaired,
+    /// The slot holds more appointments than its capacity, which has to be
+    /// sorted out by hand.
+    OverCapacity,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlotAppointmentCountDriftModel {
+    pub slot_id: Uuid,
+    pub doctor_id: i32,
+    pub max_appointment_count: i32,
+    pub recorded_appointment_count: i32,
+    pub actual_appointment_count: i32,
+    pub outcome: SlotAppointmentCountDriftOutcome,
+}
+
+impl SlotAppointmentCountDriftModel {
+    pub fn from_entity(
+        entity: SlotAppointmentCountDriftEntity,
+        outcome: SlotAppointmentCountDriftOutcome,
+    ) -> Self {
+        Self {
+            slot_id: entity.slot_id,
+            doctor_id: entity.doctor_id,
+            max_appointment_count: entity.max_appointment_count,
+            recorded_appointment_count: entity.current_appointment_count,
+            actual_appointment_count: entity.actual_appointment_count,
+            outcome,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlotReconciliationReportModel {
+    pub repair: bool,
+    pub drifts: Vec<SlotAppointmentCountDriftModel>,
+    pub repaired_count: usize,
+}
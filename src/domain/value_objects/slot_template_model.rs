@@ -0,0 +1,290 @@
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone,
+    Weekday,
+};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::slot_templates::{AddSlotTemplateEntity, EditSlotTemplateEntity, SlotTemplateEntity},
+    errors::{DomainError, DomainResult},
+};
+
+pub const MIN_SLOT_DURATION_MINUTES: i32 = 5;
+pub const MAX_SLOT_TEMPLATE_DAYS: i64 = 366;
+
+/// A validated weekly availability rule, e.g. "Mon/Wed 09:00-12:00,
+/// 20-minute slots, capacity 3, from 2025-10-01 until 2025-12-31".
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotTemplateRule {
+    pub weekdays: Vec<Weekday>,
+    pub day_start_time: NaiveTime,
+    pub day_end_time: NaiveTime,
+    pub slot_duration_minutes: i32,
+    pub max_appointment_count: i32,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+}
+
+impl SlotTemplateRule {
+    pub fn validate(&self) -> DomainResult<()> {
+        if self.weekdays.is_empty() {
+            return Err(DomainError::validation("weekdays must not be empty"));
+        }
+        if self.day_start_time >= self.day_end_time {
+            return Err(DomainError::validation(
+                "day_start_time must be before day_end_time",
+            ));
+        }
+        if self.slot_duration_minutes < MIN_SLOT_DURATION_MINUTES {
+            return Err(DomainError::validation(format!(
+                "slot_duration_minutes must be at least {}",
+                MIN_SLOT_DURATION_MINUTES
+            )));
+        }
+        if Duration::minutes(self.slot_duration_minutes.into())
+            > self.day_end_time - self.day_start_time
+        {
+            return Err(DomainError::validation(
+                "slot_duration_minutes does not fit between day_start_time and day_end_time",
+            ));
+        }
+        if self.max_appointment_count < 1 {
+            return Err(DomainError::validation(
+                "max_appointment_count must be at least 1",
+            ));
+        }
+        if self.start_date > self.end_date {
+            return Err(DomainError::validation(
+                "start_date must not be after end_date",
+            ));
+        }
+        if (self.end_date - self.start_date).num_days() >= MAX_SLOT_TEMPLATE_DAYS {
+            return Err(DomainError::validation(format!(
+                "A template can span at most {} days",
+                MAX_SLOT_TEMPLATE_DAYS
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Every `(start_time, end_time)` pair this rule produces in `timezone`, in
+    /// chronological order.
+    ///
+    /// Slots touching a wall-clock time skipped by a daylight saving change are
+    /// left out; repeated wall-clock times use their first occurrence.
+    pub fn slot_times(&self, timezone: Tz) -> Vec<(DateTime<FixedOffset>, DateTime<FixedOffset>)> {
+        let slot_duration = Duration::minutes(self.slot_duration_minutes.into());
+
+        self.start_date
+            .iter_days()
+            .take_while(|date| *date <= self.end_date)
+            .filter(|date| self.weekdays.contains(&date.weekday()))
+            .flat_map(|date| {
+                let day_end = date.and_time(self.day_end_time);
+                std::iter::successors(Some(date.and_time(self.day_start_time)), move |start| {
+                    Some(*start + slot_duration)
+                })
+                .map(move |start| (start, start + slot_duration))
+                .take_while(move |(_, end)| *end <= day_end)
+            })
+            .filter_map(|(start, end)| {
+                let start = timezone.from_local_datetime(&start).earliest()?;
+                let end = timezone.from_local_datetime(&end).earliest()?;
+                Some((start.fixed_offset(), end.fixed_offset()))
+            })
+            .collect()
+    }
+
+    pub fn weekdays_to_string(&self) -> String {
+        self.weekdays
+            .iter()
+            .map(|weekday| weekday.to_string())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    pub fn to_add_entity(
+        &self,
+        doctor_id: i32,
+        current_time: NaiveDateTime,
+    ) -> AddSlotTemplateEntity {
+        AddSlotTemplateEntity {
+            doctor_id,
+            weekdays: self.weekdays_to_string(),
+            day_start_time: self.day_start_time,
+            day_end_time: self.day_end_time,
+            slot_duration_minutes: self.slot_duration_minutes,
+            max_appointment_count: self.max_appointment_count,
+            start_date: self.start_date,
+            end_date: self.end_date,
+            created_at: current_time,
+            updated_at: current_time,
+            deleted_at: None,
+        }
+    }
+
+    pub fn to_edit_entity(&self, current_time: NaiveDateTime) -> EditSlotTemplateEntity {
+        EditSlotTemplateEntity {
+            weekdays: self.weekdays_to_string(),
+            day_start_time: self.day_start_time,
+            day_end_time: self.day_end_time,
+            slot_duration_minutes: self.slot_duration_minutes,
+            max_appointment_count: self.max_appointment_count,
+            start_date: self.start_date,
+            end_date: self.end_date,
+            updated_at: current_time,
+        }
+    }
+}
+
+impl TryFrom<&SlotTemplateEntity> for SlotTemplateRule {
+    type Error = DomainError;
+
+    fn try_from(slot_template_entity: &SlotTemplateEntity) -> DomainResult<Self> {
+        let weekdays = slot_template_entity
+            .weekdays
+            .split(',')
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+
+        Ok(SlotTemplateRule {
+            weekdays: parse_weekdays(&weekdays)?,
+            day_start_time: slot_template_entity.day_start_time,
+            day_end_time: slot_template_entity.day_end_time,
+            slot_duration_minutes: slot_template_entity.slot_duration_minutes,
+            max_appointment_count: slot_template_entity.max_appointment_count,
+            start_date: slot_template_entity.start_date,
+            end_date: slot_template_entity.end_date,
+        })
+    }
+}
+
+/// Parses weekday names such as `"Mon"` or `"monday"`, dropping duplicates.
+fn parse_weekdays(weekdays: &[String]) -> DomainResult<Vec<Weekday>> {
+    let mut parsed = Vec::with_capacity(weekdays.len());
+    for weekday in weekdays {
+        let weekday = weekday
+            .trim()
+            .parse::<Weekday>()
+            .map_err(|_| DomainError::validation(format!("Invalid weekday: {}", weekday)))?;
+        if !parsed.contains(&weekday) {
+            parsed.push(weekday);
+        }
+    }
+    parsed.sort_by_key(|weekday| weekday.num_days_from_monday());
+
+    Ok(parsed)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AddSlotTemplateDto {
+    /// Weekday names, e.g. `["Mon", "Wed"]`.
+    pub weekdays: Vec<String>,
+    pub day_start_time: NaiveTime,
+    pub day_end_time: NaiveTime,
+    pub slot_duration_minutes: i32,
+    pub max_appointment_count: i32,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+}
+
+impl AddSlotTemplateDto {
+    pub fn to_rule(&self) -> DomainResult<SlotTemplateRule> {
+        let rule = SlotTemplateRule {
+            weekdays: parse_weekdays(&self.weekdays)?,
+            day_start_time: self.day_start_time,
+            day_end_time: self.day_end_time,
+            slot_duration_minutes: self.slot_duration_minutes,
+            max_appointment_count: self.max_appointment_count,
+            start_date: self.start_date,
+            end_date: self.end_date,
+        };
+        rule.validate()?;
+
+        Ok(rule)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct EditSlotTemplateDto {
+    pub weekdays: Option<Vec<String>>,
+    pub day_start_time: Option<NaiveTime>,
+    pub day_end_time: Option<NaiveTime>,
+    pub slot_duration_minutes: Option<i32>,
+    pub max_appointment_count: Option<i32>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+}
+
+impl EditSlotTemplateDto {
+    /// Applies the edited fields on top of `current_rule`.
+    pub fn apply_to(&self, current_rule: &SlotTemplateRule) -> DomainResult<SlotTemplateRule> {
+        let weekdays = match &self.weekdays {
+            Some(weekdays) => parse_weekdays(weekdays)?,
+            None => current_rule.weekdays.clone(),
+        };
+
+        let rule = SlotTemplateRule {
+            weekdays,
+            day_start_time: self.day_start_time.unwrap_or(current_rule.day_start_time),
+            day_end_time: self.day_end_time.unwrap_or(current_rule.day_end_time),
+            slot_duration_minutes: self
+                .slot_duration_minutes
+                .unwrap_or(current_rule.slot_duration_minutes),
+            max_appointment_count: self
+                .max_appointment_count
+                .unwrap_or(current_rule.max_appointment_count),
+            start_date: self.start_date.unwrap_or(current_rule.start_date),
+            end_date: self.end_date.unwrap_or(current_rule.end_date),
+        };
+        rule.validate()?;
+
+        Ok(rule)
+    }
+}
+
+/// What happened, or would happen, to one slot produced by a template.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "status")]
+pub enum SlotOccurrenceOutcome {
+    /// Preview only: the slot would be created.
+    Available,
+    Created {
+        slot_id: Uuid,
+    },
+    /// Skipped because it overlaps another slot of the same doctor.
+    Overlapping,
+    /// Skipped because it starts in the past.
+    Past,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SlotOccurrenceModel {
+    /// In the doctor's time zone.
+    pub start_time: DateTime<FixedOffset>,
+    /// In the doctor's time zone.
+    pub end_time: DateTime<FixedOffset>,
+    pub outcome: SlotOccurrenceOutcome,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PreviewSlotTemplateResponseModel {
+    pub occurrences: Vec<SlotOccurrenceModel>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MaterializeSlotTemplateResponseModel {
+    pub template_id: Uuid,
+    /// Unbooked future slots removed before regenerating (edit only).
+    pub removed_slot_count: usize,
+    pub occurrences: Vec<SlotOccurrenceModel>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GetSlotTemplatesResponseModel {
+    pub templates: Vec<SlotTemplateEntity>,
+}
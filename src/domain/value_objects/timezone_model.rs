@@ -0,0 +1,117 @@
+use chrono::{DateTime, FixedOffset, LocalResult, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::domain::{
+    entities::doctor_timezones::{DoctorTimezoneEntity, SetDoctorTimezoneEntity},
+    errors::{DomainError, DomainResult},
+};
+
+/// Time zone used for doctors that have not set one and for responses that do
+/// not ask for one. Doctors with slots from before time zones were kept got
+/// theirs from the migration that introduced them.
+pub const DEFAULT_TIMEZONE: Tz = Tz::UTC;
+
+pub fn parse_timezone(timezone: &str) -> DomainResult<Tz> {
+    timezone
+        .trim()
+        .parse::<Tz>()
+        .map_err(|_| DomainError::validation(format!("Unknown time zone: {}", timezone)))
+}
+
+/// The time zone a client asked for, or the default when it did not ask for one.
+pub fn requested_timezone(timezone: Option<&str>) -> DomainResult<Tz> {
+    match timezone {
+        Some(timezone) => parse_timezone(timezone),
+        None => Ok(DEFAULT_TIMEZONE),
+    }
+}
+
+/// The doctor's own time zone, or the default when the doctor has not set one.
+pub fn doctor_timezone(doctor_timezone: Option<&DoctorTimezoneEntity>) -> DomainResult<Tz> {
+    match doctor_timezone {
+        Some(doctor_timezone) => parse_timezone(&doctor_timezone.timezone),
+        None => Ok(DEFAULT_TIMEZONE),
+    }
+}
+
+/// Renders a stored instant as wall-clock time with the UTC offset of `timezone`.
+pub fn in_timezone(time: DateTime<Utc>, timezone: Tz) -> DateTime<FixedOffset> {
+    time.with_timezone(&timezone).fixed_offset()
+}
+
+/// A point in time sent by a client.
+///
+/// Either carries its own UTC offset (`2025-10-01T09:00:00+07:00`) or is a
+/// wall-clock time (`2025-10-01T09:00:00`) read in the time zone chosen by
+/// the endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ClientDateTime {
+    Offset(DateTime<FixedOffset>),
+    Local(NaiveDateTime),
+}
+
+impl ClientDateTime {
+    pub fn to_utc(&self, timezone: Tz) -> DomainResult<DateTime<Utc>> {
+        match self {
+            ClientDateTime::Offset(time) => Ok(time.with_timezone(&Utc)),
+            ClientDateTime::Local(time) => match timezone.from_local_datetime(time) {
+                LocalResult::Single(time) => Ok(time.with_timezone(&Utc)),
+                LocalResult::Ambiguous(_, _) => Err(DomainError::validation(format!(
+                    "{} is ambiguous in {}, add a UTC offset",
+                    time, timezone
+                ))),
+                LocalResult::None => Err(DomainError::validation(format!(
+                    "{} does not exist in {}",
+                    time, timezone
+                ))),
+            },
+        }
+    }
+}
+
+/// Optional time zone to render times in.
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TimezoneQuery {
+    /// IANA time zone, e.g. `Asia/Bangkok`. Defaults to `UTC`.
+    pub tz: Option<String>,
+}
+
+impl TimezoneQuery {
+    pub fn to_timezone(&self) -> DomainResult<Tz> {
+        requested_timezone(self.tz.as_deref())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SetDoctorTimezoneDto {
+    /// IANA time zone, e.g. `Asia/Bangkok`.
+    pub timezone: String,
+}
+
+impl SetDoctorTimezoneDto {
+    pub fn to_entity(
+        &self,
+        doctor_id: i32,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<SetDoctorTimezoneEntity> {
+        let timezone = parse_timezone(&self.timezone)?;
+
+        Ok(SetDoctorTimezoneEntity {
+            doctor_id,
+            timezone: timezone.name().to_string(),
+            updated_at: current_time,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GetDoctorTimezoneResponseModel {
+    /// The doctor's own setting, `None` when the doctor has not set one.
+    pub doctor_timezone: Option<DoctorTimezoneEntity>,
+    /// The time zone wall-clock slot times of the doctor are read in.
+    pub effective_timezone: String,
+}
@@ -0,0 +1,52 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{appointments::AddAppointmentEntity, waitlist_entries::WaitlistEntryEntity},
+    value_objects::appointment_status::AppointmentStatus,
+};
+
+/// The appointment a waitlisted patient gets once a place frees up.
+pub fn to_promoted_appointment_entity(
+    waitlist_entry: &WaitlistEntryEntity,
+    current_time: NaiveDateTime,
+) -> AddAppointmentEntity {
+    AddAppointmentEntity {
+        slot_id: waitlist_entry.slot_id,
+        patient_id: waitlist_entry.patient_id,
+        patient_abnormal_symptom: waitlist_entry.patient_abnormal_symptom.clone(),
+        patient_is_missed_medication: waitlist_entry.patient_is_missed_medication.clone(),
+        patient_blood_test_status: waitlist_entry.patient_blood_test_status.clone(),
+        patient_is_overdue_medication: waitlist_entry.patient_is_overdue_medication.clone(),
+        patient_is_partner_hiv_positive: waitlist_entry.patient_is_partner_hiv_positive.clone(),
+        status: AppointmentStatus::Waiting.to_string(),
+        created_at: current_time,
+        updated_at: current_time,
+        deleted_at: None,
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct JoinWaitlistResponseModel {
+    pub waitlist_entry_id: Uuid,
+    /// 1 means the patient is next in line.
+    pub position: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WaitlistPositionModel {
+    pub id: Uuid,
+    pub slot_id: Uuid,
+    pub doctor_id: i32,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub position: i64,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GetWaitlistResponseModel {
+    pub entries: Vec<WaitlistPositionModel>,
+}
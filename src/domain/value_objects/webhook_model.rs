@@ -0,0 +1,494 @@
+use std::{
+    fmt,
+    net::{IpAddr, Ipv4Addr},
+    str::FromStr,
+};
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use hmac::{Hmac, Mac};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use url::{Host, Url};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::domain::{
+    entities::{
+        webhook_deliveries::{AddWebhookDeliveryEntity, WebhookDeliveryEntity},
+        webhook_subscriptions::{
+            AddWebhookSubscriptionEntity, EditWebhookSubscriptionEntity, WebhookSubscriptionEntity,
+        },
+    },
+    errors::{DomainError, DomainResult},
+    value_objects::outbox_model::{DOMAIN_EVENT_TYPES, OutboxMessage},
+};
+
+const WEBHOOK_SECRET_TAG: &str = "whsec";
+const WEBHOOK_SECRET_BYTES: usize = 32;
+const MIN_WEBHOOK_SECRET_LENGTH: usize = 16;
+const MAX_WEBHOOK_SECRET_LENGTH: usize = 255;
+const MAX_WEBHOOK_URL_LENGTH: usize = 2048;
+
+pub const DEFAULT_WEBHOOK_DELIVERIES_LIMIT: i64 = 50;
+pub const MAX_WEBHOOK_DELIVERIES_LIMIT: i64 = 200;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub enum WebhookDeliveryStatus {
+    /// Waiting for its first or next attempt.
+    Pending,
+    Succeeded,
+    /// Given up on after its last attempt.
+    Failed,
+}
+
+impl fmt::Display for WebhookDeliveryStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WebhookDeliveryStatus::Pending => write!(f, "Pending"),
+            WebhookDeliveryStatus::Succeeded => write!(f, "Succeeded"),
+            WebhookDeliveryStatus::Failed => write!(f, "Failed"),
+        }
+    }
+}
+
+impl FromStr for WebhookDeliveryStatus {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Pending" => Ok(WebhookDeliveryStatus::Pending),
+            "Succeeded" => Ok(WebhookDeliveryStatus::Succeeded),
+            "Failed" => Ok(WebhookDeliveryStatus::Failed),
+            _ => Err(DomainError::Internal(anyhow::anyhow!(
+                "Unknown webhook delivery status: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// Whether webhooks may be sent to `ip`. Loopback, private, link-local (such
+/// as the cloud metadata address 169.254.169.254), shared, unspecified,
+/// multicast and reserved addresses are refused, so subscribers cannot reach
+/// the network the booking service runs in.
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [first, second, third, _] = ip.octets();
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                || ip.is_documentation()
+                // 0.0.0.0/8, 100.64.0.0/10 (shared), 192.0.0.0/24,
+                // 198.18.0.0/15 (benchmarking) and 240.0.0.0/4 (reserved).
+                || first == 0
+                || (first == 100 && (64..128).contains(&second))
+                || (first == 192 && second == 0 && third == 0)
+                || (first == 198 && (18..20).contains(&second))
+                || first >= 240)
+        }
+        IpAddr::V6(ip) => {
+            if let Some(ip) = ip.to_ipv4_mapped() {
+                return is_public_ip(IpAddr::V4(ip));
+            }
+            let segments = ip.segments();
+            // 64:ff9b::/96 translates to the IPv4 address in its last 32 bits.
+            if segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0] {
+                let [.., high, low] = segments;
+                return is_public_ip(IpAddr::V4(Ipv4Addr::from(
+                    (u32::from(high) << 16) | u32::from(low),
+                )));
+            }
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_multicast()
+                // fc00::/7 (unique local), fe80::/10 (link-local),
+                // fec0::/10 (site-local) and 2001:db8::/32 (documentation).
+                || (segments[0] & 0xfe00) == 0xfc00
+                || (segments[0] & 0xffc0) == 0xfe80
+                || (segments[0] & 0xffc0) == 0xfec0
+                || (segments[0] == 0x2001 && segments[1] == 0x0db8))
+        }
+    }
+}
+
+/// Refuses a webhook URL whose host is not reachable from the internet: a
+/// non-public IP address, `localhost`, or a name only an internal resolver
+/// knows. Names that resolve to such an address are refused when sending.
+pub fn validate_public_host(url: &str) -> DomainResult<()> {
+    let url = Url::parse(url).map_err(|e| DomainError::validation(format!("url {}", e)))?;
+
+    let is_public = match url.host() {
+        Some(Host::Ipv4(ip)) => is_public_ip(IpAddr::V4(ip)),
+        Some(Host::Ipv6(ip)) => is_public_ip(IpAddr::V6(ip)),
+        Some(Host::Domain(domain)) => {
+            let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+            domain.contains('.')
+                && !domain.ends_with(".localhost")
+                && !domain.ends_with(".local")
+                && !domain.ends_with(".internal")
+        }
+        None => false,
+    };
+    if !is_public {
+        return Err(DomainError::validation(
+            "url must point at a public host, not a private or local address",
+        ));
+    }
+
+    Ok(())
+}
+
+fn validate_url(url: &str) -> DomainResult<()> {
+    if url.len() > MAX_WEBHOOK_URL_LENGTH {
+        return Err(DomainError::validation(format!(
+            "url must be at most {} characters",
+            MAX_WEBHOOK_URL_LENGTH
+        )));
+    }
+    if !url.starts_with("https://") {
+        return Err(DomainError::validation("url must be an https:// URL"));
+    }
+
+    validate_public_host(url)
+}
+
+fn validate_event_types(event_types: &[String]) -> DomainResult<()> {
+    if let Some(event_type) = event_types
+        .iter()
+        .find(|event_type| !DOMAIN_EVENT_TYPES.contains(&event_type.as_str()))
+    {
+        return Err(DomainError::validation(format!(
+            "Unknown event type {}, expected one of {}",
+            event_type,
+            DOMAIN_EVENT_TYPES.join(", ")
+        )));
+    }
+
+    Ok(())
+}
+
+fn generate_secret() -> String {
+    let secret = rand::rng()
+        .random::<[u8; WEBHOOK_SECRET_BYTES]>()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>();
+
+    format!("{}_{}", WEBHOOK_SECRET_TAG, secret)
+}
+
+/// `X-MedBook-Signature` of a delivery: an HMAC-SHA256 of
+/// `<timestamp>.<body>` keyed with the subscription's secret.
+pub fn webhook_signature(secret: &str, timestamp: i64, body: &str) -> DomainResult<String> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .map_err(|e| DomainError::Internal(e.into()))?;
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+
+    Ok(format!("sha256={:x}", mac.finalize().into_bytes()))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AddWebhookSubscriptionDto {
+    pub url: String,
+    /// Event types to receive, every event when empty.
+    #[serde(default)]
+    pub event_types: Vec<String>,
+    /// Doctors whose appointments and slots to receive events about, every
+    /// doctor when empty.
+    #[serde(default)]
+    pub doctor_ids: Vec<i32>,
+    /// Generated when not given. Returned only in the response to this request.
+    pub secret: Option<String>,
+}
+
+impl AddWebhookSubscriptionDto {
+    pub fn to_entity(
+        &self,
+        service_api_key_id: i32,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<AddWebhookSubscriptionEntity> {
+        validate_url(&self.url)?;
+        validate_event_types(&self.event_types)?;
+
+        let secret = match &self.secret {
+            Some(secret)
+                if (MIN_WEBHOOK_SECRET_LENGTH..=MAX_WEBHOOK_SECRET_LENGTH)
+                    .contains(&secret.len()) =>
+            {
+                secret.clone()
+            }
+            Some(_) => {
+                return Err(DomainError::validation(format!(
+                    "secret must be between {} and {} characters",
+                    MIN_WEBHOOK_SECRET_LENGTH, MAX_WEBHOOK_SECRET_LENGTH
+                )));
+            }
+            None => generate_secret(),
+        };
+
+        Ok(AddWebhookSubscriptionEntity {
+            service_api_key_id,
+            url: self.url.clone(),
+            event_types: self.event_types.clone(),
+            doctor_ids: self.doctor_ids.clone(),
+            secret,
+            created_at: current_time,
+            updated_at: current_time,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct EditWebhookSubscriptionDto {
+    pub url: Option<String>,
+    /// Event types to receive, every event when empty.
+    pub event_types: Option<Vec<String>>,
+    /// Doctors whose appointments and slots to receive events about, every
+    /// doctor when empty.
+    pub doctor_ids: Option<Vec<i32>>,
+    /// Enabling a subscription that was disabled after repeated failures also
+    /// resumes its pending deliveries.
+    pub enabled: Option<bool>,
+}
+
+impl EditWebhookSubscriptionDto {
+    pub fn to_entity(
+        &self,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<EditWebhookSubscriptionEntity> {
+        if let Some(url) = &self.url {
+            validate_url(url)?;
+        }
+        if let Some(event_types) = &self.event_types {
+            validate_event_types(event_types)?;
+        }
+
+        Ok(EditWebhookSubscriptionEntity {
+            url: self.url.clone(),
+            event_types: self.event_types.clone(),
+            doctor_ids: self.doctor_ids.clone(),
+            disabled_at: self
+                .enabled
+                .map(|enabled| (!enabled).then_some(current_time)),
+            consecutive_failures: self.enabled.filter(|enabled| *enabled).map(|_| 0),
+            updated_at: current_time,
+        })
+    }
+}
+
+/// A subscription as shown to its owner. The secret is left out.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WebhookSubscriptionModel {
+    pub id: Uuid,
+    pub url: String,
+    pub event_types: Vec<String>,
+    pub doctor_ids: Vec<i32>,
+    pub enabled: bool,
+    /// Failed attempts in a row, the subscription is disabled once it reaches
+    /// the configured limit.
+    pub consecutive_failures: i32,
+    pub disabled_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl WebhookSubscriptionModel {
+    pub fn from_entity(webhook_subscription_entity: WebhookSubscriptionEntity) -> Self {
+        Self {
+            id: webhook_subscription_entity.id,
+            url: webhook_subscription_entity.url,
+            event_types: webhook_subscription_entity.event_types,
+            doctor_ids: webhook_subscription_entity.doctor_ids,
+            enabled: webhook_subscription_entity.disabled_at.is_none(),
+            consecutive_failures: webhook_subscription_entity.consecutive_failures,
+            disabled_at: webhook_subscription_entity.disabled_at,
+            created_at: webhook_subscription_entity.created_at,
+            updated_at: webhook_subscription_entity.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AddWebhookSubscriptionResponseModel {
+    #[serde(flatten)]
+    pub subscription: WebhookSubscriptionModel,
+    /// Shown only once, keep it to verify `X-MedBook-Signature`.
+    pub secret: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GetWebhookSubscriptionsResponseModel {
+    pub subscriptions: Vec<WebhookSubscriptionModel>,
+}
+
+/// What a subscriber receives, the same for every attempt of a delivery.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WebhookEventModel {
+    /// Id of the event, the same for every subscription it is delivered to.
+    pub id: Uuid,
+    pub event_type: String,
+    /// The appointment or slot the event is about.
+    pub aggregate_id: Uuid,
+    pub occurred_at: DateTime<Utc>,
+    pub payload: serde_json::Value,
+}
+
+impl WebhookEventModel {
+    pub fn from_message(outbox_message: &OutboxMessage) -> Self {
+        Self {
+            id: outbox_message.id,
+            event_type: outbox_message.event_type.clone(),
+            aggregate_id: outbox_message.aggregate_id,
+            occurred_at: outbox_message.occurred_at,
+            payload: outbox_message.payload.clone(),
+        }
+    }
+
+    pub fn to_delivery_entity(
+        &self,
+        subscription_id: Uuid,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<AddWebhookDeliveryEntity> {
+        let body = serde_json::to_value(self).map_err(|e| DomainError::Internal(e.into()))?;
+
+        Ok(AddWebhookDeliveryEntity {
+            subscription_id,
+            outbox_event_id: self.id,
+            event_type: self.event_type.clone(),
+            body,
+            status: WebhookDeliveryStatus::Pending.to_string(),
+            next_attempt_at: current_time,
+            created_at: current_time,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WebhookDeliveryModel {
+    pub id: Uuid,
+    pub subscription_id: Uuid,
+    pub event_id: Uuid,
+    pub event_type: String,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: i32,
+    /// When the next attempt is due, while the delivery is `Pending`.
+    pub next_attempt_at: Option<NaiveDateTime>,
+    pub last_response_status: Option<i32>,
+    pub last_error: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub delivered_at: Option<NaiveDateTime>,
+}
+
+impl WebhookDeliveryModel {
+    pub fn from_entity(webhook_delivery_entity: WebhookDeliveryEntity) -> DomainResult<Self> {
+        let status = webhook_delivery_entity
+            .status
+            .parse::<WebhookDeliveryStatus>()?;
+
+        Ok(Self {
+            id: webhook_delivery_entity.id,
+            subscription_id: webhook_delivery_entity.subscription_id,
+            event_id: webhook_delivery_entity.outbox_event_id,
+            event_type: webhook_delivery_entity.event_type,
+            status,
+            attempts: webhook_delivery_entity.attempts,
+            next_attempt_at: (status == WebhookDeliveryStatus::Pending)
+                .then_some(webhook_delivery_entity.next_attempt_at),
+            last_response_status: webhook_delivery_entity.last_response_status,
+            last_error: webhook_delivery_entity.last_error,
+            created_at: webhook_delivery_entity.created_at,
+            delivered_at: webhook_delivery_entity.delivered_at,
+        })
+    }
+}
+
+/// Query parameters of a subscription's delivery log.
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GetWebhookDeliveriesQuery {
+    /// Only return deliveries in this status.
+    pub status: Option<WebhookDeliveryStatus>,
+    /// Number of most recent deliveries, between 1 and 200. Defaults to 50.
+    pub limit: Option<i64>,
+}
+
+impl GetWebhookDeliveriesQuery {
+    pub fn to_limit(&self) -> DomainResult<i64> {
+        let limit = self.limit.unwrap_or(DEFAULT_WEBHOOK_DELIVERIES_LIMIT);
+        if !(1..=MAX_WEBHOOK_DELIVERIES_LIMIT).contains(&limit) {
+            return Err(DomainError::validation(format!(
+                "limit must be between 1 and {}",
+                MAX_WEBHOOK_DELIVERIES_LIMIT
+            )));
+        }
+
+        Ok(limit)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GetWebhookDeliveriesResponseModel {
+    pub deliveries: Vec<WebhookDeliveryModel>,
+}
+
+/// One signed attempt of a delivery, ready to be sent.
+#[derive(Debug, Clone)]
+pub struct WebhookRequestModel {
+    pub delivery_id: Uuid,
+    pub url: String,
+    pub event_id: Uuid,
+    pub event_type: String,
+    pub timestamp: i64,
+    pub signature: String,
+    pub body: String,
+}
+
+impl WebhookRequestModel {
+    pub fn sign(
+        webhook_delivery_entity: &WebhookDeliveryEntity,
+        webhook_subscription_entity: &WebhookSubscriptionEntity,
+        current_time: DateTime<Utc>,
+    ) -> DomainResult<Self> {
+        let body = webhook_delivery_entity.body.to_string();
+        let timestamp = current_time.timestamp();
+        let signature = webhook_signature(&webhook_subscription_entity.secret, timestamp, &body)?;
+
+        Ok(Self {
+            delivery_id: webhook_delivery_entity.id,
+            url: webhook_subscription_entity.url.clone(),
+            event_id: webhook_delivery_entity.outbox_event_id,
+            event_type: webhook_delivery_entity.event_type.clone(),
+            timestamp,
+            signature,
+            body,
+        })
+    }
+}
+
+/// How a subscriber answered one attempt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookAttemptModel {
+    /// `None` when no response was received.
+    pub response_status: Option<i32>,
+    /// `None` when the attempt succeeded.
+    pub error: Option<String>,
+}
+
+/// Outcome of one webhook dispatcher pass.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WebhookDispatchReportModel {
+    pub delivered: usize,
+    /// Deliveries that failed and are scheduled to be tried again.
+    pub retried: usize,
+    /// Deliveries given up on after their last attempt.
+    pub failed: Vec<Uuid>,
+    /// Subscriptions disabled because they kept failing.
+    pub disabled_subscriptions: Vec<Uuid>,
+}
@@ -1,11 +1,58 @@
+use axum::{
+    Json,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
 use serde::{Deserialize, Serialize};
+use tracing::error;
 use utoipa::ToSchema;
 
+use crate::domain::errors::DomainError;
+
 #[derive(Serialize, ToSchema)]
 pub struct ApiResponse<T> {
     pub data: Option<T>,
     pub message: Option<String>,
+    /// Machine readable error code, only set on failed requests.
+    pub code: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct EmptyResponseModel;
+
+pub fn status_code_of(e: &DomainError) -> StatusCode {
+    match e {
+        DomainError::NotFound(_) => StatusCode::NOT_FOUND,
+        DomainError::Conflict(_)
+        | DomainError::SlotFull(_)
+        | DomainError::SlotOverlap(_)
+        | DomainError::InvalidTransition(_) => StatusCode::CONFLICT,
+        DomainError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+        DomainError::Forbidden(_) => StatusCode::FORBIDDEN,
+        DomainError::PastTime(_)
+        | DomainError::BookingCutoff(_)
+        | DomainError::BookingHorizon(_)
+        | DomainError::ChangeNotice(_)
+        | DomainError::Validation(_) => StatusCode::UNPROCESSABLE_ENTITY,
+        DomainError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+impl IntoResponse for DomainError {
+    fn into_response(self) -> Response {
+        let status_code = status_code_of(&self);
+        if status_code == StatusCode::INTERNAL_SERVER_ERROR {
+            error!("{:?}", self);
+        }
+
+        (
+            status_code,
+            Json(ApiResponse::<EmptyResponseModel> {
+                data: None,
+                message: Some(self.to_string()),
+                code: Some(self.code().to_string()),
+            }),
+        )
+            .into_response()
+    }
+}
@@ -0,0 +1,64 @@
+use axum::{extract::FromRequestParts, http::request::Parts};
+
+use crate::domain::{
+    errors::{DomainError, DomainResult},
+    value_objects::{
+        actor::Actor,
+        service_api_key_model::{ServiceCredentialModel, ServiceScope},
+    },
+};
+
+/// The MedBook service an API key was issued to.
+///
+/// Put into the request extensions by `services_authorization` once the key
+/// has been verified. Handlers check the scope they need with `require`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthenticatedService {
+    pub id: i32,
+    pub service_name: String,
+    pub scopes: Vec<ServiceScope>,
+}
+
+impl AuthenticatedService {
+    pub fn from_model(service_credential_model: ServiceCredentialModel) -> Self {
+        Self {
+            id: service_credential_model.id,
+            service_name: service_credential_model.service_name,
+            scopes: service_credential_model.scopes,
+        }
+    }
+
+    pub fn require(&self, scope: ServiceScope) -> DomainResult<()> {
+        if !self.scopes.contains(&scope) {
+            return Err(DomainError::forbidden(format!(
+                "API key of {} does not have the {} scope",
+                self.service_name, scope
+            )));
+        }
+
+        Ok(())
+    }
+
+    pub fn to_actor(&self) -> Actor {
+        Actor::service(self.id)
+    }
+}
+
+impl<S> FromRequestParts<S> for AuthenticatedService
+where
+    S: Send + Sync,
+{
+    type Rejection = DomainError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<AuthenticatedService>()
+            .cloned()
+            .ok_or_else(|| {
+                DomainError::Internal(anyhow::anyhow!(
+                    "AuthenticatedService is missing, the route is not behind services_authorization"
+                ))
+            })
+    }
+}
@@ -0,0 +1,46 @@
+use axum::{extract::FromRequestParts, http::request::Parts};
+
+use crate::{
+    domain::{errors::DomainError, value_objects::actor::Actor},
+    infrastructure::jwt_authentication::jwt_model::Roles,
+};
+
+/// The user an access token was issued to.
+///
+/// Put into the request extensions by the authorization middleware once the
+/// token and its `role` claim have been verified, so a handler can only
+/// extract it on routes behind one of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuthenticatedUser {
+    pub id: i32,
+    pub role: Roles,
+}
+
+impl AuthenticatedUser {
+    pub fn to_actor(&self) -> Actor {
+        match self.role {
+            Roles::Patient => Actor::patient(self.id),
+            Roles::Doctor => Actor::doctor(self.id),
+            Roles::Staff => Actor::staff(self.id),
+        }
+    }
+}
+
+impl<S> FromRequestParts<S> for AuthenticatedUser
+where
+    S: Send + Sync,
+{
+    type Rejection = DomainError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<AuthenticatedUser>()
+            .copied()
+            .ok_or_else(|| {
+                DomainError::Internal(anyhow::anyhow!(
+                    "AuthenticatedUser is missing, the route is not behind an authorization middleware"
+                ))
+            })
+    }
+}
@@ -1,9 +1,9 @@
 use std::{net::SocketAddr, sync::Arc, time::Duration};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use axum::{
     Router,
-    http::{HeaderValue, Method, header},
+    http::{HeaderName, HeaderValue, Method, header},
     routing::get,
 };
 use tokio::net::TcpListener;
@@ -20,19 +20,43 @@ use crate::{
     config::{config_loader, config_model::DotEnvyConfig, stage::Stage},
     infrastructure::{
         axum_http::{routers, swagger},
+        jwt_authentication::jwks::JwksKeyStore,
+        outbox,
         postgres::postgres_connection::PgPoolSquad,
+        reminder,
     },
 };
 
 use super::default_routers;
 
 pub async fn start(config: Arc<DotEnvyConfig>, db_pool: Arc<PgPoolSquad>) -> Result<()> {
+    let jwt_verification = config_loader::get_jwt_verification_env()?;
+    if let Some(jwks_source) = &jwt_verification.jwks {
+        let key_count = JwksKeyStore::shared(
+            jwks_source,
+            Duration::from_secs(jwt_verification.jwks_cache_seconds),
+        )
+        .refresh()
+        .await
+        .with_context(|| format!("Failed to load JWKS from {:?}", jwks_source))?;
+        info!("Loaded {} JWKS keys from {:?}", key_count, jwks_source);
+    }
+
+    let outbox_dispatch = config_loader::get_outbox_dispatch_env()?;
+    outbox::dispatcher::spawn(outbox_dispatch, db_pool.clone());
+    let webhook_dispatch = config_loader::get_webhook_dispatch_env()?;
+    outbox::webhook_dispatcher::spawn(webhook_dispatch, db_pool.clone());
+    let appointment_reminders = config_loader::get_appointment_reminders_env()?;
+    reminder::scheduler::spawn(appointment_reminders, db_pool.clone());
+
     let routes = routers::slot_ops::routes_with_openapi(db_pool.clone())
         .merge(routers::appointment_ops::routes_with_openapi(
             db_pool.clone(),
+            config.booking_policy,
         ))
         .merge(routers::appointment_ledger::routes_with_openapi(
             db_pool.clone(),
+            config.booking_policy,
         ))
         .merge(routers::doctor_schedule_viewing::routes_with_openapi(
             db_pool.clone(),
@@ -40,10 +64,36 @@ pub async fn start(config: Arc<DotEnvyConfig>, db_pool: Arc<PgPoolSquad>) -> Res
         .merge(routers::patient_schedule_viewing::routes_with_openapi(
             db_pool.clone(),
         ))
+        .merge(routers::schedule_viewing::routes_with_openapi(
+            db_pool.clone(),
+        ))
         .merge(routers::doctor_slot_viewing::routes_with_openapi(
             db_pool.clone(),
         ))
-        .merge(routers::slot_viewing::routes_with_openapi(db_pool.clone()));
+        .merge(routers::slot_viewing::routes_with_openapi(db_pool.clone()))
+        .merge(routers::slot_template::routes_with_openapi(db_pool.clone()))
+        .merge(routers::waitlist::routes_with_openapi(
+            db_pool.clone(),
+            config.booking_policy,
+        ))
+        .merge(routers::booking_window::routes_with_openapi(
+            db_pool.clone(),
+            config.booking_policy.booking_window,
+        ))
+        .merge(routers::doctor_timezone::routes_with_openapi(
+            db_pool.clone(),
+        ))
+        .merge(routers::internal::routes_with_openapi(
+            db_pool.clone(),
+            config.booking_policy,
+        ))
+        .merge(routers::webhook_subscription::routes_with_openapi(
+            db_pool.clone(),
+        ))
+        .merge(routers::admin::routes_with_openapi(
+            db_pool.clone(),
+            config.booking_policy,
+        ));
 
     let mut openapi = routes.get_openapi().clone();
     openapi.info = InfoBuilder::new()
@@ -73,7 +123,12 @@ pub async fn start(config: Arc<DotEnvyConfig>, db_pool: Arc<PgPoolSquad>) -> Res
             Method::PATCH,
             Method::DELETE,
         ])
-        .allow_headers([header::CONTENT_TYPE, header::AUTHORIZATION])
+        .allow_headers([
+            header::CONTENT_TYPE,
+            header::AUTHORIZATION,
+            HeaderName::from_static("idempotency-key"),
+            HeaderName::from_static("x-api-key"),
+        ])
         .allow_credentials(true)
         .allow_origin(
             config
@@ -91,7 +146,12 @@ pub async fn start(config: Arc<DotEnvyConfig>, db_pool: Arc<PgPoolSquad>) -> Res
             Method::PATCH,
             Method::DELETE,
         ])
-        .allow_headers([header::CONTENT_TYPE, header::AUTHORIZATION])
+        .allow_headers([
+            header::CONTENT_TYPE,
+            header::AUTHORIZATION,
+            HeaderName::from_static("idempotency-key"),
+            HeaderName::from_static("x-api-key"),
+        ])
         .allow_credentials(true)
         .allow_origin(
             config
@@ -109,7 +169,12 @@ pub async fn start(config: Arc<DotEnvyConfig>, db_pool: Arc<PgPoolSquad>) -> Res
             Method::PATCH,
             Method::DELETE,
         ])
-        .allow_headers([header::CONTENT_TYPE, header::AUTHORIZATION])
+        .allow_headers([
+            header::CONTENT_TYPE,
+            header::AUTHORIZATION,
+            HeaderName::from_static("idempotency-key"),
+            HeaderName::from_static("x-api-key"),
+        ])
         .allow_origin(Any);
 
     match config_loader::get_stage() {
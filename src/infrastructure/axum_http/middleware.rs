@@ -1,58 +1,292 @@
-use axum::{extract::Request, http::{header, StatusCode}, middleware::Next, response::Response};
+use std::{sync::Arc, time::Duration};
 
-use crate::{config::config_loader::{get_patients_secret_env, get_doctors_secret_env}, infrastructure::jwt_authentication};
+use axum::{
+    extract::{Request, State},
+    http::{HeaderMap, header},
+    middleware::Next,
+    response::Response,
+};
+use jsonwebtoken::errors::ErrorKind;
 
-pub async fn patients_authorization(
-    mut req:Request,
+use crate::{
+    application::usecases::service_api_key::ServiceApiKeyUseCase,
+    config::{
+        config_loader::{
+            get_access_token_cookie_name, get_doctors_secret_env, get_jwt_verification_env,
+            get_patients_secret_env, get_staff_secret_env,
+        },
+        config_model::{JwksSource, JwtVerification},
+    },
+    domain::{
+        errors::{DomainError, DomainResult},
+        repositories::service_api_key::ServiceApiKeyRepository,
+    },
+    infrastructure::{
+        axum_http::{
+            authenticated_service::AuthenticatedService, authenticated_user::AuthenticatedUser,
+        },
+        jwt_authentication::{
+            self,
+            jwks::{self, JwksError, JwksKeyStore},
+            jwt_model::{Claims, Roles},
+        },
+    },
+};
+
+const ALL_ROLES: [Roles; 3] = [Roles::Patient, Roles::Doctor, Roles::Staff];
+
+const API_KEY_HEADER: &str = "x-api-key";
+
+pub async fn patients_authorization(mut req: Request, next: Next) -> Result<Response, DomainError> {
+    let authenticated_user = authorize(req.headers(), &[Roles::Patient]).await?;
+
+    req.extensions_mut().insert(authenticated_user);
+    Ok(next.run(req).await)
+}
+
+pub async fn doctors_authorization(mut req: Request, next: Next) -> Result<Response, DomainError> {
+    let authenticated_user = authorize(req.headers(), &[Roles::Doctor]).await?;
+
+    req.extensions_mut().insert(authenticated_user);
+    Ok(next.run(req).await)
+}
+
+pub async fn staff_authorization(mut req: Request, next: Next) -> Result<Response, DomainError> {
+    let authenticated_user = authorize(req.headers(), &[Roles::Staff]).await?;
+
+    req.extensions_mut().insert(authenticated_user);
+    Ok(next.run(req).await)
+}
+
+/// For endpoints open to both patients and doctors, handlers tell them apart
+/// by `AuthenticatedUser::role`.
+pub async fn patients_or_doctors_authorization(
+    mut req: Request,
     next: Next,
-) -> Result<Response, StatusCode> {
-    if let Some(cookie_header) = req.headers().get(header::COOKIE) {
-        if let Ok(cookie_str) = cookie_header.to_str() {
-            let access_token = get_cookie_value(cookie_str,"act");
-
-            if let Some(token) = access_token {
-                if let Ok(secret_env) = get_patients_secret_env() {
-                    if let Ok(claims) = jwt_authentication::verify_token(secret_env.secret,token) {
-                        if let Ok(patient_id) = claims.sub.parse::<i32>() {
-                            req.extensions_mut().insert(patient_id);
-                            return Ok(next.run(req).await);
-                        }
-                    }
-                }
-            }
+) -> Result<Response, DomainError> {
+    let authenticated_user = authorize(req.headers(), &[Roles::Patient, Roles::Doctor]).await?;
+
+    req.extensions_mut().insert(authenticated_user);
+    Ok(next.run(req).await)
+}
+
+/// For endpoints called by other MedBook services with an `X-Api-Key`.
+pub async fn services_authorization<T>(
+    State(service_api_key_use_case): State<Arc<ServiceApiKeyUseCase<T>>>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, DomainError>
+where
+    T: ServiceApiKeyRepository + Send + Sync,
+{
+    let api_key = req
+        .headers()
+        .get(API_KEY_HEADER)
+        .and_then(|api_key| api_key.to_str().ok())
+        .map(|api_key| api_key.trim().to_string())
+        .filter(|api_key| !api_key.is_empty())
+        .ok_or_else(|| DomainError::unauthorized("Missing API key, send `X-Api-Key: <key>`"))?;
+
+    let service_credential = service_api_key_use_case.authenticate(&api_key).await?;
+
+    req.extensions_mut()
+        .insert(AuthenticatedService::from_model(service_credential));
+    Ok(next.run(req).await)
+}
+
+/// Verifies the access token of a request and checks that it was issued to
+/// one of `allowed_roles`.
+async fn authorize(
+    headers: &HeaderMap,
+    allowed_roles: &[Roles],
+) -> DomainResult<AuthenticatedUser> {
+    let token = get_access_token(headers)?;
+    let jwt_verification = get_jwt_verification_env()?;
+
+    match &jwt_verification.jwks {
+        Some(jwks_source) => {
+            authorize_with_jwks(token, allowed_roles, jwks_source, &jwt_verification).await
+        }
+        None => authorize_with_secrets(token, allowed_roles, &jwt_verification),
+    }
+}
+
+/// Verifies an asymmetrically signed token, whose `role` claim alone tells who
+/// it was issued to.
+async fn authorize_with_jwks(
+    token: String,
+    allowed_roles: &[Roles],
+    jwks_source: &JwksSource,
+    jwt_verification: &JwtVerification,
+) -> DomainResult<AuthenticatedUser> {
+    let key_store = JwksKeyStore::shared(
+        jwks_source,
+        Duration::from_secs(jwt_verification.jwks_cache_seconds),
+    );
+    let claims = jwks::verify_token(key_store, token, jwt_verification)
+        .await
+        .map_err(token_error)?;
+
+    if !allowed_roles.contains(&claims.role) {
+        return Err(wrong_role(claims.role, allowed_roles));
+    }
+
+    authenticated_user(claims)
+}
+
+/// Verifies an HMAC signed token against the secret of each allowed role and
+/// checks that its `role` claim matches the secret that signed it.
+fn authorize_with_secrets(
+    token: String,
+    allowed_roles: &[Roles],
+    jwt_verification: &JwtVerification,
+) -> DomainResult<AuthenticatedUser> {
+    for role in allowed_roles {
+        let Some(secret) = secret_of(*role)? else {
+            continue;
+        };
+        let claims = match jwt_authentication::verify_token(secret, token.clone(), jwt_verification)
+        {
+            Ok(claims) => claims,
+            Err(e) if matches!(error_kind(&e), Some(ErrorKind::InvalidSignature)) => continue,
+            Err(e) => return Err(token_error(e)),
+        };
+
+        if claims.role != *role {
+            return Err(DomainError::unauthorized(format!(
+                "Access token role {:?} does not match the {:?} key that signed it",
+                claims.role, role
+            )));
+        }
+
+        return authenticated_user(claims);
+    }
+
+    for other_role in ALL_ROLES
+        .iter()
+        .filter(|role| !allowed_roles.contains(role))
+    {
+        if let Some(secret) = secret_of(*other_role)?
+            && is_signed_by(secret, token.clone(), jwt_verification)
+        {
+            return Err(wrong_role(*other_role, allowed_roles));
         }
     }
 
-    Err(StatusCode::UNAUTHORIZED)
-}
-
-pub async fn doctors_authorization(
-    mut req:Request,
-    next:Next,
-) -> Result<Response,StatusCode> {
-    if let Some(cookie_header) = req.headers().get(header::COOKIE) {
-        if let Ok(cookie_str) = cookie_header.to_str() {
-            let access_token = get_cookie_value(cookie_str,"act");
-
-            if let Some(token) = access_token {
-                if let Ok(secret_env) = get_doctors_secret_env() {
-                    if let Ok(claims) = jwt_authentication::verify_token(secret_env.secret,token) {
-                        if let Ok(doctor_id) = claims.sub.parse::<i32>() {
-                            req.extensions_mut().insert(doctor_id);
-                            return Ok(next.run(req).await);
-                        }
-                    }
-                }
+    Err(DomainError::unauthorized(
+        "Access token signature is invalid",
+    ))
+}
+
+fn authenticated_user(claims: Claims) -> DomainResult<AuthenticatedUser> {
+    let id = claims
+        .sub
+        .parse::<i32>()
+        .map_err(|_| DomainError::unauthorized("Access token subject is not a user id"))?;
+
+    Ok(AuthenticatedUser {
+        id,
+        role: claims.role,
+    })
+}
+
+fn wrong_role(role: Roles, allowed_roles: &[Roles]) -> DomainError {
+    DomainError::unauthorized(format!(
+        "Access token belongs to a {:?}, this endpoint is for a {}",
+        role,
+        allowed_roles
+            .iter()
+            .map(|role| format!("{:?}", role))
+            .collect::<Vec<_>>()
+            .join(" or ")
+    ))
+}
+
+/// Explains why a token was rejected. Failures to load the keys themselves
+/// are not the client's fault and stay internal errors.
+fn token_error(e: anyhow::Error) -> DomainError {
+    if let Some(jwks_error) = e.downcast_ref::<JwksError>() {
+        return DomainError::unauthorized(match jwks_error {
+            JwksError::MissingKeyId => "Access token has no `kid` header".to_string(),
+            JwksError::UnknownKeyId(_) => "Access token was signed with an unknown key".to_string(),
+            JwksError::UnsupportedAlgorithm(algorithm) => {
+                format!("Access token algorithm {:?} is not accepted", algorithm)
             }
+        });
+    }
+
+    let Some(kind) = error_kind(&e) else {
+        return DomainError::Internal(e);
+    };
+    DomainError::unauthorized(match kind {
+        ErrorKind::ExpiredSignature => "Access token has expired".to_string(),
+        ErrorKind::InvalidSignature => "Access token signature is invalid".to_string(),
+        ErrorKind::InvalidIssuer => "Access token issuer is not trusted".to_string(),
+        ErrorKind::InvalidAudience => "Access token is not meant for this service".to_string(),
+        ErrorKind::MissingRequiredClaim(claim) => {
+            format!("Access token is missing the `{}` claim", claim)
         }
+        _ => "Access token is malformed".to_string(),
+    })
+}
+
+/// `None` for staff when `JWT_STAFF_SECRET` is not set, no token is then
+/// accepted as a staff token.
+fn secret_of(role: Roles) -> DomainResult<Option<String>> {
+    let secret = match role {
+        Roles::Patient => Some(get_patients_secret_env()?.secret),
+        Roles::Doctor => Some(get_doctors_secret_env()?.secret),
+        Roles::Staff => get_staff_secret_env()?.map(|staff_secret| staff_secret.secret),
+    };
+
+    Ok(secret)
+}
+
+fn error_kind(e: &anyhow::Error) -> Option<&ErrorKind> {
+    e.downcast_ref::<jsonwebtoken::errors::Error>()
+        .map(|e| e.kind())
+}
+
+/// Whether `token` was signed with `secret`, even if it has already expired.
+fn is_signed_by(secret: String, token: String, jwt_verification: &JwtVerification) -> bool {
+    match jwt_authentication::verify_token(secret, token, jwt_verification) {
+        Ok(_) => true,
+        Err(e) => matches!(error_kind(&e), Some(ErrorKind::ExpiredSignature)),
     }
+}
 
-    Err(StatusCode::UNAUTHORIZED)
+/// Reads the access token from `Authorization: Bearer <token>`, falling back to
+/// the access token cookie.
+fn get_access_token(headers: &HeaderMap) -> DomainResult<String> {
+    if let Some(authorization_header) = headers.get(header::AUTHORIZATION) {
+        return authorization_header
+            .to_str()
+            .ok()
+            .and_then(|authorization| authorization.strip_prefix("Bearer "))
+            .map(|token| token.trim().to_string())
+            .filter(|token| !token.is_empty())
+            .ok_or_else(|| {
+                DomainError::unauthorized("Authorization header must be `Bearer <token>`")
+            });
+    }
+
+    let cookie_name = get_access_token_cookie_name();
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|cookie_header| cookie_header.to_str().ok())
+        .find_map(|cookie_str| get_cookie_value(cookie_str, &cookie_name))
+        .ok_or_else(|| {
+            DomainError::unauthorized(format!(
+                "Missing access token, send `Authorization: Bearer <token>` or the `{}` cookie",
+                cookie_name
+            ))
+        })
 }
 
 fn get_cookie_value(cookie_header: &str, key: &str) -> Option<String> {
     cookie_header.split("; ").find_map(|cookie| {
-        let mut parts = cookie.splitn(2,"=");
+        let mut parts = cookie.splitn(2, "=");
         let name = parts.next()?.trim();
         let value = parts.next()?.trim();
         if name == key {
@@ -61,4 +295,4 @@ fn get_cookie_value(cookie_header: &str, key: &str) -> Option<String> {
             None
         }
     })
-}
\ No newline at end of file
+}
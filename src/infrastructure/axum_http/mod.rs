@@ -1,4 +1,6 @@
 pub mod api_response;
+pub mod authenticated_service;
+pub mod authenticated_user;
 pub mod default_routers;
 pub mod http_serve;
 pub mod middleware;
@@ -0,0 +1,415 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    middleware,
+    response::IntoResponse,
+};
+use utoipa_axum::router::OpenApiRouter;
+use uuid::Uuid;
+
+use crate::{
+    application::usecases::{admin::AdminUseCase, schedule_viewing::ScheduleViewingUseCase},
+    domain::{
+        repositories::{admin::AdminRepository, schedule_viewing::ScheduleViewingRepository},
+        value_objects::{
+            admin_model::{
+                AdminAddAppointmentDto, GetAppointmentsQuery, GetAppointmentsResponseModel,
+            },
+            appointment_history_model::GetAppointmentHistoryResponseModel,
+            appointment_model::{CancelAppointmentDto, EditAppointmentDto},
+            booking_policy::BookingPolicy,
+            slot_model::EditSlotDto,
+        },
+    },
+    infrastructure::{
+        axum_http::{
+            api_response::{ApiResponse, EmptyResponseModel},
+            authenticated_user::AuthenticatedUser,
+            middleware::staff_authorization,
+        },
+        postgres::{
+            postgres_connection::PgPoolSquad,
+            repositories::{admin::AdminPostgres, schedule_viewing::ScheduleViewingPostgres},
+        },
+    },
+};
+
+/// Defines routes for clinic staff with OpenAPI specs. They act on
+/// appointments and slots of any patient or doctor, and every change is
+/// recorded under the staff user's id.
+pub fn routes_with_openapi(
+    db_pool: Arc<PgPoolSquad>,
+    booking_policy: BookingPolicy,
+) -> OpenApiRouter {
+    let admin_repository = AdminPostgres::new(db_pool.clone(), booking_policy);
+    let admin_use_case = AdminUseCase::new(Arc::new(admin_repository));
+
+    let schedule_viewing_repository = ScheduleViewingPostgres::new(db_pool);
+    let schedule_viewing_use_case =
+        ScheduleViewingUseCase::new(Arc::new(schedule_viewing_repository));
+
+    OpenApiRouter::new().nest(
+        "/admin",
+        OpenApiRouter::new()
+            .routes(utoipa_axum::routes!(add_appointment, get_appointments))
+            .routes(utoipa_axum::routes!(edit_appointment))
+            .routes(utoipa_axum::routes!(cancel_appointment))
+            .routes(utoipa_axum::routes!(restore_appointment))
+            .routes(utoipa_axum::routes!(edit_slot))
+            .routes(utoipa_axum::routes!(restore_slot))
+            .with_state(Arc::new(admin_use_case))
+            .merge(
+                OpenApiRouter::new()
+                    .routes(utoipa_axum::routes!(get_appointment_history))
+                    .with_state(Arc::new(schedule_viewing_use_case)),
+            )
+            .route_layer(middleware::from_fn(staff_authorization)),
+    )
+}
+
+/// Books an appointment on behalf of a patient, e.g. a walk-in patient at the
+/// front desk. The booking policy and booking window still apply.
+#[utoipa::path(
+    post,
+    path = "/appointments",
+    tags = ["Admin"],
+    request_body = AdminAddAppointmentDto,
+    responses(
+        (status = 200, description = "Appointment added successfully", body = ApiResponse<EmptyResponseModel>),
+        (status = 401, description = "Missing or invalid staff access token", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Slot not found", body = ApiResponse<EmptyResponseModel>),
+        (status = 409, description = "Slot is full or the booking policy was violated", body = ApiResponse<EmptyResponseModel>),
+        (status = 422, description = "Slot is already ended or outside the booking window", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+async fn add_appointment<T>(
+    State(admin_use_case): State<Arc<AdminUseCase<T>>>,
+    AuthenticatedUser { id: staff_id, .. }: AuthenticatedUser,
+    Json(admin_add_appointment_dto): Json<AdminAddAppointmentDto>,
+) -> impl IntoResponse
+where
+    T: AdminRepository + Send + Sync,
+{
+    match admin_use_case
+        .add_appointment(staff_id, admin_add_appointment_dto)
+        .await
+    {
+        Ok(appointment_id) => {
+            let response = format!("Add appointment success with id: {}", appointment_id);
+            (
+                StatusCode::OK,
+                Json(ApiResponse::<EmptyResponseModel> {
+                    data: None,
+                    message: Some(response),
+                    code: None,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Lists appointments across all doctors, e.g. the whole clinic's day.
+#[utoipa::path(
+    get,
+    path = "/appointments",
+    tags = ["Admin"],
+    params(GetAppointmentsQuery),
+    responses(
+        (status = 200, description = "Fetched appointments successfully", body = ApiResponse<GetAppointmentsResponseModel>),
+        (status = 401, description = "Missing or invalid staff access token", body = ApiResponse<EmptyResponseModel>),
+        (status = 422, description = "Invalid search parameters", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+async fn get_appointments<T>(
+    State(admin_use_case): State<Arc<AdminUseCase<T>>>,
+    Query(get_appointments_query): Query<GetAppointmentsQuery>,
+) -> impl IntoResponse
+where
+    T: AdminRepository + Send + Sync,
+{
+    match admin_use_case
+        .get_appointments(get_appointments_query)
+        .await
+    {
+        Ok(get_appointments_response_model) => (
+            StatusCode::OK,
+            Json(ApiResponse::<GetAppointmentsResponseModel> {
+                data: Some(get_appointments_response_model),
+                message: None,
+                code: None,
+            }),
+        )
+            .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Retrieves the status history of any appointment, including the changes
+/// made by staff.
+#[utoipa::path(
+    get,
+    path = "/appointments/{appointment_id}/history",
+    tags = ["Admin"],
+    params(
+        ("appointment_id" = Uuid, Path, description = "Appointment ID to get the history of")
+    ),
+    responses(
+        (status = 200, description = "Fetched appointment history successfully", body = ApiResponse<GetAppointmentHistoryResponseModel>),
+        (status = 401, description = "Missing or invalid staff access token", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Appointment not found", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+async fn get_appointment_history<T>(
+    State(schedule_viewing_use_case): State<Arc<ScheduleViewingUseCase<T>>>,
+    authenticated_user: AuthenticatedUser,
+    Path(appointment_id): Path<Uuid>,
+) -> impl IntoResponse
+where
+    T: ScheduleViewingRepository + Send + Sync,
+{
+    match schedule_viewing_use_case
+        .get_appointment_history(appointment_id, authenticated_user.to_actor())
+        .await
+    {
+        Ok(history) => (
+            StatusCode::OK,
+            Json(ApiResponse::<GetAppointmentHistoryResponseModel> {
+                data: Some(history),
+                message: None,
+                code: None,
+            }),
+        )
+            .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Edits or reschedules any patient's waiting appointment.
+#[utoipa::path(
+    patch,
+    path = "/appointments/{appointment_id}",
+    tags = ["Admin"],
+    params(
+        ("appointment_id" = Uuid, Path, description = "Appointment ID to edit")
+    ),
+    request_body = EditAppointmentDto,
+    responses(
+        (status = 200, description = "Appointment edited successfully", body = ApiResponse<EmptyResponseModel>),
+        (status = 401, description = "Missing or invalid staff access token", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Appointment or slot not found", body = ApiResponse<EmptyResponseModel>),
+        (status = 409, description = "Appointment is no longer waiting, new slot is full, or the booking policy was violated", body = ApiResponse<EmptyResponseModel>),
+        (status = 422, description = "New slot is already ended or outside the booking window, or the appointment is too close to change", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+async fn edit_appointment<T>(
+    State(admin_use_case): State<Arc<AdminUseCase<T>>>,
+    AuthenticatedUser { id: staff_id, .. }: AuthenticatedUser,
+    Path(appointment_id): Path<Uuid>,
+    Json(edit_appointment_dto): Json<EditAppointmentDto>,
+) -> impl IntoResponse
+where
+    T: AdminRepository + Send + Sync,
+{
+    match admin_use_case
+        .edit_appointment(appointment_id, staff_id, edit_appointment_dto)
+        .await
+    {
+        Ok(appointment_id) => {
+            let response = format!("Edit appointment success with id: {}", appointment_id);
+            (
+                StatusCode::OK,
+                Json(ApiResponse::<EmptyResponseModel> {
+                    data: None,
+                    message: Some(response),
+                    code: None,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Cancels any appointment with a reason shown to the patient.
+#[utoipa::path(
+    patch,
+    path = "/appointments/{appointment_id}/cancel",
+    tags = ["Admin"],
+    params(
+        ("appointment_id" = Uuid, Path, description = "Appointment ID to cancel")
+    ),
+    request_body = CancelAppointmentDto,
+    responses(
+        (status = 200, description = "Appointment cancelled successfully", body = ApiResponse<EmptyResponseModel>),
+        (status = 401, description = "Missing or invalid staff access token", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Appointment not found", body = ApiResponse<EmptyResponseModel>),
+        (status = 409, description = "Appointment can no longer be cancelled", body = ApiResponse<EmptyResponseModel>),
+        (status = 422, description = "Invalid reason", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+async fn cancel_appointment<T>(
+    State(admin_use_case): State<Arc<AdminUseCase<T>>>,
+    AuthenticatedUser { id: staff_id, .. }: AuthenticatedUser,
+    Path(appointment_id): Path<Uuid>,
+    Json(cancel_appointment_dto): Json<CancelAppointmentDto>,
+) -> impl IntoResponse
+where
+    T: AdminRepository + Send + Sync,
+{
+    match admin_use_case
+        .cancel_appointment(appointment_id, staff_id, cancel_appointment_dto)
+        .await
+    {
+        Ok(appointment_id) => {
+            let response = format!("Appointment id: {} is now Cancelled", appointment_id);
+            (
+                StatusCode::OK,
+                Json(ApiResponse::<EmptyResponseModel> {
+                    data: None,
+                    message: Some(response),
+                    code: None,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Puts back an appointment a patient removed. It takes its place in the slot
+/// again, so the slot must still have room.
+#[utoipa::path(
+    patch,
+    path = "/appointments/{appointment_id}/restore",
+    tags = ["Admin"],
+    params(
+        ("appointment_id" = Uuid, Path, description = "Removed appointment ID to restore")
+    ),
+    responses(
+        (status = 200, description = "Appointment restored successfully", body = ApiResponse<EmptyResponseModel>),
+        (status = 401, description = "Missing or invalid staff access token", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Removed appointment or its slot not found", body = ApiResponse<EmptyResponseModel>),
+        (status = 409, description = "Slot is full or the booking policy was violated", body = ApiResponse<EmptyResponseModel>),
+        (status = 422, description = "Slot is already ended or outside the booking window", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+async fn restore_appointment<T>(
+    State(admin_use_case): State<Arc<AdminUseCase<T>>>,
+    AuthenticatedUser { id: staff_id, .. }: AuthenticatedUser,
+    Path(appointment_id): Path<Uuid>,
+) -> impl IntoResponse
+where
+    T: AdminRepository + Send + Sync,
+{
+    match admin_use_case
+        .restore_appointment(appointment_id, staff_id)
+        .await
+    {
+        Ok(appointment_id) => {
+            let response = format!("Restore appointment success with id: {}", appointment_id);
+            (
+                StatusCode::OK,
+                Json(ApiResponse::<EmptyResponseModel> {
+                    data: None,
+                    message: Some(response),
+                    code: None,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Edits a slot of any doctor. Times without a UTC offset are read in that
+/// doctor's time zone.
+#[utoipa::path(
+    patch,
+    path = "/slots/{slot_id}",
+    tags = ["Admin"],
+    params(
+        ("slot_id" = Uuid, Path, description = "Slot ID to edit")
+    ),
+    request_body = EditSlotDto,
+    responses(
+        (status = 200, description = "Slot edited successfully", body = ApiResponse<EmptyResponseModel>),
+        (status = 401, description = "Missing or invalid staff access token", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Slot not found", body = ApiResponse<EmptyResponseModel>),
+        (status = 409, description = "Slot already has more appointments than the new capacity", body = ApiResponse<EmptyResponseModel>),
+        (status = 422, description = "Slot is already ended or the edit is invalid", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+async fn edit_slot<T>(
+    State(admin_use_case): State<Arc<AdminUseCase<T>>>,
+    AuthenticatedUser { id: staff_id, .. }: AuthenticatedUser,
+    Path(slot_id): Path<Uuid>,
+    Json(edit_slot_dto): Json<EditSlotDto>,
+) -> impl IntoResponse
+where
+    T: AdminRepository + Send + Sync,
+{
+    match admin_use_case
+        .edit_slot(slot_id, staff_id, edit_slot_dto)
+        .await
+    {
+        Ok(slot_id) => {
+            let response = format!("Edit slot success with id: {}", slot_id);
+            (
+                StatusCode::OK,
+                Json(ApiResponse::<EmptyResponseModel> {
+                    data: None,
+                    message: Some(response),
+                    code: None,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Puts back a slot a doctor removed.
+#[utoipa::path(
+    patch,
+    path = "/slots/{slot_id}/restore",
+    tags = ["Admin"],
+    params(
+        ("slot_id" = Uuid, Path, description = "Removed slot ID to restore")
+    ),
+    responses(
+        (status = 200, description = "Slot restored successfully", body = ApiResponse<EmptyResponseModel>),
+        (status = 401, description = "Missing or invalid staff access token", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Removed slot not found", body = ApiResponse<EmptyResponseModel>),
+        (status = 409, description = "Another slot of the doctor now overlaps it", body = ApiResponse<EmptyResponseModel>),
+        (status = 422, description = "Slot is already ended", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+async fn restore_slot<T>(
+    State(admin_use_case): State<Arc<AdminUseCase<T>>>,
+    AuthenticatedUser { id: staff_id, .. }: AuthenticatedUser,
+    Path(slot_id): Path<Uuid>,
+) -> impl IntoResponse
+where
+    T: AdminRepository + Send + Sync,
+{
+    match admin_use_case.restore_slot(slot_id, staff_id).await {
+        Ok(slot_id) => {
+            let response = format!("Restore slot success with id: {}", slot_id);
+            (
+                StatusCode::OK,
+                Json(ApiResponse::<EmptyResponseModel> {
+                    data: None,
+                    message: Some(response),
+                    code: None,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
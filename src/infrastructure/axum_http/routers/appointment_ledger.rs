@@ -15,11 +15,18 @@ use crate::{
     application::usecases::appointment_ledger::AppointmentLedgerUseCase,
     domain::{
         repositories::appointment_ledger::AppointmentLedgerRepository,
-        value_objects::appointment_status::AppointmentStatus,
+        value_objects::{
+            appointment_model::{
+                CancelAppointmentDto, TransitionAppointmentDto, TransitionAppointmentResponseModel,
+            },
+            appointment_status::AppointmentStatus,
+            booking_policy::BookingPolicy,
+        },
     },
     infrastructure::{
         axum_http::{
             api_response::{ApiResponse, EmptyResponseModel},
+            authenticated_user::AuthenticatedUser,
             middleware::doctors_authorization,
         },
         postgres::{
@@ -30,8 +37,8 @@ use crate::{
 };
 
 #[deprecated]
-pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
-    let appointment_ledger_repository = AppointmentLedgerPostgres::new(db_pool);
+pub fn routes(db_pool: Arc<PgPoolSquad>, booking_policy: BookingPolicy) -> Router {
+    let appointment_ledger_repository = AppointmentLedgerPostgres::new(db_pool, booking_policy);
     let appointment_ledger_use_case =
         AppointmentLedgerUseCase::new(Arc::new(appointment_ledger_repository));
 
@@ -42,13 +49,19 @@ pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
             patch(to_waiting_for_prescription),
         )
         .route("/to-completed/:appointment_id", patch(to_completed))
+        .route("/cancel/:appointment_id", patch(cancel))
+        .route("/transition/:appointment_id", patch(transition))
+        .route("/revert/:appointment_id", patch(revert))
         .route_layer(middleware::from_fn(doctors_authorization))
         .with_state(Arc::new(appointment_ledger_use_case))
 }
 
 /// Defines routes with OpenAPI specs. Should be used over `routes()` where possible.
-pub fn routes_with_openapi(db_pool: Arc<PgPoolSquad>) -> OpenApiRouter {
-    let appointment_ledger_repository = AppointmentLedgerPostgres::new(db_pool);
+pub fn routes_with_openapi(
+    db_pool: Arc<PgPoolSquad>,
+    booking_policy: BookingPolicy,
+) -> OpenApiRouter {
+    let appointment_ledger_repository = AppointmentLedgerPostgres::new(db_pool, booking_policy);
     let appointment_ledger_use_case =
         AppointmentLedgerUseCase::new(Arc::new(appointment_ledger_repository));
 
@@ -58,6 +71,9 @@ pub fn routes_with_openapi(db_pool: Arc<PgPoolSquad>) -> OpenApiRouter {
             .routes(utoipa_axum::routes!(to_ready))
             .routes(utoipa_axum::routes!(to_waiting_for_prescription))
             .routes(utoipa_axum::routes!(to_completed))
+            .routes(utoipa_axum::routes!(cancel))
+            .routes(utoipa_axum::routes!(transition))
+            .routes(utoipa_axum::routes!(revert))
             .route_layer(middleware::from_fn(doctors_authorization))
             .with_state(Arc::new(appointment_ledger_use_case)),
     )
@@ -72,17 +88,24 @@ pub fn routes_with_openapi(db_pool: Arc<PgPoolSquad>) -> OpenApiRouter {
         ("appointment_id" = Uuid, Path, description = "Appointment ID to update to Ready")
     ),
     responses(
-        (status = 200, description = "Appointment status updated to Ready successfully", body = ApiResponse<EmptyResponseModel>)
+        (status = 200, description = "Appointment status updated to Ready successfully", body = ApiResponse<EmptyResponseModel>),
+        (status = 403, description = "Appointment is not in one of the doctor's slots", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Appointment not found", body = ApiResponse<EmptyResponseModel>),
+        (status = 409, description = "Appointment is not in a status that can move to Ready", body = ApiResponse<EmptyResponseModel>)
     )
 )]
 pub async fn to_ready<T>(
     State(appointment_ledger_use_case): State<Arc<AppointmentLedgerUseCase<T>>>,
+    AuthenticatedUser { id: doctor_id, .. }: AuthenticatedUser,
     Path(appointment_id): Path<Uuid>,
 ) -> impl IntoResponse
 where
     T: AppointmentLedgerRepository + Send + Sync,
 {
-    match appointment_ledger_use_case.to_ready(appointment_id).await {
+    match appointment_ledger_use_case
+        .to_ready(appointment_id, doctor_id)
+        .await
+    {
         Ok(appointment_id) => {
             let response = format!(
                 "Appointment id: {} is now {:?}",
@@ -94,16 +117,12 @@ where
                 Json(ApiResponse::<EmptyResponseModel> {
                     data: None,
                     message: Some(response),
+                    code: None,
                 }),
             )
+                .into_response()
         }
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::<EmptyResponseModel> {
-                data: None,
-                message: Some(e.to_string()),
-            }),
-        ),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -116,19 +135,23 @@ where
         ("appointment_id" = Uuid, Path, description = "Appointment ID to update to WaitingForPrescription")
     ),
     responses(
-        (status = 200, description = "Appointment status updated to WaitingForPrescription successfully", body = ApiResponse<EmptyResponseModel>)
+        (status = 200, description = "Appointment status updated to WaitingForPrescription successfully", body = ApiResponse<EmptyResponseModel>),
+        (status = 403, description = "Appointment is not in one of the doctor's slots", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Appointment not found", body = ApiResponse<EmptyResponseModel>),
+        (status = 409, description = "Appointment is not in a status that can move to WaitingForPrescription", body = ApiResponse<EmptyResponseModel>)
     )
 )]
 
 pub async fn to_waiting_for_prescription<T>(
     State(appointment_ledger_use_case): State<Arc<AppointmentLedgerUseCase<T>>>,
+    AuthenticatedUser { id: doctor_id, .. }: AuthenticatedUser,
     Path(appointment_id): Path<Uuid>,
 ) -> impl IntoResponse
 where
     T: AppointmentLedgerRepository + Send + Sync,
 {
     match appointment_ledger_use_case
-        .to_waiting_for_prescription(appointment_id)
+        .to_waiting_for_prescription(appointment_id, doctor_id)
         .await
     {
         Ok(appointment_id) => {
@@ -142,16 +165,12 @@ where
                 Json(ApiResponse::<EmptyResponseModel> {
                     data: None,
                     message: Some(response),
+                    code: None,
                 }),
             )
+                .into_response()
         }
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::<EmptyResponseModel> {
-                data: None,
-                message: Some(e.to_string()),
-            }),
-        ),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -164,18 +183,22 @@ where
         ("appointment_id" = Uuid, Path, description = "Appointment ID to update to Completed")
     ),
     responses(
-        (status = 200, description = "Appointment status updated to Completed successfully", body = ApiResponse<EmptyResponseModel>)
+        (status = 200, description = "Appointment status updated to Completed successfully", body = ApiResponse<EmptyResponseModel>),
+        (status = 403, description = "Appointment is not in one of the doctor's slots", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Appointment not found", body = ApiResponse<EmptyResponseModel>),
+        (status = 409, description = "Appointment is not in a status that can move to Completed", body = ApiResponse<EmptyResponseModel>)
     )
 )]
 pub async fn to_completed<T>(
     State(appointment_ledger_use_case): State<Arc<AppointmentLedgerUseCase<T>>>,
+    AuthenticatedUser { id: doctor_id, .. }: AuthenticatedUser,
     Path(appointment_id): Path<Uuid>,
 ) -> impl IntoResponse
 where
     T: AppointmentLedgerRepository + Send + Sync,
 {
     match appointment_ledger_use_case
-        .to_completed(appointment_id)
+        .to_completed(appointment_id, doctor_id)
         .await
     {
         Ok(appointment_id) => {
@@ -189,15 +212,159 @@ where
                 Json(ApiResponse::<EmptyResponseModel> {
                     data: None,
                     message: Some(response),
+                    code: None,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Cancels an appointment on behalf of the doctor who owns its slot.
+///
+/// Only **Waiting** or **Ready** appointments can be cancelled. The slot
+/// capacity is freed and the patient sees the reason in their schedule.
+#[utoipa::path(
+    patch,
+    path = "/cancel/{appointment_id}",
+    tags = ["Appointment Ledger"],
+    params(
+        ("appointment_id" = Uuid, Path, description = "Appointment ID to cancel")
+    ),
+    request_body = CancelAppointmentDto,
+    responses(
+        (status = 200, description = "Appointment cancelled successfully", body = ApiResponse<EmptyResponseModel>),
+        (status = 403, description = "Appointment is not in one of the doctor's slots", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Appointment not found", body = ApiResponse<EmptyResponseModel>),
+        (status = 409, description = "Appointment is not in a status that can be cancelled", body = ApiResponse<EmptyResponseModel>),
+        (status = 422, description = "Invalid cancellation reason", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+pub async fn cancel<T>(
+    State(appointment_ledger_use_case): State<Arc<AppointmentLedgerUseCase<T>>>,
+    AuthenticatedUser { id: doctor_id, .. }: AuthenticatedUser,
+    Path(appointment_id): Path<Uuid>,
+    Json(cancel_appointment_dto): Json<CancelAppointmentDto>,
+) -> impl IntoResponse
+where
+    T: AppointmentLedgerRepository + Send + Sync,
+{
+    match appointment_ledger_use_case
+        .cancel(appointment_id, doctor_id, cancel_appointment_dto)
+        .await
+    {
+        Ok(appointment_id) => {
+            let response = format!(
+                "Appointment id: {} is now {:?}",
+                appointment_id,
+                AppointmentStatus::Cancelled
+            );
+            (
+                StatusCode::OK,
+                Json(ApiResponse::<EmptyResponseModel> {
+                    data: None,
+                    message: Some(response),
+                    code: None,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Moves an appointment to any status allowed by the status transition table.
+#[utoipa::path(
+    patch,
+    path = "/transition/{appointment_id}",
+    tags = ["Appointment Ledger"],
+    params(
+        ("appointment_id" = Uuid, Path, description = "Appointment ID to update")
+    ),
+    request_body = TransitionAppointmentDto,
+    responses(
+        (status = 200, description = "Appointment status updated successfully", body = ApiResponse<TransitionAppointmentResponseModel>),
+        (status = 403, description = "Appointment is not in one of the doctor's slots", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Appointment not found", body = ApiResponse<EmptyResponseModel>),
+        (status = 409, description = "Transition is not allowed or the slot is full", body = ApiResponse<EmptyResponseModel>),
+        (status = 422, description = "Invalid reason", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+pub async fn transition<T>(
+    State(appointment_ledger_use_case): State<Arc<AppointmentLedgerUseCase<T>>>,
+    AuthenticatedUser { id: doctor_id, .. }: AuthenticatedUser,
+    Path(appointment_id): Path<Uuid>,
+    Json(transition_appointment_dto): Json<TransitionAppointmentDto>,
+) -> impl IntoResponse
+where
+    T: AppointmentLedgerRepository + Send + Sync,
+{
+    match appointment_ledger_use_case
+        .transition(appointment_id, doctor_id, transition_appointment_dto)
+        .await
+    {
+        Ok(result) => {
+            let response = format!(
+                "Appointment id: {} is now {:?}",
+                result.appointment_id, result.status
+            );
+            (
+                StatusCode::OK,
+                Json(ApiResponse::<TransitionAppointmentResponseModel> {
+                    data: Some(result),
+                    message: Some(response),
+                    code: None,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Reverts the last status change of an appointment, e.g. **Ready** back to **Waiting**.
+#[utoipa::path(
+    patch,
+    path = "/revert/{appointment_id}",
+    tags = ["Appointment Ledger"],
+    params(
+        ("appointment_id" = Uuid, Path, description = "Appointment ID to revert")
+    ),
+    responses(
+        (status = 200, description = "Appointment status reverted successfully", body = ApiResponse<TransitionAppointmentResponseModel>),
+        (status = 403, description = "Appointment is not in one of the doctor's slots", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Appointment not found", body = ApiResponse<EmptyResponseModel>),
+        (status = 409, description = "Status cannot be reverted or the slot is full", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+pub async fn revert<T>(
+    State(appointment_ledger_use_case): State<Arc<AppointmentLedgerUseCase<T>>>,
+    AuthenticatedUser { id: doctor_id, .. }: AuthenticatedUser,
+    Path(appointment_id): Path<Uuid>,
+) -> impl IntoResponse
+where
+    T: AppointmentLedgerRepository + Send + Sync,
+{
+    match appointment_ledger_use_case
+        .revert(appointment_id, doctor_id)
+        .await
+    {
+        Ok(result) => {
+            let response = format!(
+                "Appointment id: {} is now {:?}",
+                result.appointment_id, result.status
+            );
+            (
+                StatusCode::OK,
+                Json(ApiResponse::<TransitionAppointmentResponseModel> {
+                    data: Some(result),
+                    message: Some(response),
+                    code: None,
                 }),
             )
+                .into_response()
         }
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::<EmptyResponseModel> {
-                data: None,
-                message: Some(e.to_string()),
-            }),
-        ),
+        Err(e) => e.into_response(),
     }
 }
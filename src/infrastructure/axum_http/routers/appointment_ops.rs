@@ -1,9 +1,9 @@
 use std::sync::Arc;
 
 use axum::{
-    Extension, Json, Router,
+    Json, Router,
     extract::{Path, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     middleware,
     response::IntoResponse,
     routing::{delete, patch, post},
@@ -14,12 +14,17 @@ use uuid::Uuid;
 use crate::{
     application::usecases::appointment_ops::AppointmentOpsUseCase,
     domain::{
+        errors::{DomainError, DomainResult},
         repositories::appointment_ops::AppointmentOpsRepository,
-        value_objects::appointment_model::{AddAppointmentDto, EditAppointmentDto},
+        value_objects::{
+            appointment_model::{AddAppointmentDto, EditAppointmentDto},
+            booking_policy::BookingPolicy,
+        },
     },
     infrastructure::{
         axum_http::{
             api_response::{ApiResponse, EmptyResponseModel},
+            authenticated_user::AuthenticatedUser,
             middleware::patients_authorization,
         },
         postgres::{
@@ -29,8 +34,8 @@ use crate::{
 };
 
 #[deprecated]
-pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
-    let appointment_ops_repository = AppointmentOpsPostgres::new(db_pool);
+pub fn routes(db_pool: Arc<PgPoolSquad>, booking_policy: BookingPolicy) -> Router {
+    let appointment_ops_repository = AppointmentOpsPostgres::new(db_pool, booking_policy);
     let appointment_ops_use_case = AppointmentOpsUseCase::new(Arc::new(appointment_ops_repository));
 
     Router::new()
@@ -41,9 +46,26 @@ pub fn routes(db_pool: Arc<PgPoolSquad>) -> Router {
         .with_state(Arc::new(appointment_ops_use_case))
 }
 
+const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+fn idempotency_key(headers: &HeaderMap) -> DomainResult<Option<String>> {
+    headers
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .map(|value| {
+            value
+                .to_str()
+                .map(|key| key.to_string())
+                .map_err(|_| DomainError::validation("Idempotency-Key must be visible ASCII"))
+        })
+        .transpose()
+}
+
 /// Defines routes with OpenAPI specs. Should be used over `routes()` where possible.
-pub fn routes_with_openapi(db_pool: Arc<PgPoolSquad>) -> OpenApiRouter {
-    let appointment_ops_repository = AppointmentOpsPostgres::new(db_pool);
+pub fn routes_with_openapi(
+    db_pool: Arc<PgPoolSquad>,
+    booking_policy: BookingPolicy,
+) -> OpenApiRouter {
+    let appointment_ops_repository = AppointmentOpsPostgres::new(db_pool, booking_policy);
     let appointment_ops_use_case = AppointmentOpsUseCase::new(Arc::new(appointment_ops_repository));
 
     OpenApiRouter::new().nest(
@@ -62,21 +84,33 @@ pub fn routes_with_openapi(db_pool: Arc<PgPoolSquad>) -> OpenApiRouter {
     post,
     path = "/",
     tags = ["Appointment Operations"],
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Retrying with the same key and body returns the original appointment instead of booking again")
+    ),
     request_body = AddAppointmentDto,
     responses(
-        (status = 200, description = "Appointment added successfully", body = ApiResponse<EmptyResponseModel>)
+        (status = 200, description = "Appointment added successfully", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Slot not found", body = ApiResponse<EmptyResponseModel>),
+        (status = 409, description = "Slot is full, the booking policy was violated, or the Idempotency-Key was used for a different request", body = ApiResponse<EmptyResponseModel>),
+        (status = 422, description = "Slot is already ended, outside the booking window, or the Idempotency-Key is invalid", body = ApiResponse<EmptyResponseModel>)
     )
 )]
 async fn add<T>(
     State(appointment_ops_use_case): State<Arc<AppointmentOpsUseCase<T>>>,
-    Extension(patient_id): Extension<i32>,
+    AuthenticatedUser { id: patient_id, .. }: AuthenticatedUser,
+    headers: HeaderMap,
     Json(add_appointment_dto): Json<AddAppointmentDto>,
 ) -> impl IntoResponse
 where
     T: AppointmentOpsRepository + Send + Sync,
 {
+    let idempotency_key = match idempotency_key(&headers) {
+        Ok(idempotency_key) => idempotency_key,
+        Err(e) => return e.into_response(),
+    };
+
     match appointment_ops_use_case
-        .add(add_appointment_dto, patient_id)
+        .add(add_appointment_dto, patient_id, idempotency_key)
         .await
     {
         Ok(appointment_id) => {
@@ -86,16 +120,12 @@ where
                 Json(ApiResponse::<EmptyResponseModel> {
                     data: None,
                     message: Some(response),
+                    code: None,
                 }),
             )
+                .into_response()
         }
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::<EmptyResponseModel> {
-                data: None,
-                message: Some(e.to_string()),
-            }),
-        ),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -105,24 +135,40 @@ where
     path = "/{appointment_id}",
     tags = ["Appointment Operations"],
     params(
-        ("appointment_id" = Uuid, Path, description = "Appointment ID to edit")
+        ("appointment_id" = Uuid, Path, description = "Appointment ID to edit"),
+        ("Idempotency-Key" = Option<String>, Header, description = "Retrying a reschedule with the same key and body does not move the appointment again")
     ),
     request_body = EditAppointmentDto,
     responses(
-        (status = 200, description = "Appointment edited successfully", body = ApiResponse<EmptyResponseModel>)
+        (status = 200, description = "Appointment edited successfully", body = ApiResponse<EmptyResponseModel>),
+        (status = 403, description = "Appointment does not belong to the patient", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Appointment or slot not found", body = ApiResponse<EmptyResponseModel>),
+        (status = 409, description = "Appointment is no longer waiting, new slot is full, the booking policy was violated, or the Idempotency-Key was used for a different request", body = ApiResponse<EmptyResponseModel>),
+        (status = 422, description = "New slot is already ended or outside the booking window, the appointment is too close to change, or the Idempotency-Key is invalid", body = ApiResponse<EmptyResponseModel>)
     )
 )]
 async fn edit<T>(
     State(appointment_ops_use_case): State<Arc<AppointmentOpsUseCase<T>>>,
-    Extension(patient_id): Extension<i32>,
+    AuthenticatedUser { id: patient_id, .. }: AuthenticatedUser,
     Path(appointment_id): Path<Uuid>,
+    headers: HeaderMap,
     Json(edit_appointment_dto): Json<EditAppointmentDto>,
 ) -> impl IntoResponse
 where
     T: AppointmentOpsRepository + Send + Sync,
 {
+    let idempotency_key = match idempotency_key(&headers) {
+        Ok(idempotency_key) => idempotency_key,
+        Err(e) => return e.into_response(),
+    };
+
     match appointment_ops_use_case
-        .edit(appointment_id, patient_id, edit_appointment_dto)
+        .edit(
+            appointment_id,
+            patient_id,
+            edit_appointment_dto,
+            idempotency_key,
+        )
         .await
     {
         Ok(_) => {
@@ -132,16 +178,12 @@ where
                 Json(ApiResponse::<EmptyResponseModel> {
                     data: None,
                     message: Some(response),
+                    code: None,
                 }),
             )
+                .into_response()
         }
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::<EmptyResponseModel> {
-                data: None,
-                message: Some(e.to_string()),
-            }),
-        ),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -154,12 +196,16 @@ where
         ("appointment_id" = Uuid, Path, description = "Appointment ID to remove")
     ),
     responses(
-        (status = 200, description = "Appointment removed successfully", body = ApiResponse<EmptyResponseModel>)
+        (status = 200, description = "Appointment removed successfully", body = ApiResponse<EmptyResponseModel>),
+        (status = 403, description = "Appointment does not belong to the patient", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Appointment not found", body = ApiResponse<EmptyResponseModel>),
+        (status = 409, description = "Appointment is no longer waiting", body = ApiResponse<EmptyResponseModel>),
+        (status = 422, description = "Appointment is too close to be cancelled", body = ApiResponse<EmptyResponseModel>)
     )
 )]
 async fn remove<T>(
     State(appointment_ops_use_case): State<Arc<AppointmentOpsUseCase<T>>>,
-    Extension(patient_id): Extension<i32>,
+    AuthenticatedUser { id: patient_id, .. }: AuthenticatedUser,
     Path(appointment_id): Path<Uuid>,
 ) -> impl IntoResponse
 where
@@ -176,15 +222,11 @@ where
                 Json(ApiResponse::<EmptyResponseModel> {
                     data: None,
                     message: Some(response),
+                    code: None,
                 }),
             )
+                .into_response()
         }
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::<EmptyResponseModel> {
-                data: None,
-                message: Some(e.to_string()),
-            }),
-        ),
+        Err(e) => e.into_response(),
     }
 }
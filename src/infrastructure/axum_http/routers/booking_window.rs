@@ -0,0 +1,113 @@
+use std::sync::Arc;
+
+use axum::{Json, extract::State, http::StatusCode, middleware, response::IntoResponse};
+use utoipa_axum::router::OpenApiRouter;
+
+use crate::{
+    application::usecases::booking_window::BookingWindowUseCase,
+    domain::{
+        repositories::booking_window::BookingWindowRepository,
+        value_objects::booking_window::{
+            BookingWindow, GetDoctorBookingWindowResponseModel, SetDoctorBookingWindowDto,
+        },
+    },
+    infrastructure::{
+        axum_http::{
+            api_response::{ApiResponse, EmptyResponseModel},
+            authenticated_user::AuthenticatedUser,
+            middleware::doctors_authorization,
+        },
+        postgres::{
+            postgres_connection::PgPoolSquad, repositories::booking_window::BookingWindowPostgres,
+        },
+    },
+};
+
+/// Defines routes with OpenAPI specs.
+pub fn routes_with_openapi(
+    db_pool: Arc<PgPoolSquad>,
+    booking_window: BookingWindow,
+) -> OpenApiRouter {
+    let booking_window_repository = BookingWindowPostgres::new(db_pool, booking_window);
+    let booking_window_use_case = BookingWindowUseCase::new(Arc::new(booking_window_repository));
+
+    OpenApiRouter::new().nest(
+        "/booking-window",
+        OpenApiRouter::new()
+            .routes(utoipa_axum::routes!(get_doctor_booking_window))
+            .routes(utoipa_axum::routes!(set_doctor_booking_window))
+            .route_layer(middleware::from_fn(doctors_authorization))
+            .with_state(Arc::new(booking_window_use_case)),
+    )
+}
+
+/// Retrieves the authenticated doctor's booking window and the one actually enforced.
+#[utoipa::path(
+    get,
+    path = "/",
+    tags = ["Booking Window"],
+    responses(
+        (status = 200, description = "Fetched booking window successfully", body = ApiResponse<GetDoctorBookingWindowResponseModel>)
+    )
+)]
+pub async fn get_doctor_booking_window<T>(
+    State(booking_window_use_case): State<Arc<BookingWindowUseCase<T>>>,
+    AuthenticatedUser { id: doctor_id, .. }: AuthenticatedUser,
+) -> impl IntoResponse
+where
+    T: BookingWindowRepository + Send + Sync,
+{
+    match booking_window_use_case
+        .get_doctor_booking_window(doctor_id)
+        .await
+    {
+        Ok(result) => (
+            StatusCode::OK,
+            Json(ApiResponse::<GetDoctorBookingWindowResponseModel> {
+                data: Some(result),
+                message: None,
+                code: None,
+            }),
+        )
+            .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Sets the authenticated doctor's booking window.
+///
+/// Fields left out fall back to the global settings.
+#[utoipa::path(
+    put,
+    path = "/",
+    tags = ["Booking Window"],
+    request_body = SetDoctorBookingWindowDto,
+    responses(
+        (status = 200, description = "Booking window set successfully", body = ApiResponse<GetDoctorBookingWindowResponseModel>),
+        (status = 422, description = "Invalid booking window", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+pub async fn set_doctor_booking_window<T>(
+    State(booking_window_use_case): State<Arc<BookingWindowUseCase<T>>>,
+    AuthenticatedUser { id: doctor_id, .. }: AuthenticatedUser,
+    Json(set_doctor_booking_window_dto): Json<SetDoctorBookingWindowDto>,
+) -> impl IntoResponse
+where
+    T: BookingWindowRepository + Send + Sync,
+{
+    match booking_window_use_case
+        .set_doctor_booking_window(doctor_id, set_doctor_booking_window_dto)
+        .await
+    {
+        Ok(result) => (
+            StatusCode::OK,
+            Json(ApiResponse::<GetDoctorBookingWindowResponseModel> {
+                data: Some(result),
+                message: Some("Set booking window success".to_string()),
+                code: None,
+            }),
+        )
+            .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
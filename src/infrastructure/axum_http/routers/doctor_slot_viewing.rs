@@ -1,7 +1,11 @@
 use std::sync::Arc;
 
 use axum::{
-    Extension, Json, Router, extract::State, http::StatusCode, middleware, response::IntoResponse,
+    Json, Router,
+    extract::{Query, State},
+    http::StatusCode,
+    middleware,
+    response::IntoResponse,
     routing::get,
 };
 use utoipa_axum::router::OpenApiRouter;
@@ -10,10 +14,14 @@ use crate::{
     application::usecases::slot_viewing::SlotViewingUseCase,
     domain::{
         repositories::slot_viewing::SlotViewingRepository,
-        value_objects::slot_model::GetSlotsResponseModel,
+        value_objects::{slot_model::GetDoctorSlotsResponseModel, timezone_model::TimezoneQuery},
     },
     infrastructure::{
-        axum_http::{api_response::ApiResponse, middleware::doctors_authorization},
+        axum_http::{
+            api_response::{ApiResponse, EmptyResponseModel},
+            authenticated_user::AuthenticatedUser,
+            middleware::doctors_authorization,
+        },
         postgres::{
             postgres_connection::PgPoolSquad, repositories::slot_viewing::SlotViewingPostgres,
         },
@@ -50,33 +58,33 @@ pub fn routes_with_openapi(db_pool: Arc<PgPoolSquad>) -> OpenApiRouter {
     get,
     path = "/view-my-slots",
     tags = ["Slot Viewing"],
+    params(TimezoneQuery),
     responses(
-        (status = 200, description = "Fetched doctor slots successfully", body = ApiResponse<GetSlotsResponseModel>)
+        (status = 200, description = "Fetched doctor slots successfully", body = ApiResponse<GetDoctorSlotsResponseModel>),
+        (status = 422, description = "Unknown time zone", body = ApiResponse<EmptyResponseModel>)
     )
 )]
 async fn get_doctor_slots<T>(
     State(slot_viewing_use_case): State<Arc<SlotViewingUseCase<T>>>,
-    Extension(doctor_id): Extension<i32>,
+    AuthenticatedUser { id: doctor_id, .. }: AuthenticatedUser,
+    Query(timezone_query): Query<TimezoneQuery>,
 ) -> impl IntoResponse
 where
     T: SlotViewingRepository + Send + Sync,
 {
-    match slot_viewing_use_case.get_doctor_slots(doctor_id).await {
+    match slot_viewing_use_case
+        .get_doctor_slots(doctor_id, timezone_query)
+        .await
+    {
         Ok(slots) => (
             StatusCode::OK,
-            Json(ApiResponse::<GetSlotsResponseModel> {
-                data: Some(GetSlotsResponseModel { slots }),
+            Json(ApiResponse::<GetDoctorSlotsResponseModel> {
+                data: Some(GetDoctorSlotsResponseModel { slots }),
                 message: None,
+                code: None,
             }),
         )
             .into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::<GetSlotsResponseModel> {
-                data: None,
-                message: Some(e.to_string()),
-            }),
-        )
-            .into_response(),
+        Err(e) => e.into_response(),
     }
 }
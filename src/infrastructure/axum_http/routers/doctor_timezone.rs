@@ -0,0 +1,108 @@
+use std::sync::Arc;
+
+use axum::{Json, extract::State, http::StatusCode, middleware, response::IntoResponse};
+use utoipa_axum::router::OpenApiRouter;
+
+use crate::{
+    application::usecases::doctor_timezone::DoctorTimezoneUseCase,
+    domain::{
+        repositories::doctor_timezone::DoctorTimezoneRepository,
+        value_objects::timezone_model::{GetDoctorTimezoneResponseModel, SetDoctorTimezoneDto},
+    },
+    infrastructure::{
+        axum_http::{
+            api_response::{ApiResponse, EmptyResponseModel},
+            authenticated_user::AuthenticatedUser,
+            middleware::doctors_authorization,
+        },
+        postgres::{
+            postgres_connection::PgPoolSquad, repositories::doctor_timezone::DoctorTimezonePostgres,
+        },
+    },
+};
+
+/// Defines routes with OpenAPI specs.
+pub fn routes_with_openapi(db_pool: Arc<PgPoolSquad>) -> OpenApiRouter {
+    let doctor_timezone_repository = DoctorTimezonePostgres::new(db_pool);
+    let doctor_timezone_use_case = DoctorTimezoneUseCase::new(Arc::new(doctor_timezone_repository));
+
+    OpenApiRouter::new().nest(
+        "/doctor-timezone",
+        OpenApiRouter::new()
+            .routes(utoipa_axum::routes!(get_doctor_timezone))
+            .routes(utoipa_axum::routes!(set_doctor_timezone))
+            .route_layer(middleware::from_fn(doctors_authorization))
+            .with_state(Arc::new(doctor_timezone_use_case)),
+    )
+}
+
+/// Retrieves the authenticated doctor's time zone.
+#[utoipa::path(
+    get,
+    path = "/",
+    tags = ["Doctor Timezone"],
+    responses(
+        (status = 200, description = "Fetched time zone successfully", body = ApiResponse<GetDoctorTimezoneResponseModel>)
+    )
+)]
+pub async fn get_doctor_timezone<T>(
+    State(doctor_timezone_use_case): State<Arc<DoctorTimezoneUseCase<T>>>,
+    AuthenticatedUser { id: doctor_id, .. }: AuthenticatedUser,
+) -> impl IntoResponse
+where
+    T: DoctorTimezoneRepository + Send + Sync,
+{
+    match doctor_timezone_use_case
+        .get_doctor_timezone(doctor_id)
+        .await
+    {
+        Ok(result) => (
+            StatusCode::OK,
+            Json(ApiResponse::<GetDoctorTimezoneResponseModel> {
+                data: Some(result),
+                message: None,
+                code: None,
+            }),
+        )
+            .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Sets the authenticated doctor's time zone.
+///
+/// Slot times sent by the doctor without a UTC offset are read in this zone.
+#[utoipa::path(
+    put,
+    path = "/",
+    tags = ["Doctor Timezone"],
+    request_body = SetDoctorTimezoneDto,
+    responses(
+        (status = 200, description = "Time zone set successfully", body = ApiResponse<GetDoctorTimezoneResponseModel>),
+        (status = 422, description = "Unknown time zone", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+pub async fn set_doctor_timezone<T>(
+    State(doctor_timezone_use_case): State<Arc<DoctorTimezoneUseCase<T>>>,
+    AuthenticatedUser { id: doctor_id, .. }: AuthenticatedUser,
+    Json(set_doctor_timezone_dto): Json<SetDoctorTimezoneDto>,
+) -> impl IntoResponse
+where
+    T: DoctorTimezoneRepository + Send + Sync,
+{
+    match doctor_timezone_use_case
+        .set_doctor_timezone(doctor_id, set_doctor_timezone_dto)
+        .await
+    {
+        Ok(result) => (
+            StatusCode::OK,
+            Json(ApiResponse::<GetDoctorTimezoneResponseModel> {
+                data: Some(result),
+                message: Some("Set time zone success".to_string()),
+                code: None,
+            }),
+        )
+            .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
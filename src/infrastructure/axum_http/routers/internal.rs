@@ -0,0 +1,332 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    middleware,
+    response::IntoResponse,
+};
+use utoipa_axum::router::OpenApiRouter;
+use uuid::Uuid;
+
+use crate::{
+    application::usecases::{
+        appointment_ledger::AppointmentLedgerUseCase, schedule_viewing::ScheduleViewingUseCase,
+        service_api_key::ServiceApiKeyUseCase,
+    },
+    domain::{
+        repositories::{
+            appointment_ledger::AppointmentLedgerRepository,
+            schedule_viewing::ScheduleViewingRepository,
+        },
+        value_objects::{
+            appointment_history_model::GetAppointmentHistoryResponseModel,
+            appointment_model::{TransitionAppointmentDto, TransitionAppointmentResponseModel},
+            booking_policy::BookingPolicy,
+            schedule_model::{
+                GetDoctorScheduleResponseModel, GetPatientScheduleResponseModel, ScheduleViewModel,
+            },
+            service_api_key_model::ServiceScope,
+            timezone_model::TimezoneQuery,
+        },
+    },
+    infrastructure::{
+        axum_http::{
+            api_response::{ApiResponse, EmptyResponseModel},
+            authenticated_service::AuthenticatedService,
+            middleware::services_authorization,
+        },
+        postgres::{
+            postgres_connection::PgPoolSquad,
+            repositories::{
+                appointment_ledger::AppointmentLedgerPostgres,
+                schedule_viewing::ScheduleViewingPostgres, service_api_key::ServiceApiKeyPostgres,
+            },
+        },
+    },
+};
+
+/// Defines routes for other MedBook services with OpenAPI specs. Unlike the
+/// patient and doctor routes, they are not limited to the caller's own
+/// appointments, only by the scopes of its API key.
+pub fn routes_with_openapi(
+    db_pool: Arc<PgPoolSquad>,
+    booking_policy: BookingPolicy,
+) -> OpenApiRouter {
+    let service_api_key_repository = ServiceApiKeyPostgres::new(db_pool.clone());
+    let service_api_key_use_case = ServiceApiKeyUseCase::new(Arc::new(service_api_key_repository));
+
+    let schedule_viewing_repository = ScheduleViewingPostgres::new(db_pool.clone());
+    let schedule_viewing_use_case =
+        ScheduleViewingUseCase::new(Arc::new(schedule_viewing_repository));
+
+    let appointment_ledger_repository = AppointmentLedgerPostgres::new(db_pool, booking_policy);
+    let appointment_ledger_use_case =
+        AppointmentLedgerUseCase::new(Arc::new(appointment_ledger_repository));
+
+    OpenApiRouter::new().nest(
+        "/internal",
+        OpenApiRouter::new()
+            .routes(utoipa_axum::routes!(get_appointment))
+            .routes(utoipa_axum::routes!(get_appointment_history))
+            .routes(utoipa_axum::routes!(get_patient_appointments))
+            .routes(utoipa_axum::routes!(get_doctor_appointments))
+            .with_state(Arc::new(schedule_viewing_use_case))
+            .merge(
+                OpenApiRouter::new()
+                    .routes(utoipa_axum::routes!(transition_appointment))
+                    .with_state(Arc::new(appointment_ledger_use_case)),
+            )
+            .route_layer(middleware::from_fn_with_state(
+                Arc::new(service_api_key_use_case),
+                services_authorization,
+            )),
+    )
+}
+
+/// Retrieves any appointment. Requires the `appointments:read` scope.
+#[utoipa::path(
+    get,
+    path = "/appointments/{appointment_id}",
+    tags = ["Internal"],
+    params(
+        ("appointment_id" = Uuid, Path, description = "Appointment ID to get"),
+        TimezoneQuery
+    ),
+    responses(
+        (status = 200, description = "Fetched appointment successfully", body = ApiResponse<ScheduleViewModel>),
+        (status = 401, description = "Missing or invalid API key", body = ApiResponse<EmptyResponseModel>),
+        (status = 403, description = "API key does not have the appointments:read scope", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Appointment not found", body = ApiResponse<EmptyResponseModel>),
+        (status = 422, description = "Unknown time zone", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+async fn get_appointment<T>(
+    State(schedule_viewing_use_case): State<Arc<ScheduleViewingUseCase<T>>>,
+    authenticated_service: AuthenticatedService,
+    Path(appointment_id): Path<Uuid>,
+    Query(timezone_query): Query<TimezoneQuery>,
+) -> impl IntoResponse
+where
+    T: ScheduleViewingRepository + Send + Sync,
+{
+    if let Err(e) = authenticated_service.require(ServiceScope::AppointmentsRead) {
+        return e.into_response();
+    }
+
+    match schedule_viewing_use_case
+        .get_schedule(appointment_id, timezone_query)
+        .await
+    {
+        Ok(schedule) => (
+            StatusCode::OK,
+            Json(ApiResponse::<ScheduleViewModel> {
+                data: Some(schedule),
+                message: None,
+                code: None,
+            }),
+        )
+            .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Retrieves the status history of any appointment. Requires the
+/// `appointments:read` scope.
+#[utoipa::path(
+    get,
+    path = "/appointments/{appointment_id}/history",
+    tags = ["Internal"],
+    params(
+        ("appointment_id" = Uuid, Path, description = "Appointment ID to get the history of")
+    ),
+    responses(
+        (status = 200, description = "Fetched appointment history successfully", body = ApiResponse<GetAppointmentHistoryResponseModel>),
+        (status = 401, description = "Missing or invalid API key", body = ApiResponse<EmptyResponseModel>),
+        (status = 403, description = "API key does not have the appointments:read scope", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Appointment not found", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+async fn get_appointment_history<T>(
+    State(schedule_viewing_use_case): State<Arc<ScheduleViewingUseCase<T>>>,
+    authenticated_service: AuthenticatedService,
+    Path(appointment_id): Path<Uuid>,
+) -> impl IntoResponse
+where
+    T: ScheduleViewingRepository + Send + Sync,
+{
+    if let Err(e) = authenticated_service.require(ServiceScope::AppointmentsRead) {
+        return e.into_response();
+    }
+
+    match schedule_viewing_use_case
+        .get_appointment_history(appointment_id, authenticated_service.to_actor())
+        .await
+    {
+        Ok(history) => (
+            StatusCode::OK,
+            Json(ApiResponse::<GetAppointmentHistoryResponseModel> {
+                data: Some(history),
+                message: None,
+                code: None,
+            }),
+        )
+            .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Retrieves all appointments of a patient. Requires the `appointments:read`
+/// scope.
+#[utoipa::path(
+    get,
+    path = "/patients/{patient_id}/appointments",
+    tags = ["Internal"],
+    params(
+        ("patient_id" = i32, Path, description = "Patient ID to get the appointments of"),
+        TimezoneQuery
+    ),
+    responses(
+        (status = 200, description = "Fetched patient appointments successfully", body = ApiResponse<GetPatientScheduleResponseModel>),
+        (status = 401, description = "Missing or invalid API key", body = ApiResponse<EmptyResponseModel>),
+        (status = 403, description = "API key does not have the appointments:read scope", body = ApiResponse<EmptyResponseModel>),
+        (status = 422, description = "Unknown time zone", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+async fn get_patient_appointments<T>(
+    State(schedule_viewing_use_case): State<Arc<ScheduleViewingUseCase<T>>>,
+    authenticated_service: AuthenticatedService,
+    Path(patient_id): Path<i32>,
+    Query(timezone_query): Query<TimezoneQuery>,
+) -> impl IntoResponse
+where
+    T: ScheduleViewingRepository + Send + Sync,
+{
+    if let Err(e) = authenticated_service.require(ServiceScope::AppointmentsRead) {
+        return e.into_response();
+    }
+
+    match schedule_viewing_use_case
+        .get_patient_schedules(patient_id, timezone_query)
+        .await
+    {
+        Ok(schedules) => (
+            StatusCode::OK,
+            Json(ApiResponse::<GetPatientScheduleResponseModel> {
+                data: Some(GetPatientScheduleResponseModel { schedules }),
+                message: None,
+                code: None,
+            }),
+        )
+            .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Retrieves all appointments in a doctor's slots. Requires the
+/// `appointments:read` scope.
+#[utoipa::path(
+    get,
+    path = "/doctors/{doctor_id}/appointments",
+    tags = ["Internal"],
+    params(
+        ("doctor_id" = i32, Path, description = "Doctor ID to get the appointments of"),
+        TimezoneQuery
+    ),
+    responses(
+        (status = 200, description = "Fetched doctor appointments successfully", body = ApiResponse<GetDoctorScheduleResponseModel>),
+        (status = 401, description = "Missing or invalid API key", body = ApiResponse<EmptyResponseModel>),
+        (status = 403, description = "API key does not have the appointments:read scope", body = ApiResponse<EmptyResponseModel>),
+        (status = 422, description = "Unknown time zone", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+async fn get_doctor_appointments<T>(
+    State(schedule_viewing_use_case): State<Arc<ScheduleViewingUseCase<T>>>,
+    authenticated_service: AuthenticatedService,
+    Path(doctor_id): Path<i32>,
+    Query(timezone_query): Query<TimezoneQuery>,
+) -> impl IntoResponse
+where
+    T: ScheduleViewingRepository + Send + Sync,
+{
+    if let Err(e) = authenticated_service.require(ServiceScope::AppointmentsRead) {
+        return e.into_response();
+    }
+
+    match schedule_viewing_use_case
+        .get_doctor_schedules(doctor_id, timezone_query)
+        .await
+    {
+        Ok(schedules) => (
+            StatusCode::OK,
+            Json(ApiResponse::<GetDoctorScheduleResponseModel> {
+                data: Some(GetDoctorScheduleResponseModel { schedules }),
+                message: None,
+                code: None,
+            }),
+        )
+            .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Moves any appointment to a status allowed by the status transition table,
+/// e.g. to **Completed** once its prescription has been issued. Requires the
+/// `ledger:write` scope.
+#[utoipa::path(
+    patch,
+    path = "/appointments/{appointment_id}/transition",
+    tags = ["Internal"],
+    params(
+        ("appointment_id" = Uuid, Path, description = "Appointment ID to update")
+    ),
+    request_body = TransitionAppointmentDto,
+    responses(
+        (status = 200, description = "Appointment status updated successfully", body = ApiResponse<TransitionAppointmentResponseModel>),
+        (status = 401, description = "Missing or invalid API key", body = ApiResponse<EmptyResponseModel>),
+        (status = 403, description = "API key does not have the ledger:write scope", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Appointment not found", body = ApiResponse<EmptyResponseModel>),
+        (status = 409, description = "Transition is not allowed or the slot is full", body = ApiResponse<EmptyResponseModel>),
+        (status = 422, description = "Invalid reason", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+async fn transition_appointment<T>(
+    State(appointment_ledger_use_case): State<Arc<AppointmentLedgerUseCase<T>>>,
+    authenticated_service: AuthenticatedService,
+    Path(appointment_id): Path<Uuid>,
+    Json(transition_appointment_dto): Json<TransitionAppointmentDto>,
+) -> impl IntoResponse
+where
+    T: AppointmentLedgerRepository + Send + Sync,
+{
+    if let Err(e) = authenticated_service.require(ServiceScope::LedgerWrite) {
+        return e.into_response();
+    }
+
+    match appointment_ledger_use_case
+        .transition_by_service(
+            appointment_id,
+            authenticated_service.id,
+            transition_appointment_dto,
+        )
+        .await
+    {
+        Ok(result) => {
+            let response = format!(
+                "Appointment id: {} is now {:?}",
+                result.appointment_id, result.status
+            );
+            (
+                StatusCode::OK,
+                Json(ApiResponse::<TransitionAppointmentResponseModel> {
+                    data: Some(result),
+                    message: Some(response),
+                    code: None,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
@@ -1,7 +1,15 @@
+pub mod admin;
 pub mod appointment_ledger;
 pub mod appointment_ops;
+pub mod booking_window;
+pub mod doctor_timezone;
+pub mod internal;
 pub mod doctor_schedule_viewing;
 pub mod doctor_slot_viewing;
 pub mod patient_schedule_viewing;
+pub mod schedule_viewing;
 pub mod slot_ops;
+pub mod slot_template;
 pub mod slot_viewing;
+pub mod waitlist;
+pub mod webhook_subscription;
@@ -1,7 +1,11 @@
 use std::sync::Arc;
 
 use axum::{
-    Extension, Json, Router, extract::State, http::StatusCode, middleware, response::IntoResponse,
+    Json, Router,
+    extract::{Query, State},
+    http::StatusCode,
+    middleware,
+    response::IntoResponse,
     routing::get,
 };
 use utoipa_axum::router::OpenApiRouter;
@@ -10,10 +14,16 @@ use crate::{
     application::usecases::schedule_viewing::ScheduleViewingUseCase,
     domain::{
         repositories::schedule_viewing::ScheduleViewingRepository,
-        value_objects::schedule_model::GetPatientScheduleResponseModel,
+        value_objects::{
+            schedule_model::GetPatientScheduleResponseModel, timezone_model::TimezoneQuery,
+        },
     },
     infrastructure::{
-        axum_http::{api_response::ApiResponse, middleware::patients_authorization},
+        axum_http::{
+            api_response::{ApiResponse, EmptyResponseModel},
+            authenticated_user::AuthenticatedUser,
+            middleware::patients_authorization,
+        },
         postgres::{
             postgres_connection::PgPoolSquad,
             repositories::schedule_viewing::ScheduleViewingPostgres,
@@ -53,19 +63,22 @@ pub fn routes_with_openapi(db_pool: Arc<PgPoolSquad>) -> OpenApiRouter {
     get,
     path = "/",
     tags = ["Schedule Viewing"],
+    params(TimezoneQuery),
     responses(
-        (status = 200, description = "Fetched patient schedules successfully", body = ApiResponse<GetPatientScheduleResponseModel>)
+        (status = 200, description = "Fetched patient schedules successfully", body = ApiResponse<GetPatientScheduleResponseModel>),
+        (status = 422, description = "Unknown time zone", body = ApiResponse<EmptyResponseModel>)
     )
 )]
 async fn get_patient_schedules<T>(
     State(schedule_viewing_use_case): State<Arc<ScheduleViewingUseCase<T>>>,
-    Extension(patient_id): Extension<i32>,
+    AuthenticatedUser { id: patient_id, .. }: AuthenticatedUser,
+    Query(timezone_query): Query<TimezoneQuery>,
 ) -> impl IntoResponse
 where
     T: ScheduleViewingRepository + Send + Sync,
 {
     match schedule_viewing_use_case
-        .get_patient_schedules(patient_id)
+        .get_patient_schedules(patient_id, timezone_query)
         .await
     {
         Ok(schedules) => (
@@ -73,16 +86,10 @@ where
             Json(ApiResponse::<GetPatientScheduleResponseModel> {
                 data: Some(GetPatientScheduleResponseModel { schedules }),
                 message: None,
+                code: None,
             }),
         )
             .into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::<GetPatientScheduleResponseModel> {
-                data: None,
-                message: Some(e.to_string()),
-            }),
-        )
-            .into_response(),
+        Err(e) => e.into_response(),
     }
 }
@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    middleware,
+    response::IntoResponse,
+};
+use utoipa_axum::router::OpenApiRouter;
+use uuid::Uuid;
+
+use crate::{
+    application::usecases::schedule_viewing::ScheduleViewingUseCase,
+    domain::{
+        repositories::schedule_viewing::ScheduleViewingRepository,
+        value_objects::appointment_history_model::GetAppointmentHistoryResponseModel,
+    },
+    infrastructure::{
+        axum_http::{
+            api_response::{ApiResponse, EmptyResponseModel},
+            authenticated_user::AuthenticatedUser,
+            middleware::patients_or_doctors_authorization,
+        },
+        postgres::{
+            postgres_connection::PgPoolSquad,
+            repositories::schedule_viewing::ScheduleViewingPostgres,
+        },
+    },
+};
+
+/// Defines routes open to both patients and doctors with OpenAPI specs.
+pub fn routes_with_openapi(db_pool: Arc<PgPoolSquad>) -> OpenApiRouter {
+    let schedule_viewing_repository = ScheduleViewingPostgres::new(db_pool);
+    let schedule_viewing_use_case =
+        ScheduleViewingUseCase::new(Arc::new(schedule_viewing_repository));
+
+    OpenApiRouter::new().nest(
+        "/schedule-view",
+        OpenApiRouter::new()
+            .routes(utoipa_axum::routes!(get_appointment_history))
+            .route_layer(middleware::from_fn(patients_or_doctors_authorization))
+            .with_state(Arc::new(schedule_viewing_use_case)),
+    )
+}
+
+/// Retrieves the status history of an appointment of the authenticated patient,
+/// or of an appointment in one of the authenticated doctor's slots.
+#[utoipa::path(
+    get,
+    path = "/{appointment_id}/history",
+    tags = ["Schedule Viewing"],
+    params(
+        ("appointment_id" = Uuid, Path, description = "Appointment ID to get the history of")
+    ),
+    responses(
+        (status = 200, description = "Fetched appointment history successfully", body = ApiResponse<GetAppointmentHistoryResponseModel>),
+        (status = 401, description = "Missing or invalid access token", body = ApiResponse<EmptyResponseModel>),
+        (status = 403, description = "Appointment is neither the patient's nor in one of the doctor's slots", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Appointment not found", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+async fn get_appointment_history<T>(
+    State(schedule_viewing_use_case): State<Arc<ScheduleViewingUseCase<T>>>,
+    authenticated_user: AuthenticatedUser,
+    Path(appointment_id): Path<Uuid>,
+) -> impl IntoResponse
+where
+    T: ScheduleViewingRepository + Send + Sync,
+{
+    match schedule_viewing_use_case
+        .get_appointment_history(appointment_id, authenticated_user.to_actor())
+        .await
+    {
+        Ok(history) => (
+            StatusCode::OK,
+            Json(ApiResponse::<GetAppointmentHistoryResponseModel> {
+                data: Some(history),
+                message: None,
+                code: None,
+            }),
+        )
+            .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
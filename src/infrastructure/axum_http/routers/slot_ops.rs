@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use axum::{
-    Extension, Json, Router,
+    Json, Router,
     extract::{Path, State},
     http::StatusCode,
     middleware,
@@ -15,11 +15,15 @@ use crate::{
     application::usecases::slot_ops::SlotOpsUseCase,
     domain::{
         repositories::slot_ops::SlotOpsRepository,
-        value_objects::slot_model::{AddSlotDto, EditSlotDto},
+        value_objects::slot_model::{
+            AddSlotDto, AddSlotsBulkDto, AddSlotsBulkResponseModel, EditSlotDto,
+            ForceRemoveSlotDto, ForceShrinkSlotDto, SlotCascadeResponseModel,
+        },
     },
     infrastructure::{
         axum_http::{
             api_response::{ApiResponse, EmptyResponseModel},
+            authenticated_user::AuthenticatedUser,
             middleware::doctors_authorization,
         },
         postgres::{postgres_connection::PgPoolSquad, repositories::slot_ops::SlotOpsPostgres},
@@ -48,8 +52,11 @@ pub fn routes_with_openapi(db_pool: Arc<PgPoolSquad>) -> OpenApiRouter {
         "/slot-ops",
         OpenApiRouter::new()
             .routes(utoipa_axum::routes!(add))
+            .routes(utoipa_axum::routes!(add_bulk))
             .routes(utoipa_axum::routes!(edit))
             .routes(utoipa_axum::routes!(remove))
+            .routes(utoipa_axum::routes!(force_shrink))
+            .routes(utoipa_axum::routes!(force_remove))
             .route_layer(middleware::from_fn(doctors_authorization))
             .with_state(Arc::new(slot_ops_use_case)),
     )
@@ -62,12 +69,14 @@ pub fn routes_with_openapi(db_pool: Arc<PgPoolSquad>) -> OpenApiRouter {
     tags = ["Slot Operations"],
     request_body = AddSlotDto,
     responses(
-        (status = 200, description = "Slot added successfully", body = ApiResponse<EmptyResponseModel>)
+        (status = 200, description = "Slot added successfully", body = ApiResponse<EmptyResponseModel>),
+        (status = 409, description = "Slot time is overlapping", body = ApiResponse<EmptyResponseModel>),
+        (status = 422, description = "Slot is in the past", body = ApiResponse<EmptyResponseModel>)
     )
 )]
 pub async fn add<T>(
     State(slot_ops_use_case): State<Arc<SlotOpsUseCase<T>>>,
-    Extension(doctor_id): Extension<i32>,
+    AuthenticatedUser { id: doctor_id, .. }: AuthenticatedUser,
     Json(add_slot_dto): Json<AddSlotDto>,
 ) -> impl IntoResponse
 where
@@ -81,16 +90,59 @@ where
                 Json(ApiResponse::<EmptyResponseModel> {
                     data: None,
                     message: Some(response),
+                    code: None,
                 }),
             )
+                .into_response()
         }
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::<EmptyResponseModel> {
-                data: None,
-                message: Some(e.to_string()),
-            }),
-        ),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Adds many doctor slots at once.
+///
+/// In `all_or_nothing` mode (the default) nothing is created unless every slot
+/// can be created. In `best_effort` mode every slot that can be created is.
+/// Either way the outcome of every item is returned.
+#[utoipa::path(
+    post,
+    path = "/bulk",
+    tags = ["Slot Operations"],
+    request_body = AddSlotsBulkDto,
+    responses(
+        (status = 200, description = "Bulk slot creation processed, see per-item results", body = ApiResponse<AddSlotsBulkResponseModel>),
+        (status = 422, description = "Empty or too large batch", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+pub async fn add_bulk<T>(
+    State(slot_ops_use_case): State<Arc<SlotOpsUseCase<T>>>,
+    AuthenticatedUser { id: doctor_id, .. }: AuthenticatedUser,
+    Json(add_slots_bulk_dto): Json<AddSlotsBulkDto>,
+) -> impl IntoResponse
+where
+    T: SlotOpsRepository + Send + Sync,
+{
+    match slot_ops_use_case
+        .add_bulk(doctor_id, add_slots_bulk_dto)
+        .await
+    {
+        Ok(result) => {
+            let response = format!(
+                "Add slots success, {} of {} created",
+                result.created_count,
+                result.results.len()
+            );
+            (
+                StatusCode::OK,
+                Json(ApiResponse::<AddSlotsBulkResponseModel> {
+                    data: Some(result),
+                    message: Some(response),
+                    code: None,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => e.into_response(),
     }
 }
 
@@ -104,12 +156,16 @@ where
     ),
     request_body = EditSlotDto,
     responses(
-        (status = 200, description = "Slot edited successfully", body = ApiResponse<EmptyResponseModel>)
+        (status = 200, description = "Slot edited successfully", body = ApiResponse<EmptyResponseModel>),
+        (status = 403, description = "Slot does not belong to the doctor", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Slot not found", body = ApiResponse<EmptyResponseModel>),
+        (status = 409, description = "Slot already has more appointments than the new capacity", body = ApiResponse<EmptyResponseModel>),
+        (status = 422, description = "Slot is already ended or the edit is invalid", body = ApiResponse<EmptyResponseModel>)
     )
 )]
 pub async fn edit<T>(
     State(slot_ops_use_case): State<Arc<SlotOpsUseCase<T>>>,
-    Extension(doctor_id): Extension<i32>,
+    AuthenticatedUser { id: doctor_id, .. }: AuthenticatedUser,
     Path(slot_id): Path<Uuid>,
     Json(edit_slot_dto): Json<EditSlotDto>,
 ) -> impl IntoResponse
@@ -127,16 +183,12 @@ where
                 Json(ApiResponse::<EmptyResponseModel> {
                     data: None,
                     message: Some(response),
+                    code: None,
                 }),
             )
+                .into_response()
         }
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::<EmptyResponseModel> {
-                data: None,
-                message: Some(e.to_string()),
-            }),
-        ),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -149,12 +201,16 @@ where
         ("slot_id" = Uuid, Path, description = "Slot ID to remove")
     ),
     responses(
-        (status = 200, description = "Slot removed successfully", body = ApiResponse<EmptyResponseModel>)
+        (status = 200, description = "Slot removed successfully", body = ApiResponse<EmptyResponseModel>),
+        (status = 403, description = "Slot does not belong to the doctor", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Slot not found", body = ApiResponse<EmptyResponseModel>),
+        (status = 409, description = "Patient already booked this slot", body = ApiResponse<EmptyResponseModel>),
+        (status = 422, description = "Slot is already ended", body = ApiResponse<EmptyResponseModel>)
     )
 )]
 pub async fn remove<T>(
     State(slot_ops_use_case): State<Arc<SlotOpsUseCase<T>>>,
-    Extension(doctor_id): Extension<i32>,
+    AuthenticatedUser { id: doctor_id, .. }: AuthenticatedUser,
     Path(slot_id): Path<Uuid>,
 ) -> impl IntoResponse
 where
@@ -168,15 +224,117 @@ where
                 Json(ApiResponse::<EmptyResponseModel> {
                     data: None,
                     message: Some(response),
+                    code: None,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Lowers the capacity of a booked slot.
+///
+/// The most recently booked appointments that no longer fit are cancelled with
+/// `reason`, or moved to `move_to_slot_id` when it is set.
+#[utoipa::path(
+    post,
+    path = "/{slot_id}/force-shrink",
+    tags = ["Slot Operations"],
+    params(
+        ("slot_id" = Uuid, Path, description = "Slot ID to shrink")
+    ),
+    request_body = ForceShrinkSlotDto,
+    responses(
+        (status = 200, description = "Slot shrunk successfully, see affected appointments", body = ApiResponse<SlotCascadeResponseModel>),
+        (status = 403, description = "Slot does not belong to the doctor", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Slot not found", body = ApiResponse<EmptyResponseModel>),
+        (status = 409, description = "An affected appointment cannot be cancelled or the target slot is full", body = ApiResponse<EmptyResponseModel>),
+        (status = 422, description = "Slot is already ended or the request is invalid", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+pub async fn force_shrink<T>(
+    State(slot_ops_use_case): State<Arc<SlotOpsUseCase<T>>>,
+    AuthenticatedUser { id: doctor_id, .. }: AuthenticatedUser,
+    Path(slot_id): Path<Uuid>,
+    Json(force_shrink_slot_dto): Json<ForceShrinkSlotDto>,
+) -> impl IntoResponse
+where
+    T: SlotOpsRepository + Send + Sync,
+{
+    match slot_ops_use_case
+        .force_shrink(slot_id, doctor_id, force_shrink_slot_dto)
+        .await
+    {
+        Ok(result) => {
+            let response = format!(
+                "Shrink slot success with id: {}, {} appointments affected",
+                slot_id,
+                result.affected_appointments.len()
+            );
+            (
+                StatusCode::OK,
+                Json(ApiResponse::<SlotCascadeResponseModel> {
+                    data: Some(result),
+                    message: Some(response),
+                    code: None,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Removes a booked slot.
+///
+/// Its appointments are cancelled with `reason`, or moved to `move_to_slot_id`
+/// when it is set.
+#[utoipa::path(
+    post,
+    path = "/{slot_id}/force-remove",
+    tags = ["Slot Operations"],
+    params(
+        ("slot_id" = Uuid, Path, description = "Slot ID to remove")
+    ),
+    request_body = ForceRemoveSlotDto,
+    responses(
+        (status = 200, description = "Slot removed successfully, see affected appointments", body = ApiResponse<SlotCascadeResponseModel>),
+        (status = 403, description = "Slot does not belong to the doctor", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Slot not found", body = ApiResponse<EmptyResponseModel>),
+        (status = 409, description = "An affected appointment cannot be cancelled or the target slot is full", body = ApiResponse<EmptyResponseModel>),
+        (status = 422, description = "Slot is already ended or the request is invalid", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+pub async fn force_remove<T>(
+    State(slot_ops_use_case): State<Arc<SlotOpsUseCase<T>>>,
+    AuthenticatedUser { id: doctor_id, .. }: AuthenticatedUser,
+    Path(slot_id): Path<Uuid>,
+    Json(force_remove_slot_dto): Json<ForceRemoveSlotDto>,
+) -> impl IntoResponse
+where
+    T: SlotOpsRepository + Send + Sync,
+{
+    match slot_ops_use_case
+        .force_remove(slot_id, doctor_id, force_remove_slot_dto)
+        .await
+    {
+        Ok(result) => {
+            let response = format!(
+                "Remove slot success with id: {}, {} appointments affected",
+                slot_id,
+                result.affected_appointments.len()
+            );
+            (
+                StatusCode::OK,
+                Json(ApiResponse::<SlotCascadeResponseModel> {
+                    data: Some(result),
+                    message: Some(response),
+                    code: None,
                 }),
             )
+                .into_response()
         }
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::<EmptyResponseModel> {
-                data: None,
-                message: Some(e.to_string()),
-            }),
-        ),
+        Err(e) => e.into_response(),
     }
 }
@@ -0,0 +1,247 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    middleware,
+    response::IntoResponse,
+};
+use utoipa_axum::router::OpenApiRouter;
+use uuid::Uuid;
+
+use crate::{
+    application::usecases::slot_template::SlotTemplateUseCase,
+    domain::{
+        repositories::slot_template::SlotTemplateRepository,
+        value_objects::slot_template_model::{
+            AddSlotTemplateDto, EditSlotTemplateDto, GetSlotTemplatesResponseModel,
+            MaterializeSlotTemplateResponseModel, PreviewSlotTemplateResponseModel,
+        },
+    },
+    infrastructure::{
+        axum_http::{
+            api_response::{ApiResponse, EmptyResponseModel},
+            authenticated_user::AuthenticatedUser,
+            middleware::doctors_authorization,
+        },
+        postgres::{
+            postgres_connection::PgPoolSquad, repositories::slot_template::SlotTemplatePostgres,
+        },
+    },
+};
+
+/// Defines routes with OpenAPI specs.
+pub fn routes_with_openapi(db_pool: Arc<PgPoolSquad>) -> OpenApiRouter {
+    let slot_template_repository = SlotTemplatePostgres::new(db_pool);
+    let slot_template_use_case = SlotTemplateUseCase::new(Arc::new(slot_template_repository));
+
+    OpenApiRouter::new().nest(
+        "/slot-template",
+        OpenApiRouter::new()
+            .routes(utoipa_axum::routes!(get_doctor_slot_templates))
+            .routes(utoipa_axum::routes!(preview))
+            .routes(utoipa_axum::routes!(add))
+            .routes(utoipa_axum::routes!(edit))
+            .routes(utoipa_axum::routes!(remove))
+            .route_layer(middleware::from_fn(doctors_authorization))
+            .with_state(Arc::new(slot_template_use_case)),
+    )
+}
+
+/// Retrieves all slot templates belonging to the authenticated doctor.
+#[utoipa::path(
+    get,
+    path = "/",
+    tags = ["Slot Templates"],
+    responses(
+        (status = 200, description = "Fetched doctor slot templates successfully", body = ApiResponse<GetSlotTemplatesResponseModel>)
+    )
+)]
+pub async fn get_doctor_slot_templates<T>(
+    State(slot_template_use_case): State<Arc<SlotTemplateUseCase<T>>>,
+    AuthenticatedUser { id: doctor_id, .. }: AuthenticatedUser,
+) -> impl IntoResponse
+where
+    T: SlotTemplateRepository + Send + Sync,
+{
+    match slot_template_use_case
+        .get_doctor_slot_templates(doctor_id)
+        .await
+    {
+        Ok(templates) => (
+            StatusCode::OK,
+            Json(ApiResponse::<GetSlotTemplatesResponseModel> {
+                data: Some(GetSlotTemplatesResponseModel { templates }),
+                message: None,
+                code: None,
+            }),
+        )
+            .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Previews the slots a template would create, without saving anything.
+#[utoipa::path(
+    post,
+    path = "/preview",
+    tags = ["Slot Templates"],
+    request_body = AddSlotTemplateDto,
+    responses(
+        (status = 200, description = "Previewed slot template successfully", body = ApiResponse<PreviewSlotTemplateResponseModel>),
+        (status = 422, description = "Invalid slot template", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+pub async fn preview<T>(
+    State(slot_template_use_case): State<Arc<SlotTemplateUseCase<T>>>,
+    AuthenticatedUser { id: doctor_id, .. }: AuthenticatedUser,
+    Json(add_slot_template_dto): Json<AddSlotTemplateDto>,
+) -> impl IntoResponse
+where
+    T: SlotTemplateRepository + Send + Sync,
+{
+    match slot_template_use_case
+        .preview(doctor_id, add_slot_template_dto)
+        .await
+    {
+        Ok(preview) => (
+            StatusCode::OK,
+            Json(ApiResponse::<PreviewSlotTemplateResponseModel> {
+                data: Some(preview),
+                message: None,
+                code: None,
+            }),
+        )
+            .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Adds a new slot template and creates its slots.
+///
+/// Slots that are in the past or overlap an existing slot are skipped.
+#[utoipa::path(
+    post,
+    path = "/",
+    tags = ["Slot Templates"],
+    request_body = AddSlotTemplateDto,
+    responses(
+        (status = 200, description = "Slot template added successfully", body = ApiResponse<MaterializeSlotTemplateResponseModel>),
+        (status = 422, description = "Invalid slot template", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+pub async fn add<T>(
+    State(slot_template_use_case): State<Arc<SlotTemplateUseCase<T>>>,
+    AuthenticatedUser { id: doctor_id, .. }: AuthenticatedUser,
+    Json(add_slot_template_dto): Json<AddSlotTemplateDto>,
+) -> impl IntoResponse
+where
+    T: SlotTemplateRepository + Send + Sync,
+{
+    match slot_template_use_case
+        .add(doctor_id, add_slot_template_dto)
+        .await
+    {
+        Ok(result) => {
+            let response = format!("Add slot template success with id: {}", result.template_id);
+            (
+                StatusCode::OK,
+                Json(ApiResponse::<MaterializeSlotTemplateResponseModel> {
+                    data: Some(result),
+                    message: Some(response),
+                    code: None,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Edits a slot template and regenerates its unbooked future slots.
+#[utoipa::path(
+    patch,
+    path = "/{template_id}",
+    tags = ["Slot Templates"],
+    params(
+        ("template_id" = Uuid, Path, description = "Slot template ID to edit")
+    ),
+    request_body = EditSlotTemplateDto,
+    responses(
+        (status = 200, description = "Slot template edited successfully", body = ApiResponse<MaterializeSlotTemplateResponseModel>),
+        (status = 403, description = "Slot template does not belong to the doctor", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Slot template not found", body = ApiResponse<EmptyResponseModel>),
+        (status = 422, description = "Invalid slot template", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+pub async fn edit<T>(
+    State(slot_template_use_case): State<Arc<SlotTemplateUseCase<T>>>,
+    AuthenticatedUser { id: doctor_id, .. }: AuthenticatedUser,
+    Path(template_id): Path<Uuid>,
+    Json(edit_slot_template_dto): Json<EditSlotTemplateDto>,
+) -> impl IntoResponse
+where
+    T: SlotTemplateRepository + Send + Sync,
+{
+    match slot_template_use_case
+        .edit(template_id, doctor_id, edit_slot_template_dto)
+        .await
+    {
+        Ok(result) => {
+            let response = format!("Edit slot template success with id: {}", template_id);
+            (
+                StatusCode::OK,
+                Json(ApiResponse::<MaterializeSlotTemplateResponseModel> {
+                    data: Some(result),
+                    message: Some(response),
+                    code: None,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Removes a slot template and its unbooked future slots. Booked slots are kept.
+#[utoipa::path(
+    delete,
+    path = "/{template_id}",
+    tags = ["Slot Templates"],
+    params(
+        ("template_id" = Uuid, Path, description = "Slot template ID to remove")
+    ),
+    responses(
+        (status = 200, description = "Slot template removed successfully", body = ApiResponse<EmptyResponseModel>),
+        (status = 403, description = "Slot template does not belong to the doctor", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Slot template not found", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+pub async fn remove<T>(
+    State(slot_template_use_case): State<Arc<SlotTemplateUseCase<T>>>,
+    AuthenticatedUser { id: doctor_id, .. }: AuthenticatedUser,
+    Path(template_id): Path<Uuid>,
+) -> impl IntoResponse
+where
+    T: SlotTemplateRepository + Send + Sync,
+{
+    match slot_template_use_case.remove(template_id, doctor_id).await {
+        Ok(removed_slot_count) => {
+            let response = format!(
+                "Remove slot template success with id: {}, {} unbooked slots removed",
+                template_id, removed_slot_count
+            );
+            (
+                StatusCode::OK,
+                Json(ApiResponse::<EmptyResponseModel> {
+                    data: None,
+                    message: Some(response),
+                    code: None,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
@@ -1,16 +1,22 @@
 use std::sync::Arc;
 
-use axum::{Json, Router, extract::State, http::StatusCode, response::IntoResponse, routing::get};
+use axum::{
+    Json, Router,
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::get,
+};
 use utoipa_axum::router::OpenApiRouter;
 
 use crate::{
     application::usecases::slot_viewing::SlotViewingUseCase,
     domain::{
         repositories::slot_viewing::SlotViewingRepository,
-        value_objects::slot_model::GetSlotsResponseModel,
+        value_objects::slot_model::{GetSlotsQuery, GetSlotsResponseModel},
     },
     infrastructure::{
-        axum_http::api_response::ApiResponse,
+        axum_http::api_response::{ApiResponse, EmptyResponseModel},
         postgres::{
             postgres_connection::PgPoolSquad, repositories::slot_viewing::SlotViewingPostgres,
         },
@@ -40,37 +46,36 @@ pub fn routes_with_openapi(db_pool: Arc<PgPoolSquad>) -> OpenApiRouter {
     )
 }
 
-/// Retrieves all available slots (public endpoint, no authentication required).
+/// Searches bookable slots (public endpoint, no authentication required).
+///
+/// By default only future slots with free capacity are returned.
 #[utoipa::path(
     get,
     path = "/",
     tags = ["Slot Viewing"],
+    params(GetSlotsQuery),
     responses(
-        (status = 200, description = "Fetched all available slots successfully", body = ApiResponse<GetSlotsResponseModel>)
+        (status = 200, description = "Fetched available slots successfully", body = ApiResponse<GetSlotsResponseModel>),
+        (status = 422, description = "Invalid search parameters", body = ApiResponse<EmptyResponseModel>)
     )
 )]
 async fn get_slots<T>(
     State(slot_viewing_use_case): State<Arc<SlotViewingUseCase<T>>>,
+    Query(get_slots_query): Query<GetSlotsQuery>,
 ) -> impl IntoResponse
 where
     T: SlotViewingRepository + Send + Sync,
 {
-    match slot_viewing_use_case.get_slots().await {
-        Ok(slots) => (
+    match slot_viewing_use_case.get_slots(get_slots_query).await {
+        Ok(get_slots_response_model) => (
             StatusCode::OK,
             Json(ApiResponse::<GetSlotsResponseModel> {
-                data: Some(GetSlotsResponseModel { slots }),
+                data: Some(get_slots_response_model),
                 message: None,
+                code: None,
             }),
         )
             .into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::<GetSlotsResponseModel> {
-                data: None,
-                message: Some(e.to_string()),
-            }),
-        )
-            .into_response(),
+        Err(e) => e.into_response(),
     }
 }
@@ -0,0 +1,163 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    middleware,
+    response::IntoResponse,
+};
+use utoipa_axum::router::OpenApiRouter;
+use uuid::Uuid;
+
+use crate::{
+    application::usecases::waitlist::WaitlistUseCase,
+    domain::{
+        repositories::waitlist::WaitlistRepository,
+        value_objects::{
+            appointment_model::AddAppointmentDto,
+            booking_policy::BookingPolicy,
+            waitlist_model::{GetWaitlistResponseModel, JoinWaitlistResponseModel},
+        },
+    },
+    infrastructure::{
+        axum_http::{
+            api_response::{ApiResponse, EmptyResponseModel},
+            authenticated_user::AuthenticatedUser,
+            middleware::patients_authorization,
+        },
+        postgres::{postgres_connection::PgPoolSquad, repositories::waitlist::WaitlistPostgres},
+    },
+};
+
+/// Defines routes with OpenAPI specs.
+pub fn routes_with_openapi(
+    db_pool: Arc<PgPoolSquad>,
+    booking_policy: BookingPolicy,
+) -> OpenApiRouter {
+    let waitlist_repository = WaitlistPostgres::new(db_pool, booking_policy);
+    let waitlist_use_case = WaitlistUseCase::new(Arc::new(waitlist_repository));
+
+    OpenApiRouter::new().nest(
+        "/waitlist",
+        OpenApiRouter::new()
+            .routes(utoipa_axum::routes!(get_patient_waitlist))
+            .routes(utoipa_axum::routes!(join))
+            .routes(utoipa_axum::routes!(leave))
+            .route_layer(middleware::from_fn(patients_authorization))
+            .with_state(Arc::new(waitlist_use_case)),
+    )
+}
+
+/// Retrieves the authenticated patient's waitlist entries and their positions.
+#[utoipa::path(
+    get,
+    path = "/",
+    tags = ["Waitlist"],
+    responses(
+        (status = 200, description = "Fetched patient waitlist successfully", body = ApiResponse<GetWaitlistResponseModel>)
+    )
+)]
+pub async fn get_patient_waitlist<T>(
+    State(waitlist_use_case): State<Arc<WaitlistUseCase<T>>>,
+    AuthenticatedUser { id: patient_id, .. }: AuthenticatedUser,
+) -> impl IntoResponse
+where
+    T: WaitlistRepository + Send + Sync,
+{
+    match waitlist_use_case.get_patient_waitlist(patient_id).await {
+        Ok(entries) => (
+            StatusCode::OK,
+            Json(ApiResponse::<GetWaitlistResponseModel> {
+                data: Some(GetWaitlistResponseModel { entries }),
+                message: None,
+                code: None,
+            }),
+        )
+            .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Joins the waitlist of a full slot.
+///
+/// When a place frees up, the first patient in line gets an appointment
+/// booked with the details sent here.
+#[utoipa::path(
+    post,
+    path = "/",
+    tags = ["Waitlist"],
+    request_body = AddAppointmentDto,
+    responses(
+        (status = 200, description = "Joined waitlist successfully", body = ApiResponse<JoinWaitlistResponseModel>),
+        (status = 404, description = "Slot not found", body = ApiResponse<EmptyResponseModel>),
+        (status = 409, description = "Slot still has free places, the patient is already waiting, or the booking policy was violated", body = ApiResponse<EmptyResponseModel>),
+        (status = 422, description = "Slot is already ended", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+pub async fn join<T>(
+    State(waitlist_use_case): State<Arc<WaitlistUseCase<T>>>,
+    AuthenticatedUser { id: patient_id, .. }: AuthenticatedUser,
+    Json(add_appointment_dto): Json<AddAppointmentDto>,
+) -> impl IntoResponse
+where
+    T: WaitlistRepository + Send + Sync,
+{
+    match waitlist_use_case
+        .join(add_appointment_dto, patient_id)
+        .await
+    {
+        Ok(result) => {
+            let response = format!("Joined waitlist at position {}", result.position);
+            (
+                StatusCode::OK,
+                Json(ApiResponse::<JoinWaitlistResponseModel> {
+                    data: Some(result),
+                    message: Some(response),
+                    code: None,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Leaves a waitlist by entry ID.
+#[utoipa::path(
+    delete,
+    path = "/{waitlist_entry_id}",
+    tags = ["Waitlist"],
+    params(
+        ("waitlist_entry_id" = Uuid, Path, description = "Waitlist entry ID to remove")
+    ),
+    responses(
+        (status = 200, description = "Left waitlist successfully", body = ApiResponse<EmptyResponseModel>),
+        (status = 403, description = "Waitlist entry does not belong to the patient", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Waitlist entry not found", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+pub async fn leave<T>(
+    State(waitlist_use_case): State<Arc<WaitlistUseCase<T>>>,
+    AuthenticatedUser { id: patient_id, .. }: AuthenticatedUser,
+    Path(waitlist_entry_id): Path<Uuid>,
+) -> impl IntoResponse
+where
+    T: WaitlistRepository + Send + Sync,
+{
+    match waitlist_use_case.leave(waitlist_entry_id, patient_id).await {
+        Ok(_) => {
+            let response = format!("Left waitlist entry with id: {}", waitlist_entry_id);
+            (
+                StatusCode::OK,
+                Json(ApiResponse::<EmptyResponseModel> {
+                    data: None,
+                    message: Some(response),
+                    code: None,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
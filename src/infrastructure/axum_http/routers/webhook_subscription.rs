@@ -0,0 +1,378 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    middleware,
+    response::IntoResponse,
+};
+use utoipa_axum::router::OpenApiRouter;
+use uuid::Uuid;
+
+use crate::{
+    application::usecases::{
+        service_api_key::ServiceApiKeyUseCase, webhook_subscription::WebhookSubscriptionUseCase,
+    },
+    domain::{
+        repositories::webhook::WebhookSubscriptionRepository,
+        value_objects::{
+            service_api_key_model::ServiceScope,
+            webhook_model::{
+                AddWebhookSubscriptionDto, AddWebhookSubscriptionResponseModel,
+                EditWebhookSubscriptionDto, GetWebhookDeliveriesQuery,
+                GetWebhookDeliveriesResponseModel, GetWebhookSubscriptionsResponseModel,
+                WebhookDeliveryModel, WebhookSubscriptionModel,
+            },
+        },
+    },
+    infrastructure::{
+        axum_http::{
+            api_response::{ApiResponse, EmptyResponseModel},
+            authenticated_service::AuthenticatedService,
+            middleware::services_authorization,
+        },
+        postgres::{
+            postgres_connection::PgPoolSquad,
+            repositories::{
+                service_api_key::ServiceApiKeyPostgres, webhook::WebhookSubscriptionPostgres,
+            },
+        },
+    },
+};
+
+/// Defines routes for other MedBook services to manage the webhooks they
+/// receive booking events on, with OpenAPI specs. A service only sees the
+/// subscriptions of its own API key.
+pub fn routes_with_openapi(db_pool: Arc<PgPoolSquad>) -> OpenApiRouter {
+    let service_api_key_repository = ServiceApiKeyPostgres::new(db_pool.clone());
+    let service_api_key_use_case = ServiceApiKeyUseCase::new(Arc::new(service_api_key_repository));
+
+    let webhook_subscription_repository = WebhookSubscriptionPostgres::new(db_pool);
+    let webhook_subscription_use_case =
+        WebhookSubscriptionUseCase::new(Arc::new(webhook_subscription_repository));
+
+    OpenApiRouter::new().nest(
+        "/internal/webhooks",
+        OpenApiRouter::new()
+            .routes(utoipa_axum::routes!(get_subscriptions))
+            .routes(utoipa_axum::routes!(add))
+            .routes(utoipa_axum::routes!(edit))
+            .routes(utoipa_axum::routes!(remove))
+            .routes(utoipa_axum::routes!(get_deliveries))
+            .routes(utoipa_axum::routes!(redeliver))
+            .with_state(Arc::new(webhook_subscription_use_case))
+            .route_layer(middleware::from_fn_with_state(
+                Arc::new(service_api_key_use_case),
+                services_authorization,
+            )),
+    )
+}
+
+/// Retrieves the webhook subscriptions of the calling service. Requires the
+/// `webhooks:write` scope.
+#[utoipa::path(
+    get,
+    path = "/",
+    tags = ["Webhooks"],
+    responses(
+        (status = 200, description = "Fetched webhook subscriptions successfully", body = ApiResponse<GetWebhookSubscriptionsResponseModel>),
+        (status = 401, description = "Missing or invalid API key", body = ApiResponse<EmptyResponseModel>),
+        (status = 403, description = "API key does not have the webhooks:write scope", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+async fn get_subscriptions<T>(
+    State(webhook_subscription_use_case): State<Arc<WebhookSubscriptionUseCase<T>>>,
+    authenticated_service: AuthenticatedService,
+) -> impl IntoResponse
+where
+    T: WebhookSubscriptionRepository + Send + Sync,
+{
+    if let Err(e) = authenticated_service.require(ServiceScope::WebhooksWrite) {
+        return e.into_response();
+    }
+
+    match webhook_subscription_use_case
+        .get_subscriptions(authenticated_service.id)
+        .await
+    {
+        Ok(subscriptions) => (
+            StatusCode::OK,
+            Json(ApiResponse::<GetWebhookSubscriptionsResponseModel> {
+                data: Some(GetWebhookSubscriptionsResponseModel { subscriptions }),
+                message: None,
+                code: None,
+            }),
+        )
+            .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Subscribes a URL to booking events, of every doctor or only of the given
+/// ones. Each delivery is signed with the returned secret, which is not shown
+/// again. Requires the `webhooks:write` and `appointments:read` scopes.
+#[utoipa::path(
+    post,
+    path = "/",
+    tags = ["Webhooks"],
+    request_body = AddWebhookSubscriptionDto,
+    responses(
+        (status = 200, description = "Webhook subscription added successfully", body = ApiResponse<AddWebhookSubscriptionResponseModel>),
+        (status = 401, description = "Missing or invalid API key", body = ApiResponse<EmptyResponseModel>),
+        (status = 403, description = "API key does not have the webhooks:write or appointments:read scope", body = ApiResponse<EmptyResponseModel>),
+        (status = 422, description = "Invalid URL, event type or secret", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+async fn add<T>(
+    State(webhook_subscription_use_case): State<Arc<WebhookSubscriptionUseCase<T>>>,
+    authenticated_service: AuthenticatedService,
+    Json(add_webhook_subscription_dto): Json<AddWebhookSubscriptionDto>,
+) -> impl IntoResponse
+where
+    T: WebhookSubscriptionRepository + Send + Sync,
+{
+    if let Err(e) = authenticated_service.require(ServiceScope::WebhooksWrite) {
+        return e.into_response();
+    }
+    // Events carry patient and appointment ids, which need the same scope as
+    // reading them from the API.
+    if let Err(e) = authenticated_service.require(ServiceScope::AppointmentsRead) {
+        return e.into_response();
+    }
+
+    match webhook_subscription_use_case
+        .add(authenticated_service.id, add_webhook_subscription_dto)
+        .await
+    {
+        Ok(result) => {
+            let response = format!(
+                "Add webhook subscription success with id: {}",
+                result.subscription.id
+            );
+            (
+                StatusCode::OK,
+                Json(ApiResponse::<AddWebhookSubscriptionResponseModel> {
+                    data: Some(result),
+                    message: Some(response),
+                    code: None,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Edits a webhook subscription, or enables or disables it. Requires the
+/// `webhooks:write` and `appointments:read` scopes.
+#[utoipa::path(
+    patch,
+    path = "/{subscription_id}",
+    tags = ["Webhooks"],
+    params(
+        ("subscription_id" = Uuid, Path, description = "Webhook subscription ID to edit")
+    ),
+    request_body = EditWebhookSubscriptionDto,
+    responses(
+        (status = 200, description = "Webhook subscription edited successfully", body = ApiResponse<WebhookSubscriptionModel>),
+        (status = 401, description = "Missing or invalid API key", body = ApiResponse<EmptyResponseModel>),
+        (status = 403, description = "API key does not have the webhooks:write or appointments:read scope", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Webhook subscription not found", body = ApiResponse<EmptyResponseModel>),
+        (status = 422, description = "Invalid URL or event type", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+async fn edit<T>(
+    State(webhook_subscription_use_case): State<Arc<WebhookSubscriptionUseCase<T>>>,
+    authenticated_service: AuthenticatedService,
+    Path(subscription_id): Path<Uuid>,
+    Json(edit_webhook_subscription_dto): Json<EditWebhookSubscriptionDto>,
+) -> impl IntoResponse
+where
+    T: WebhookSubscriptionRepository + Send + Sync,
+{
+    if let Err(e) = authenticated_service.require(ServiceScope::WebhooksWrite) {
+        return e.into_response();
+    }
+    if let Err(e) = authenticated_service.require(ServiceScope::AppointmentsRead) {
+        return e.into_response();
+    }
+
+    match webhook_subscription_use_case
+        .edit(
+            subscription_id,
+            authenticated_service.id,
+            edit_webhook_subscription_dto,
+        )
+        .await
+    {
+        Ok(result) => {
+            let response = format!(
+                "Edit webhook subscription success with id: {}",
+                subscription_id
+            );
+            (
+                StatusCode::OK,
+                Json(ApiResponse::<WebhookSubscriptionModel> {
+                    data: Some(result),
+                    message: Some(response),
+                    code: None,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Removes a webhook subscription. Its pending deliveries are not sent.
+/// Requires the `webhooks:write` scope.
+#[utoipa::path(
+    delete,
+    path = "/{subscription_id}",
+    tags = ["Webhooks"],
+    params(
+        ("subscription_id" = Uuid, Path, description = "Webhook subscription ID to remove")
+    ),
+    responses(
+        (status = 200, description = "Webhook subscription removed successfully", body = ApiResponse<EmptyResponseModel>),
+        (status = 401, description = "Missing or invalid API key", body = ApiResponse<EmptyResponseModel>),
+        (status = 403, description = "API key does not have the webhooks:write scope", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Webhook subscription not found", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+async fn remove<T>(
+    State(webhook_subscription_use_case): State<Arc<WebhookSubscriptionUseCase<T>>>,
+    authenticated_service: AuthenticatedService,
+    Path(subscription_id): Path<Uuid>,
+) -> impl IntoResponse
+where
+    T: WebhookSubscriptionRepository + Send + Sync,
+{
+    if let Err(e) = authenticated_service.require(ServiceScope::WebhooksWrite) {
+        return e.into_response();
+    }
+
+    match webhook_subscription_use_case
+        .remove(subscription_id, authenticated_service.id)
+        .await
+    {
+        Ok(()) => {
+            let response = format!(
+                "Remove webhook subscription success with id: {}",
+                subscription_id
+            );
+            (
+                StatusCode::OK,
+                Json(ApiResponse::<EmptyResponseModel> {
+                    data: None,
+                    message: Some(response),
+                    code: None,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Retrieves the delivery log of a webhook subscription, most recent first.
+/// Requires the `webhooks:write` scope.
+#[utoipa::path(
+    get,
+    path = "/{subscription_id}/deliveries",
+    tags = ["Webhooks"],
+    params(
+        ("subscription_id" = Uuid, Path, description = "Webhook subscription ID to get the deliveries of"),
+        GetWebhookDeliveriesQuery
+    ),
+    responses(
+        (status = 200, description = "Fetched webhook deliveries successfully", body = ApiResponse<GetWebhookDeliveriesResponseModel>),
+        (status = 401, description = "Missing or invalid API key", body = ApiResponse<EmptyResponseModel>),
+        (status = 403, description = "API key does not have the webhooks:write scope", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Webhook subscription not found", body = ApiResponse<EmptyResponseModel>),
+        (status = 422, description = "Invalid limit", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+async fn get_deliveries<T>(
+    State(webhook_subscription_use_case): State<Arc<WebhookSubscriptionUseCase<T>>>,
+    authenticated_service: AuthenticatedService,
+    Path(subscription_id): Path<Uuid>,
+    Query(get_webhook_deliveries_query): Query<GetWebhookDeliveriesQuery>,
+) -> impl IntoResponse
+where
+    T: WebhookSubscriptionRepository + Send + Sync,
+{
+    if let Err(e) = authenticated_service.require(ServiceScope::WebhooksWrite) {
+        return e.into_response();
+    }
+
+    match webhook_subscription_use_case
+        .get_deliveries(
+            subscription_id,
+            authenticated_service.id,
+            get_webhook_deliveries_query,
+        )
+        .await
+    {
+        Ok(deliveries) => (
+            StatusCode::OK,
+            Json(ApiResponse::<GetWebhookDeliveriesResponseModel> {
+                data: Some(GetWebhookDeliveriesResponseModel { deliveries }),
+                message: None,
+                code: None,
+            }),
+        )
+            .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Sends a delivery again with a fresh set of attempts, whatever its status.
+/// Requires the `webhooks:write` scope.
+#[utoipa::path(
+    post,
+    path = "/deliveries/{delivery_id}/redeliver",
+    tags = ["Webhooks"],
+    params(
+        ("delivery_id" = Uuid, Path, description = "Webhook delivery ID to send again")
+    ),
+    responses(
+        (status = 200, description = "Webhook delivery scheduled successfully", body = ApiResponse<WebhookDeliveryModel>),
+        (status = 401, description = "Missing or invalid API key", body = ApiResponse<EmptyResponseModel>),
+        (status = 403, description = "API key does not have the webhooks:write scope", body = ApiResponse<EmptyResponseModel>),
+        (status = 404, description = "Webhook delivery not found", body = ApiResponse<EmptyResponseModel>),
+        (status = 409, description = "Webhook subscription is disabled", body = ApiResponse<EmptyResponseModel>)
+    )
+)]
+async fn redeliver<T>(
+    State(webhook_subscription_use_case): State<Arc<WebhookSubscriptionUseCase<T>>>,
+    authenticated_service: AuthenticatedService,
+    Path(delivery_id): Path<Uuid>,
+) -> impl IntoResponse
+where
+    T: WebhookSubscriptionRepository + Send + Sync,
+{
+    if let Err(e) = authenticated_service.require(ServiceScope::WebhooksWrite) {
+        return e.into_response();
+    }
+
+    match webhook_subscription_use_case
+        .redeliver(delivery_id, authenticated_service.id)
+        .await
+    {
+        Ok(result) => {
+            let response = format!("Redeliver webhook success with id: {}", delivery_id);
+            (
+                StatusCode::OK,
+                Json(ApiResponse::<WebhookDeliveryModel> {
+                    data: Some(result),
+                    message: Some(response),
+                    code: None,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
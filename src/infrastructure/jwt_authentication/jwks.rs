@@ -0,0 +1,190 @@
+use std::{
+    fmt,
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use jsonwebtoken::{
+    Algorithm, DecodingKey, decode, decode_header,
+    jwk::{Jwk, JwkSet},
+};
+use tokio::sync::RwLock;
+use tracing::warn;
+
+use crate::config::config_model::{JwksSource, JwtVerification};
+
+use super::{jwt_model::Claims, validation};
+
+/// Algorithms accepted for tokens verified against the JWKS.
+const SUPPORTED_ALGORITHMS: [Algorithm; 2] = [Algorithm::RS256, Algorithm::ES256];
+
+/// Shortest time between two fetches triggered by a token signed with a key
+/// that is not in the cached set yet.
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+const FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+static KEY_STORE: OnceLock<JwksKeyStore> = OnceLock::new();
+
+/// Why a token could not be matched to a key of the JWKS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JwksError {
+    MissingKeyId,
+    UnknownKeyId(String),
+    UnsupportedAlgorithm(Algorithm),
+}
+
+impl fmt::Display for JwksError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JwksError::MissingKeyId => write!(f, "Token has no `kid` header"),
+            JwksError::UnknownKeyId(kid) => write!(f, "No key with `kid` {}", kid),
+            JwksError::UnsupportedAlgorithm(algorithm) => {
+                write!(f, "Unsupported algorithm {:?}", algorithm)
+            }
+        }
+    }
+}
+
+impl std::error::Error for JwksError {}
+
+struct CachedJwkSet {
+    jwk_set: JwkSet,
+    /// Last time the source was read, successfully or not.
+    refreshed_at: Instant,
+}
+
+/// Public keys read from a JWKS file or URL, kept for `cache_ttl`.
+///
+/// Several keys can be active at once, so the auth service can rotate by
+/// publishing the new key before signing with it.
+pub struct JwksKeyStore {
+    source: JwksSource,
+    cache_ttl: Duration,
+    http_client: reqwest::Client,
+    cache: RwLock<Option<CachedJwkSet>>,
+}
+
+impl JwksKeyStore {
+    pub fn new(source: JwksSource, cache_ttl: Duration) -> Self {
+        Self {
+            source,
+            cache_ttl,
+            http_client: reqwest::Client::builder()
+                .timeout(FETCH_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+            cache: RwLock::new(None),
+        }
+    }
+
+    /// The process wide store of `source`, created on first use.
+    pub fn shared(source: &JwksSource, cache_ttl: Duration) -> &'static Self {
+        KEY_STORE.get_or_init(|| Self::new(source.clone(), cache_ttl))
+    }
+
+    /// Reads the key set now, failing when it cannot be loaded.
+    pub async fn refresh(&self) -> Result<usize> {
+        let jwk_set = self.load().await?;
+        let key_count = jwk_set.keys.len();
+
+        *self.cache.write().await = Some(CachedJwkSet {
+            jwk_set,
+            refreshed_at: Instant::now(),
+        });
+
+        Ok(key_count)
+    }
+
+    async fn find(&self, kid: &str) -> Result<Option<Jwk>> {
+        let refresh_interval = MIN_REFRESH_INTERVAL.min(self.cache_ttl);
+        {
+            let cache = self.cache.read().await;
+            if let Some(cached) = cache.as_ref() {
+                let age = cached.refreshed_at.elapsed();
+                match cached.jwk_set.find(kid) {
+                    Some(jwk) if age < self.cache_ttl => return Ok(Some(jwk.clone())),
+                    None if age < refresh_interval => return Ok(None),
+                    _ => {}
+                }
+            }
+        }
+
+        let mut cache = self.cache.write().await;
+        // Another request may have refreshed it while this one waited for the lock.
+        if let Some(cached) = cache.as_ref()
+            && cached.refreshed_at.elapsed() < refresh_interval
+        {
+            return Ok(cached.jwk_set.find(kid).cloned());
+        }
+
+        match self.load().await {
+            Ok(jwk_set) => {
+                *cache = Some(CachedJwkSet {
+                    jwk_set,
+                    refreshed_at: Instant::now(),
+                });
+            }
+            Err(e) => match cache.as_mut() {
+                Some(cached) => {
+                    warn!("Failed to refresh JWKS, keeping the cached keys: {e}");
+                    cached.refreshed_at = Instant::now();
+                }
+                None => return Err(e),
+            },
+        }
+
+        Ok(cache
+            .as_ref()
+            .and_then(|cached| cached.jwk_set.find(kid))
+            .cloned())
+    }
+
+    async fn load(&self) -> Result<JwkSet> {
+        let body = match &self.source {
+            JwksSource::File(path) => tokio::fs::read_to_string(path).await?,
+            JwksSource::Url(url) => {
+                self.http_client
+                    .get(url)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .text()
+                    .await?
+            }
+        };
+
+        Ok(serde_json::from_str::<JwkSet>(&body)?)
+    }
+}
+
+/// Verifies an RS256 or ES256 token against the key its `kid` header names.
+pub async fn verify_token(
+    key_store: &JwksKeyStore,
+    token: String,
+    jwt_verification: &JwtVerification,
+) -> Result<Claims> {
+    let header = decode_header(&token)?;
+    if !SUPPORTED_ALGORITHMS.contains(&header.alg) {
+        return Err(JwksError::UnsupportedAlgorithm(header.alg).into());
+    }
+    let kid = header.kid.ok_or(JwksError::MissingKeyId)?;
+
+    let jwk = key_store
+        .find(&kid)
+        .await?
+        .ok_or_else(|| JwksError::UnknownKeyId(kid.clone()))?;
+    if let Some(key_algorithm) = jwk.common.key_algorithm
+        && key_algorithm.to_string().parse::<Algorithm>().ok() != Some(header.alg)
+    {
+        return Err(JwksError::UnsupportedAlgorithm(header.alg).into());
+    }
+
+    let result = decode::<Claims>(
+        &token,
+        &DecodingKey::from_jwk(&jwk)?,
+        &validation(header.alg, jwt_verification),
+    )?;
+    Ok(result.claims)
+}
@@ -14,8 +14,11 @@ pub struct Claims {
     pub iat: usize,
 }
 
-#[derive(Debug,Clone,Serialize,Deserialize,PartialEq)]
+#[derive(Debug,Clone,Copy,Serialize,Deserialize,PartialEq,Eq)]
 pub enum Roles {
     Patient,
-    Doctor
+    Doctor,
+    /// Clinic front desk and administrators, who act on behalf of any patient
+    /// or doctor.
+    Staff
 }
\ No newline at end of file
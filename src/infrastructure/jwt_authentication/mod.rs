@@ -1,9 +1,12 @@
-mod jwt_model;
+pub mod jwks;
+pub mod jwt_model;
 
 use anyhow::Result;
-use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
 use jwt_model::Claims;
 
+use crate::config::config_model::JwtVerification;
+
 pub fn generate_token(secret: String, claims: &Claims) -> Result<String> {
     let token = encode(
         &Header::default(),
@@ -13,11 +16,34 @@ pub fn generate_token(secret: String, claims: &Claims) -> Result<String> {
     Ok(token)
 }
 
-pub fn verify_token(secret: String, token: String) -> Result<Claims> {
+pub fn verify_token(
+    secret: String,
+    token: String,
+    jwt_verification: &JwtVerification,
+) -> Result<Claims> {
     let result = decode(
         &token,
         &DecodingKey::from_secret(secret.as_ref()),
-        &Validation::default(),
+        &validation(Algorithm::HS256, jwt_verification),
     )?;
     Ok(result.claims)
 }
+
+/// Validation of `algorithm` tokens that also requires the configured `iss`
+/// and `aud`, if any.
+fn validation(algorithm: Algorithm, jwt_verification: &JwtVerification) -> Validation {
+    let mut validation = Validation::new(algorithm);
+    let mut required_spec_claims = vec!["exp"];
+
+    if let Some(issuer) = &jwt_verification.issuer {
+        validation.set_issuer(&[issuer]);
+        required_spec_claims.push("iss");
+    }
+    if let Some(audience) = &jwt_verification.audience {
+        validation.set_audience(&[audience]);
+        required_spec_claims.push("aud");
+    }
+    validation.set_required_spec_claims(&required_spec_claims);
+
+    validation
+}
@@ -1,3 +1,5 @@
 pub mod postgres;
 pub mod axum_http;
-pub mod jwt_authentication;
\ No newline at end of file
+pub mod jwt_authentication;
+pub mod outbox;
+pub mod reminder;
\ No newline at end of file
@@ -0,0 +1,116 @@
+use std::{sync::Arc, time::Duration};
+
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use crate::{
+    application::usecases::outbox_dispatch::OutboxDispatchUseCase,
+    config::config_model::{OutboxDispatch, OutboxSinkKind},
+    domain::repositories::outbox::{OutboxRepository, OutboxSink},
+    infrastructure::{
+        outbox::{log_sink::LogOutboxSink, webhook_sink::WebhookOutboxSink},
+        postgres::{
+            postgres_connection::PgPoolSquad,
+            repositories::{
+                outbox::{OutboxPostgres, PgNotifyOutboxSink},
+                webhook::WebhookFanOutSink,
+            },
+        },
+    },
+};
+
+/// Starts delivering outbox events to the configured sink in the background.
+/// Every event is also recorded for the webhook subscriptions that receive it.
+pub fn spawn(outbox_dispatch: OutboxDispatch, db_pool: Arc<PgPoolSquad>) -> JoinHandle<()> {
+    let outbox_repository = Arc::new(OutboxPostgres::new(Arc::clone(&db_pool)));
+    let poll_interval = Duration::from_millis(outbox_dispatch.poll_interval_ms);
+
+    info!("Outbox events are dispatched to {:?}", outbox_dispatch.sink);
+
+    match outbox_dispatch.sink.clone() {
+        OutboxSinkKind::Log => tokio::spawn(run(
+            use_case(
+                &outbox_dispatch,
+                outbox_repository,
+                WebhookFanOutSink::new(Arc::clone(&db_pool), LogOutboxSink),
+            ),
+            poll_interval,
+        )),
+        OutboxSinkKind::Webhook {
+            url,
+            timeout_seconds,
+        } => tokio::spawn(run(
+            use_case(
+                &outbox_dispatch,
+                outbox_repository,
+                WebhookFanOutSink::new(
+                    Arc::clone(&db_pool),
+                    WebhookOutboxSink::new(url, Duration::from_secs(timeout_seconds)),
+                ),
+            ),
+            poll_interval,
+        )),
+        OutboxSinkKind::PgNotify { channel } => tokio::spawn(run(
+            use_case(
+                &outbox_dispatch,
+                outbox_repository,
+                WebhookFanOutSink::new(
+                    Arc::clone(&db_pool),
+                    PgNotifyOutboxSink::new(Arc::clone(&db_pool), channel),
+                ),
+            ),
+            poll_interval,
+        )),
+    }
+}
+
+fn use_case<T, S>(
+    outbox_dispatch: &OutboxDispatch,
+    outbox_repository: Arc<T>,
+    outbox_sink: S,
+) -> OutboxDispatchUseCase<T, S>
+where
+    T: OutboxRepository + Send + Sync,
+    S: OutboxSink + Send + Sync,
+{
+    OutboxDispatchUseCase::new(
+        outbox_repository,
+        Arc::new(outbox_sink),
+        outbox_dispatch.retry_policy,
+        outbox_dispatch.batch_size,
+        outbox_dispatch.lease_seconds,
+    )
+}
+
+/// Polls for due events forever. A full batch is followed by the next one
+/// right away, so a backlog drains without waiting for the poll interval.
+async fn run<T, S>(outbox_dispatch_use_case: OutboxDispatchUseCase<T, S>, poll_interval: Duration)
+where
+    T: OutboxRepository + Send + Sync,
+    S: OutboxSink + Send + Sync,
+{
+    loop {
+        let batch_was_full = match outbox_dispatch_use_case.dispatch_due().await {
+            Ok(report) => {
+                if report.retried > 0 {
+                    warn!(
+                        "Outbox: {} events dispatched, {} failed and will be retried",
+                        report.dispatched, report.retried
+                    );
+                }
+                for id in &report.failed {
+                    error!("Outbox event {id} was given up on after its last attempt");
+                }
+                outbox_dispatch_use_case.is_full_batch(&report)
+            }
+            Err(e) => {
+                error!("Failed to dispatch outbox events: {e}");
+                false
+            }
+        };
+
+        if !batch_was_full {
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+}
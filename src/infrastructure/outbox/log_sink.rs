@@ -0,0 +1,24 @@
+use tracing::info;
+
+use crate::domain::{
+    errors::DomainResult, repositories::outbox::OutboxSink,
+    value_objects::outbox_model::OutboxMessage,
+};
+
+/// Writes outbox events to the service log. Never fails, so every event is
+/// dispatched on its first attempt.
+pub struct LogOutboxSink;
+
+impl OutboxSink for LogOutboxSink {
+    async fn deliver(&self, outbox_message: &OutboxMessage) -> DomainResult<()> {
+        info!(
+            "Outbox event {} {} of {}: {}",
+            outbox_message.id,
+            outbox_message.event_type,
+            outbox_message.aggregate_id,
+            outbox_message.payload
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+pub mod dispatcher;
+pub mod log_sink;
+pub mod webhook_dispatcher;
+pub mod webhook_sender;
+pub mod webhook_sink;
@@ -0,0 +1,68 @@
+use std::{sync::Arc, time::Duration};
+
+use tokio::task::JoinHandle;
+use tracing::{error, warn};
+
+use crate::{
+    application::usecases::webhook_dispatch::WebhookDispatchUseCase,
+    config::config_model::WebhookDispatch,
+    infrastructure::{
+        outbox::webhook_sender::HttpWebhookSender,
+        postgres::{
+            postgres_connection::PgPoolSquad, repositories::webhook::WebhookDeliveryPostgres,
+        },
+    },
+};
+
+/// Starts sending webhook deliveries to their subscribers in the background.
+pub fn spawn(webhook_dispatch: WebhookDispatch, db_pool: Arc<PgPoolSquad>) -> JoinHandle<()> {
+    let webhook_dispatch_use_case = WebhookDispatchUseCase::new(
+        Arc::new(WebhookDeliveryPostgres::new(db_pool)),
+        Arc::new(HttpWebhookSender::new(Duration::from_secs(
+            webhook_dispatch.timeout_seconds,
+        ))),
+        webhook_dispatch.retry_policy,
+        webhook_dispatch.disable_after_failures,
+        webhook_dispatch.batch_size,
+        webhook_dispatch.lease_seconds,
+    );
+
+    tokio::spawn(run(
+        webhook_dispatch_use_case,
+        Duration::from_millis(webhook_dispatch.poll_interval_ms),
+    ))
+}
+
+/// Polls for due deliveries forever, like the outbox dispatcher.
+async fn run(
+    webhook_dispatch_use_case: WebhookDispatchUseCase<WebhookDeliveryPostgres, HttpWebhookSender>,
+    poll_interval: Duration,
+) {
+    loop {
+        let batch_was_full = match webhook_dispatch_use_case.dispatch_due().await {
+            Ok(report) => {
+                if report.retried > 0 {
+                    warn!(
+                        "Webhooks: {} deliveries sent, {} failed and will be retried",
+                        report.delivered, report.retried
+                    );
+                }
+                for id in &report.failed {
+                    error!("Webhook delivery {id} was given up on after its last attempt");
+                }
+                for id in &report.disabled_subscriptions {
+                    error!("Webhook subscription {id} was disabled after repeated failures");
+                }
+                webhook_dispatch_use_case.is_full_batch(&report)
+            }
+            Err(e) => {
+                error!("Failed to send webhook deliveries: {e}");
+                false
+            }
+        };
+
+        if !batch_was_full {
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+}
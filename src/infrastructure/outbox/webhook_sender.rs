@@ -0,0 +1,109 @@
+use std::{sync::Arc, time::Duration};
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+
+use crate::domain::{
+    repositories::webhook::WebhookSender,
+    value_objects::webhook_model::{
+        WebhookAttemptModel, WebhookRequestModel, is_public_ip, validate_public_host,
+    },
+};
+
+/// Longest part of a subscriber's response body kept in the delivery log.
+const MAX_LOGGED_RESPONSE_LENGTH: usize = 512;
+
+/// Resolves subscriber hosts with the system resolver, leaving out addresses
+/// that are not public, so a name cannot be pointed at the internal network
+/// after its subscription was accepted.
+struct PublicAddressResolver;
+
+impl Resolve for PublicAddressResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addrs = tokio::net::lookup_host((name.as_str(), 0))
+                .await?
+                .filter(|addr| is_public_ip(addr.ip()))
+                .collect::<Vec<_>>();
+            if addrs.is_empty() {
+                return Err(format!("{} has no public address", name.as_str()).into());
+            }
+
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// POSTs each delivery to its subscription's URL with the signature headers.
+/// Any response other than 2xx is a failed attempt.
+pub struct HttpWebhookSender {
+    http_client: reqwest::Client,
+}
+
+impl HttpWebhookSender {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            http_client: reqwest::Client::builder()
+                .timeout(timeout)
+                .redirect(reqwest::redirect::Policy::none())
+                .dns_resolver(Arc::new(PublicAddressResolver))
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl WebhookSender for HttpWebhookSender {
+    async fn send(&self, webhook_request: &WebhookRequestModel) -> WebhookAttemptModel {
+        // Subscriptions made before hosts were checked may still point inside.
+        if let Err(e) = validate_public_host(&webhook_request.url) {
+            return WebhookAttemptModel {
+                response_status: None,
+                error: Some(format!("Not sent to {}: {}", webhook_request.url, e)),
+            };
+        }
+
+        let response = self
+            .http_client
+            .post(&webhook_request.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(
+                "X-MedBook-Webhook-Id",
+                webhook_request.delivery_id.to_string(),
+            )
+            .header("X-MedBook-Event", &webhook_request.event_type)
+            .header("X-MedBook-Event-Id", webhook_request.event_id.to_string())
+            .header("X-MedBook-Timestamp", webhook_request.timestamp.to_string())
+            .header("X-MedBook-Signature", &webhook_request.signature)
+            .body(webhook_request.body.clone())
+            .send()
+            .await;
+
+        let response = match response {
+            Ok(response) => response,
+            Err(e) => {
+                return WebhookAttemptModel {
+                    response_status: None,
+                    error: Some(format!("POST {} failed: {}", webhook_request.url, e)),
+                };
+            }
+        };
+
+        let status = response.status();
+        if status.is_success() {
+            return WebhookAttemptModel {
+                response_status: Some(status.as_u16().into()),
+                error: None,
+            };
+        }
+
+        let body = response.text().await.unwrap_or_default();
+        let body = body
+            .chars()
+            .take(MAX_LOGGED_RESPONSE_LENGTH)
+            .collect::<String>();
+        WebhookAttemptModel {
+            response_status: Some(status.as_u16().into()),
+            error: Some(format!("Answered {}: {}", status, body)),
+        }
+    }
+}
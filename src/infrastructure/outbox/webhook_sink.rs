@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use anyhow::anyhow;
+
+use crate::domain::{
+    errors::{DomainError, DomainResult},
+    repositories::outbox::OutboxSink,
+    value_objects::outbox_model::OutboxMessage,
+};
+
+/// POSTs each outbox event as JSON to one URL. Any response other than 2xx
+/// is a failed delivery.
+pub struct WebhookOutboxSink {
+    url: String,
+    http_client: reqwest::Client,
+}
+
+impl WebhookOutboxSink {
+    pub fn new(url: String, timeout: Duration) -> Self {
+        Self {
+            url,
+            http_client: reqwest::Client::builder()
+                .timeout(timeout)
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl OutboxSink for WebhookOutboxSink {
+    async fn deliver(&self, outbox_message: &OutboxMessage) -> DomainResult<()> {
+        let body =
+            serde_json::to_vec(outbox_message).map_err(|e| DomainError::Internal(e.into()))?;
+
+        let response = self
+            .http_client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header("X-MedBook-Event", &outbox_message.event_type)
+            .header("X-MedBook-Event-Id", outbox_message.id.to_string())
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| anyhow!("POST {} failed: {}", self.url, e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(anyhow!("POST {} answered {}", self.url, status).into());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_slots_active_doctor_id_start_time;
+DROP INDEX IF EXISTS idx_slots_active_start_time;
@@ -0,0 +1,8 @@
+-- Your SQL goes here
+CREATE INDEX idx_slots_active_start_time ON slots (start_time)
+WHERE
+    deleted_at IS NULL;
+
+CREATE INDEX idx_slots_active_doctor_id_start_time ON slots (doctor_id, start_time)
+WHERE
+    deleted_at IS NULL;
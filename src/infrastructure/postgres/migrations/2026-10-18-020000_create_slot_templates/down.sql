@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE slots
+DROP COLUMN IF EXISTS template_id;
+
+DROP TABLE IF EXISTS slot_templates;
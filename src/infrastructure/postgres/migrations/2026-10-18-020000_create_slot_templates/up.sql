@@ -0,0 +1,24 @@
+-- Your SQL goes here
+CREATE TABLE
+    slot_templates (
+        id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+        doctor_id INTEGER NOT NULL,
+        weekdays VARCHAR(27) NOT NULL,
+        day_start_time TIME NOT NULL,
+        day_end_time TIME NOT NULL,
+        slot_duration_minutes INTEGER NOT NULL,
+        max_appointment_count INTEGER NOT NULL,
+        start_date DATE NOT NULL,
+        end_date DATE NOT NULL,
+        created_at TIMESTAMP NOT NULL DEFAULT now (),
+        updated_at TIMESTAMP NOT NULL DEFAULT now (),
+        deleted_at TIMESTAMP
+    );
+
+ALTER TABLE slots
+ADD COLUMN template_id UUID,
+ADD CONSTRAINT fk_slots_slot_template FOREIGN KEY (template_id) REFERENCES slot_templates (id);
+
+CREATE INDEX idx_slot_templates_doctor_id ON slot_templates (doctor_id);
+
+CREATE INDEX idx_slots_template_id ON slots (template_id);
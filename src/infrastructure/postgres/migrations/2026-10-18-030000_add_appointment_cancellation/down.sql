@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE appointments
+DROP COLUMN IF EXISTS cancelled_at,
+DROP COLUMN IF EXISTS cancellation_reason;
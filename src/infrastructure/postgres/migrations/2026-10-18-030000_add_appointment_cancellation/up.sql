@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TABLE appointments
+ADD COLUMN cancellation_reason VARCHAR(500),
+ADD COLUMN cancelled_at TIMESTAMP;
@@ -0,0 +1,16 @@
+-- Your SQL goes here
+CREATE TABLE
+    appointment_status_history (
+        id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+        appointment_id UUID NOT NULL,
+        action VARCHAR(50) NOT NULL,
+        previous_status VARCHAR(50),
+        new_status VARCHAR(50) NOT NULL,
+        actor_role VARCHAR(50) NOT NULL,
+        actor_id INTEGER NOT NULL,
+        note VARCHAR(500),
+        created_at TIMESTAMP NOT NULL DEFAULT now (),
+        CONSTRAINT fk_appointment_status_history_appointment FOREIGN KEY (appointment_id) REFERENCES appointments (id) ON DELETE CASCADE
+    );
+
+CREATE INDEX idx_appointment_status_history_appointment_id ON appointment_status_history (appointment_id, created_at);
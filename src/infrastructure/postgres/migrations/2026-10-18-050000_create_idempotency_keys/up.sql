@@ -0,0 +1,12 @@
+-- Your SQL goes here
+CREATE TABLE
+    idempotency_keys (
+        id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+        patient_id INTEGER NOT NULL,
+        operation VARCHAR(50) NOT NULL,
+        idempotency_key VARCHAR(255) NOT NULL,
+        request_hash VARCHAR(64) NOT NULL,
+        response JSONB NOT NULL,
+        created_at TIMESTAMP NOT NULL DEFAULT now (),
+        CONSTRAINT uq_idempotency_keys_patient_operation_key UNIQUE (patient_id, operation, idempotency_key)
+    );
@@ -0,0 +1,19 @@
+-- Your SQL goes here
+CREATE TABLE
+    waitlist_entries (
+        id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+        slot_id UUID NOT NULL,
+        patient_id INTEGER NOT NULL,
+        patient_abnormal_symptom VARCHAR(255) NOT NULL,
+        patient_is_missed_medication VARCHAR(255) NOT NULL,
+        patient_blood_test_status VARCHAR(255) NOT NULL,
+        patient_is_overdue_medication VARCHAR(255) NOT NULL,
+        patient_is_partner_hiv_positive VARCHAR(255) NOT NULL,
+        created_at TIMESTAMP NOT NULL DEFAULT now (),
+        CONSTRAINT fk_waitlist_entries_slot FOREIGN KEY (slot_id) REFERENCES slots (id) ON DELETE CASCADE,
+        CONSTRAINT uq_waitlist_entries_slot_patient UNIQUE (slot_id, patient_id)
+    );
+
+CREATE INDEX idx_waitlist_entries_slot_id ON waitlist_entries (slot_id, created_at);
+
+CREATE INDEX idx_waitlist_entries_patient_id ON waitlist_entries (patient_id);
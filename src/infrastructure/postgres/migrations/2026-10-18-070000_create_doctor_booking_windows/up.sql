@@ -0,0 +1,13 @@
+-- Your SQL goes here
+CREATE TABLE
+    doctor_booking_windows (
+        doctor_id INTEGER PRIMARY KEY,
+        min_booking_lead_minutes INTEGER,
+        max_booking_horizon_days INTEGER,
+        min_change_notice_minutes INTEGER,
+        created_at TIMESTAMP NOT NULL DEFAULT now (),
+        updated_at TIMESTAMP NOT NULL DEFAULT now (),
+        CONSTRAINT chk_doctor_booking_windows_min_booking_lead_minutes CHECK (min_booking_lead_minutes >= 0),
+        CONSTRAINT chk_doctor_booking_windows_max_booking_horizon_days CHECK (max_booking_horizon_days >= 1),
+        CONSTRAINT chk_doctor_booking_windows_min_change_notice_minutes CHECK (min_change_notice_minutes >= 0)
+    );
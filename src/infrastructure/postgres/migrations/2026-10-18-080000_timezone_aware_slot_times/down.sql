@@ -0,0 +1,12 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS doctor_timezones;
+
+ALTER TABLE slots
+ALTER COLUMN start_time TYPE TIMESTAMP USING start_time AT TIME ZONE COALESCE(
+    NULLIF(current_setting('medbook.legacy_slot_timezone', TRUE), ''),
+    'Asia/Bangkok'
+),
+ALTER COLUMN end_time TYPE TIMESTAMP USING end_time AT TIME ZONE COALESCE(
+    NULLIF(current_setting('medbook.legacy_slot_timezone', TRUE), ''),
+    'Asia/Bangkok'
+);
@@ -0,0 +1,45 @@
+-- Your SQL goes here
+-- Existing slot times are wall-clock times of the clinic, entered before slots
+-- knew about time zones. They are read in `medbook.legacy_slot_timezone`, or
+-- Asia/Bangkok when it is not set, e.g. for slots entered as UTC:
+--   ALTER DATABASE booking SET medbook.legacy_slot_timezone = 'UTC';
+ALTER TABLE slots
+ALTER COLUMN start_time TYPE TIMESTAMPTZ USING start_time AT TIME ZONE COALESCE(
+    NULLIF(current_setting('medbook.legacy_slot_timezone', TRUE), ''),
+    'Asia/Bangkok'
+),
+ALTER COLUMN end_time TYPE TIMESTAMPTZ USING end_time AT TIME ZONE COALESCE(
+    NULLIF(current_setting('medbook.legacy_slot_timezone', TRUE), ''),
+    'Asia/Bangkok'
+);
+
+CREATE TABLE
+    doctor_timezones (
+        doctor_id INTEGER PRIMARY KEY,
+        timezone VARCHAR(64) NOT NULL,
+        created_at TIMESTAMP NOT NULL DEFAULT now (),
+        updated_at TIMESTAMP NOT NULL DEFAULT now ()
+    );
+
+-- Doctors who already have slots or templates keep reading times without an
+-- offset in the same zone, rather than falling back to UTC.
+INSERT INTO
+    doctor_timezones (doctor_id, timezone)
+SELECT DISTINCT
+    doctor_id,
+    COALESCE(
+        NULLIF(current_setting('medbook.legacy_slot_timezone', TRUE), ''),
+        'Asia/Bangkok'
+    )
+FROM
+    (
+        SELECT
+            doctor_id
+        FROM
+            slots
+        UNION
+        SELECT
+            doctor_id
+        FROM
+            slot_templates
+    ) AS legacy_doctors;
@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE slots
+DROP CONSTRAINT IF EXISTS excl_slots_doctor_id_time_overlap,
+DROP CONSTRAINT IF EXISTS chk_slots_current_appointment_count_within_max,
+DROP CONSTRAINT IF EXISTS chk_slots_current_appointment_count_not_negative,
+DROP CONSTRAINT IF EXISTS chk_slots_end_time_after_start_time;
@@ -0,0 +1,76 @@
+-- Your SQL goes here
+CREATE EXTENSION IF NOT EXISTS btree_gist;
+
+-- Rows written before these checks existed are not repaired here, the migration
+-- stops with the ids of every slot that breaks them. The README ("add slot")
+-- has the SQL to repair them by hand.
+DO $$
+DECLARE
+    negative_count_slot_ids TEXT;
+    overbooked_slot_ids TEXT;
+    inverted_slot_ids TEXT;
+    overlapping_slot_ids TEXT;
+    problems TEXT[] := ARRAY[]::TEXT[];
+BEGIN
+    SELECT string_agg(id::TEXT, ', ' ORDER BY id) INTO negative_count_slot_ids
+    FROM slots
+    WHERE current_appointment_count < 0;
+
+    SELECT string_agg(id::TEXT, ', ' ORDER BY id) INTO overbooked_slot_ids
+    FROM slots
+    WHERE current_appointment_count > max_appointment_count;
+
+    SELECT string_agg(id::TEXT, ', ' ORDER BY id) INTO inverted_slot_ids
+    FROM slots
+    WHERE deleted_at IS NULL
+        AND end_time <= start_time;
+
+    SELECT string_agg(id::TEXT, ', ' ORDER BY id) INTO overlapping_slot_ids
+    FROM slots
+    WHERE deleted_at IS NULL
+        AND end_time > start_time
+        AND EXISTS (
+            SELECT 1
+            FROM slots AS other
+            WHERE other.id <> slots.id
+                AND other.deleted_at IS NULL
+                AND other.doctor_id = slots.doctor_id
+                AND other.end_time > other.start_time
+                AND other.start_time < slots.end_time
+                AND slots.start_time < other.end_time
+        );
+
+    IF negative_count_slot_ids IS NOT NULL THEN
+        problems := problems || format('current_appointment_count is negative: %s', negative_count_slot_ids);
+    END IF;
+    IF overbooked_slot_ids IS NOT NULL THEN
+        problems := problems || format('current_appointment_count is above max_appointment_count: %s', overbooked_slot_ids);
+    END IF;
+    IF inverted_slot_ids IS NOT NULL THEN
+        problems := problems || format('active slots that do not end after they start: %s', inverted_slot_ids);
+    END IF;
+    IF overlapping_slot_ids IS NOT NULL THEN
+        problems := problems || format('active slots that overlap another slot of their doctor: %s', overlapping_slot_ids);
+    END IF;
+
+    IF cardinality(problems) > 0 THEN
+        RAISE EXCEPTION 'Slots break the constraints added by this migration, repair them and run it again. %', array_to_string(problems, '; ');
+    END IF;
+END;
+$$;
+
+-- Removed slots are left alone and may still not end after they start, GREATEST
+-- keeps the range of the overlap index valid for them.
+ALTER TABLE slots
+ADD CONSTRAINT chk_slots_end_time_after_start_time CHECK (
+    deleted_at IS NOT NULL
+    OR end_time > start_time
+),
+ADD CONSTRAINT chk_slots_current_appointment_count_not_negative CHECK (current_appointment_count >= 0),
+ADD CONSTRAINT chk_slots_current_appointment_count_within_max CHECK (current_appointment_count <= max_appointment_count),
+ADD CONSTRAINT excl_slots_doctor_id_time_overlap EXCLUDE USING gist (
+    doctor_id WITH =,
+    tstzrange (start_time, GREATEST (start_time, end_time)) WITH &&
+)
+WHERE
+    (deleted_at IS NULL);
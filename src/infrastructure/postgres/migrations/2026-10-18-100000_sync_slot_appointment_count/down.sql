@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+DROP TRIGGER IF EXISTS trg_appointments_sync_slot_appointment_count ON appointments;
+
+DROP FUNCTION IF EXISTS sync_slot_appointment_count ();
+
+DROP FUNCTION IF EXISTS appointment_holds_slot_place (VARCHAR, TIMESTAMP);
@@ -0,0 +1,70 @@
+-- Your SQL goes here
+-- Must stay in line with `AppointmentStatus::holds_slot_place`.
+CREATE FUNCTION appointment_holds_slot_place (status VARCHAR, deleted_at TIMESTAMP) RETURNS BOOLEAN LANGUAGE SQL IMMUTABLE AS $$
+    SELECT deleted_at IS NULL AND status NOT IN ('Cancelled', 'Rejected')
+$$;
+
+-- Keeps slots.current_appointment_count equal to the number of appointments
+-- holding a place in the slot. Applied as deltas so concurrent writers never
+-- overwrite each other's counts; chk_slots_current_appointment_count_within_max
+-- turns an overbooking into an error.
+CREATE FUNCTION sync_slot_appointment_count () RETURNS TRIGGER LANGUAGE plpgsql AS $$
+DECLARE
+    old_holds_place BOOLEAN := FALSE;
+    new_holds_place BOOLEAN := FALSE;
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+        old_holds_place := appointment_holds_slot_place (OLD.status, OLD.deleted_at);
+    END IF;
+    IF TG_OP IN ('INSERT', 'UPDATE') THEN
+        new_holds_place := appointment_holds_slot_place (NEW.status, NEW.deleted_at);
+    END IF;
+
+    IF TG_OP = 'UPDATE'
+        AND OLD.slot_id = NEW.slot_id
+        AND old_holds_place = new_holds_place THEN
+        RETURN NULL;
+    END IF;
+
+    IF old_holds_place THEN
+        UPDATE slots
+        SET current_appointment_count = current_appointment_count - 1
+        WHERE id = OLD.slot_id;
+    END IF;
+    IF new_holds_place THEN
+        UPDATE slots
+        SET current_appointment_count = current_appointment_count + 1
+        WHERE id = NEW.slot_id;
+    END IF;
+
+    RETURN NULL;
+END;
+$$;
+
+CREATE TRIGGER trg_appointments_sync_slot_appointment_count
+AFTER INSERT OR DELETE OR UPDATE OF slot_id, status, deleted_at ON appointments
+FOR EACH ROW
+EXECUTE FUNCTION sync_slot_appointment_count ();
+
+-- One-off resync of counts that drifted while they were maintained by hand.
+-- Slots holding more appointments than their capacity are left for
+-- `reconcile-slot-counts` to report.
+UPDATE slots
+SET
+    current_appointment_count = actual.appointment_count
+FROM
+    (
+        SELECT
+            slots.id,
+            COUNT(appointments.id)::INTEGER AS appointment_count
+        FROM
+            slots
+            LEFT JOIN appointments ON appointments.slot_id = slots.id
+            AND appointment_holds_slot_place (appointments.status, appointments.deleted_at)
+        GROUP BY
+            slots.id
+    ) AS actual
+WHERE
+    slots.id = actual.id
+    AND slots.current_appointment_count <> actual.appointment_count
+    AND actual.appointment_count <= slots.max_appointment_count;
@@ -0,0 +1,12 @@
+-- Your SQL goes here
+CREATE TABLE
+    service_api_keys (
+        id SERIAL PRIMARY KEY,
+        service_name VARCHAR(100) NOT NULL,
+        key_prefix VARCHAR(16) NOT NULL,
+        key_hash VARCHAR(255) NOT NULL,
+        scopes TEXT[] NOT NULL,
+        created_at TIMESTAMP NOT NULL DEFAULT now (),
+        revoked_at TIMESTAMP,
+        CONSTRAINT uq_service_api_keys_key_prefix UNIQUE (key_prefix)
+    );
@@ -0,0 +1,14 @@
+-- Your SQL goes here
+-- Audit trail of what clinic staff did on behalf of patients and doctors.
+-- `target_id` is an appointment or a slot, depending on `action`.
+CREATE TABLE
+    staff_actions (
+        id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+        staff_id INTEGER NOT NULL,
+        action VARCHAR(50) NOT NULL,
+        target_id UUID NOT NULL,
+        note VARCHAR(500),
+        created_at TIMESTAMP NOT NULL DEFAULT now ()
+    );
+
+CREATE INDEX idx_staff_actions_target_id ON staff_actions (target_id);
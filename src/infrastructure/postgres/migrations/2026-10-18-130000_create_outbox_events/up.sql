@@ -0,0 +1,23 @@
+-- Your SQL goes here
+-- Domain events written in the same transaction as the booking change they
+-- describe, then delivered at least once by the outbox dispatcher.
+CREATE TABLE
+    outbox_events (
+        id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+        event_type VARCHAR(50) NOT NULL,
+        aggregate_id UUID NOT NULL,
+        payload JSONB NOT NULL,
+        created_at TIMESTAMP NOT NULL DEFAULT now (),
+        attempts INTEGER NOT NULL DEFAULT 0,
+        next_attempt_at TIMESTAMP NOT NULL DEFAULT now (),
+        last_error TEXT,
+        dispatched_at TIMESTAMP,
+        failed_at TIMESTAMP
+    );
+
+CREATE INDEX idx_outbox_events_pending ON outbox_events (next_attempt_at)
+WHERE
+    dispatched_at IS NULL
+    AND failed_at IS NULL;
+
+CREATE INDEX idx_outbox_events_aggregate_id ON outbox_events (aggregate_id);
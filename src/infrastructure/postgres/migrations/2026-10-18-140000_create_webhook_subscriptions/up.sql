@@ -0,0 +1,43 @@
+-- Your SQL goes here
+-- Webhooks other services registered with their API key. An empty
+-- `event_types` receives every event. `secret` signs the deliveries, so it is
+-- kept as is.
+CREATE TABLE
+    webhook_subscriptions (
+        id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+        service_api_key_id INTEGER NOT NULL REFERENCES service_api_keys (id),
+        url VARCHAR(2048) NOT NULL,
+        event_types TEXT[] NOT NULL DEFAULT '{}',
+        secret VARCHAR(255) NOT NULL,
+        consecutive_failures INTEGER NOT NULL DEFAULT 0,
+        disabled_at TIMESTAMP,
+        created_at TIMESTAMP NOT NULL DEFAULT now (),
+        updated_at TIMESTAMP NOT NULL DEFAULT now (),
+        deleted_at TIMESTAMP
+    );
+
+CREATE INDEX idx_webhook_subscriptions_service_api_key_id ON webhook_subscriptions (service_api_key_id);
+
+-- One row per outbox event and subscription, kept as the delivery log.
+CREATE TABLE
+    webhook_deliveries (
+        id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+        subscription_id UUID NOT NULL REFERENCES webhook_subscriptions (id),
+        outbox_event_id UUID NOT NULL REFERENCES outbox_events (id),
+        event_type VARCHAR(50) NOT NULL,
+        body JSONB NOT NULL,
+        status VARCHAR(20) NOT NULL DEFAULT 'Pending',
+        attempts INTEGER NOT NULL DEFAULT 0,
+        next_attempt_at TIMESTAMP NOT NULL DEFAULT now (),
+        last_response_status INTEGER,
+        last_error TEXT,
+        created_at TIMESTAMP NOT NULL DEFAULT now (),
+        delivered_at TIMESTAMP,
+        CONSTRAINT uq_webhook_deliveries_subscription_event UNIQUE (subscription_id, outbox_event_id)
+    );
+
+CREATE INDEX idx_webhook_deliveries_pending ON webhook_deliveries (next_attempt_at)
+WHERE
+    status = 'Pending';
+
+CREATE INDEX idx_webhook_deliveries_subscription_id ON webhook_deliveries (subscription_id, created_at);
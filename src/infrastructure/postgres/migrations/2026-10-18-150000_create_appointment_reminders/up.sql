@@ -0,0 +1,14 @@
+-- Your SQL goes here
+-- One row per reminder the scheduler took on, written before the notifier is
+-- called so a restart never sends it twice. `start_time` is the slot start
+-- the reminder was about, a rescheduled appointment is reminded again.
+CREATE TABLE
+    appointment_reminders (
+        id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+        appointment_id UUID NOT NULL REFERENCES appointments (id),
+        offset_minutes INTEGER NOT NULL,
+        start_time TIMESTAMPTZ NOT NULL,
+        created_at TIMESTAMP NOT NULL DEFAULT now (),
+        sent_at TIMESTAMP,
+        CONSTRAINT uq_appointment_reminders_appointment_offset_start UNIQUE (appointment_id, offset_minutes, start_time)
+    );
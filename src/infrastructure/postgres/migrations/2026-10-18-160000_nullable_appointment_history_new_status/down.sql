@@ -0,0 +1,16 @@
+-- This file should undo anything in `up.sql`
+UPDATE appointment_status_history
+SET
+    previous_status = new_status
+WHERE
+    action = 'Restore';
+
+UPDATE appointment_status_history
+SET
+    new_status = previous_status
+WHERE
+    new_status IS NULL;
+
+ALTER TABLE appointment_status_history
+ALTER COLUMN new_status
+SET NOT NULL;
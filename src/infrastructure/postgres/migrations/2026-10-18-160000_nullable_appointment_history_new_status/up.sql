@@ -0,0 +1,19 @@
+-- Your SQL goes here
+-- A removed appointment has no status, its history entry keeps the status it
+-- was removed from in previous_status and no new_status. A restore entry is
+-- the other way around.
+ALTER TABLE appointment_status_history
+ALTER COLUMN new_status
+DROP NOT NULL;
+
+UPDATE appointment_status_history
+SET
+    new_status = NULL
+WHERE
+    action = 'Remove';
+
+UPDATE appointment_status_history
+SET
+    previous_status = NULL
+WHERE
+    action = 'Restore';
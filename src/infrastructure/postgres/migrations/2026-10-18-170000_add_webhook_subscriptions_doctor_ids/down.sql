@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE webhook_subscriptions
+DROP COLUMN doctor_ids;
@@ -0,0 +1,5 @@
+-- Your SQL goes here
+-- Doctors whose appointments and slots a subscription receives events about,
+-- every doctor when empty, like `event_types`.
+ALTER TABLE webhook_subscriptions
+ADD COLUMN doctor_ids INTEGER[] NOT NULL DEFAULT '{}';
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE appointment_reminders
+DROP COLUMN lease_until;
@@ -0,0 +1,13 @@
+-- Your SQL goes here
+-- A reminder that is still not sent when its lease runs out was claimed by a
+-- pass that stopped before sending it, the next pass claims it again.
+ALTER TABLE appointment_reminders
+ADD COLUMN lease_until TIMESTAMP;
+
+UPDATE appointment_reminders
+SET
+    lease_until = created_at;
+
+ALTER TABLE appointment_reminders
+ALTER COLUMN lease_until
+SET NOT NULL;
@@ -1,4 +1,5 @@
 pub mod postgres_connection;
+pub mod postgres_error;
 pub mod postgres_migration;
 pub mod repositories;
 pub mod schema;
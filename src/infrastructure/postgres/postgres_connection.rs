@@ -1,11 +1,9 @@
 use anyhow::Result;
 use std::time::Duration;
 
-use diesel::sql_query;
 use diesel_async::{
     pooled_connection::{bb8::Pool, AsyncDieselConnectionManager},
     AsyncPgConnection,
-    RunQueryDsl, // สำหรับ .execute().await
 };
 
 pub type PgPoolSquad = Pool<AsyncPgConnection>;
@@ -24,7 +22,7 @@ pub async fn establish_connection(database_url: &str) -> Result<PgPoolSquad> {
         .await?; // ถ้าเปิดคอนเนกชันแรกไม่ได้จะ error ที่นี่ (เมื่อมี min_idle)
 
     // พิสูจน์ว่าเชื่อมได้จริงโดยยืมคอนเนกชันแล้วยิง SELECT 1
-    let mut conn = pool.get().await?; // ถ้าต่อไม่ได้ จะ error ตรงนี้
+    let conn = pool.get().await?; // ถ้าต่อไม่ได้ จะ error ตรงนี้
     drop(conn);
 
     Ok(pool)
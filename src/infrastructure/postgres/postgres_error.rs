@@ -0,0 +1,54 @@
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use diesel_async::pooled_connection::bb8::RunError;
+
+use crate::domain::errors::DomainError;
+
+/// Maps violations of constraints the domain knows about to the error the
+/// matching application-level check would have returned.
+fn constraint_violation(constraint_name: &str) -> Option<DomainError> {
+    match constraint_name {
+        "excl_slots_doctor_id_time_overlap" => {
+            Some(DomainError::slot_overlap("Slot time is overlapping!!!"))
+        }
+        "chk_slots_end_time_after_start_time" => {
+            Some(DomainError::validation("end_time must be after start_time"))
+        }
+        "chk_slots_current_appointment_count_within_max" => {
+            Some(DomainError::slot_full("Slot is full!!!"))
+        }
+        "chk_slots_current_appointment_count_not_negative" => {
+            Some(DomainError::conflict("Slot has no appointment to release"))
+        }
+        _ => None,
+    }
+}
+
+impl From<DieselError> for DomainError {
+    fn from(e: DieselError) -> Self {
+        if let DieselError::DatabaseError(_, info) = &e
+            && let Some(domain_error) = info.constraint_name().and_then(constraint_violation)
+        {
+            return domain_error;
+        }
+
+        match e {
+            DieselError::NotFound => DomainError::not_found("Record not found"),
+            DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, info) => {
+                DomainError::conflict(info.message().to_string())
+            }
+            DieselError::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, info) => {
+                DomainError::not_found(info.message().to_string())
+            }
+            DieselError::DatabaseError(DatabaseErrorKind::CheckViolation, info) => {
+                DomainError::validation(info.message().to_string())
+            }
+            e => DomainError::Internal(e.into()),
+        }
+    }
+}
+
+impl From<RunError> for DomainError {
+    fn from(e: RunError) -> Self {
+        DomainError::Internal(e.into())
+    }
+}
@@ -0,0 +1,408 @@
+use std::sync::Arc;
+
+use chrono_tz::Tz;
+use diesel_async::{AsyncConnection, scoped_futures::ScopedFutureExt};
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        entities::{
+            appointments::{
+                AddAppointmentEntity, EditAppointmentEntity, RescheduleAppointmentEntity,
+            },
+            schedule_view::ScheduleViewEntity,
+            slots::EditSlotEntity,
+        },
+        errors::{DomainError, DomainResult},
+        repositories::admin::AdminRepository,
+        value_objects::{
+            actor::Actor,
+            admin_model::{AppointmentFilter, StaffAction, StaffActionRecord},
+            appointment_history_model::{AppointmentHistoryAction, AppointmentHistoryRecord},
+            appointment_model::{AppointmentTransition, TransitionAppointmentResponseModel},
+            booking_policy::BookingPolicy,
+            outbox_model::{AppointmentRestored, DomainEvent, SlotRestored},
+            timezone_model::doctor_timezone,
+        },
+    },
+    infrastructure::postgres::{
+        postgres_connection::PgPoolSquad,
+        repositories::{
+            appointment_ledger::AppointmentLedgerPostgres,
+            appointment_ops::AppointmentOpsPostgres,
+            data_access_objects::{
+                appointment_ledger::AppointmentLedgerDao, appointment_ops::AppointmentOpsDao,
+                appointment_status_history::AppointmentStatusHistoryDao,
+                appointment_viewing::AppointmentViewingDao, doctor_timezone::DoctorTimezoneDao,
+                outbox_event::OutboxEventDao, schedule_viewing::ScheduleViewingDao,
+                slot_ops::SlotOpsDao, slot_viewing::SlotViewingDao, staff_action::StaffActionDao,
+            },
+            slot_ops::SlotOpsPostgres,
+        },
+    },
+};
+
+/// Staff follow the same booking rules as patients and doctors, only the
+/// checks that a record belongs to the caller are lifted.
+pub struct AdminPostgres {
+    db_pool: Arc<PgPoolSquad>,
+    booking_policy: BookingPolicy,
+}
+
+impl AdminPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>, booking_policy: BookingPolicy) -> Self {
+        Self {
+            db_pool,
+            booking_policy,
+        }
+    }
+}
+
+impl AdminRepository for AdminPostgres {
+    async fn get_slot_doctor_timezone(&self, slot_id: Uuid) -> DomainResult<Tz> {
+        let mut conn = self.db_pool.get().await?;
+        let slot = SlotViewingDao::get_slot_by_id(&mut conn, slot_id).await?;
+        let doctor_timezone_entity =
+            DoctorTimezoneDao::get_by_doctor_id(&mut conn, slot.doctor_id).await?;
+
+        doctor_timezone(doctor_timezone_entity.as_ref())
+    }
+
+    async fn add_appointment(
+        &self,
+        add_appointment_entity: AddAppointmentEntity,
+        staff_id: i32,
+    ) -> DomainResult<Uuid> {
+        let mut conn = self.db_pool.get().await?;
+        let booking_policy = self.booking_policy;
+
+        let appointment_id = conn
+            .transaction(|conn| {
+                async move {
+                    let patient_id = add_appointment_entity.patient_id;
+                    let created_at = add_appointment_entity.created_at;
+                    let appointment_id = AppointmentOpsPostgres::apply_add(
+                        conn,
+                        booking_policy,
+                        add_appointment_entity,
+                        Actor::staff(staff_id),
+                        None,
+                    )
+                    .await?;
+
+                    let staff_action_record = StaffActionRecord {
+                        action: StaffAction::BookAppointment,
+                        staff_id,
+                        target_id: appointment_id,
+                        note: Some(format!("Booked for patient {}", patient_id)),
+                    };
+                    StaffActionDao::add(conn, staff_action_record, created_at).await?;
+
+                    Ok::<_, DomainError>(appointment_id)
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        Ok(appointment_id)
+    }
+
+    async fn edit_appointment(
+        &self,
+        appointment_id: Uuid,
+        reschedule_appointment_entity: Option<RescheduleAppointmentEntity>,
+        edit_appointment_entity: EditAppointmentEntity,
+        staff_id: i32,
+    ) -> DomainResult<Uuid> {
+        let mut conn = self.db_pool.get().await?;
+        let booking_policy = self.booking_policy;
+
+        let result = conn
+            .transaction(|conn| {
+                async move {
+                    let (patient_id, _) =
+                        AppointmentViewingDao::get_patient_id_and_doctor_id_by_appointment_id(
+                            conn,
+                            appointment_id,
+                        )
+                        .await?;
+
+                    if let Some(reschedule_appointment_entity) = reschedule_appointment_entity {
+                        let new_slot_id = reschedule_appointment_entity.slot_id;
+                        let updated_at = reschedule_appointment_entity.updated_at;
+                        AppointmentOpsPostgres::apply_reschedule(
+                            conn,
+                            booking_policy,
+                            appointment_id,
+                            patient_id,
+                            reschedule_appointment_entity,
+                            Actor::staff(staff_id),
+                            None,
+                        )
+                        .await?;
+
+                        let staff_action_record = StaffActionRecord {
+                            action: StaffAction::RescheduleAppointment,
+                            staff_id,
+                            target_id: appointment_id,
+                            note: Some(format!("Moved to slot {}", new_slot_id)),
+                        };
+                        StaffActionDao::add(conn, staff_action_record, updated_at).await?;
+                    }
+
+                    let updated_at = edit_appointment_entity.updated_at;
+                    let appointment_id = AppointmentOpsPostgres::apply_edit(
+                        conn,
+                        appointment_id,
+                        patient_id,
+                        edit_appointment_entity,
+                        Actor::staff(staff_id),
+                    )
+                    .await?;
+
+                    let staff_action_record = StaffActionRecord {
+                        action: StaffAction::EditAppointment,
+                        staff_id,
+                        target_id: appointment_id,
+                        note: None,
+                    };
+                    StaffActionDao::add(conn, staff_action_record, updated_at).await?;
+
+                    Ok::<_, DomainError>(appointment_id)
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn cancel_appointment(
+        &self,
+        appointment_id: Uuid,
+        staff_id: i32,
+        appointment_transition: AppointmentTransition,
+    ) -> DomainResult<TransitionAppointmentResponseModel> {
+        let mut conn = self.db_pool.get().await?;
+        let booking_policy = self.booking_policy;
+
+        let result = conn
+            .transaction(|conn| {
+                async move {
+                    let (slot_id, _) =
+                        AppointmentLedgerDao::lock_with_slot_doctor_id(conn, appointment_id)
+                            .await?;
+
+                    let staff_action_record = StaffActionRecord {
+                        action: StaffAction::CancelAppointment,
+                        staff_id,
+                        target_id: appointment_id,
+                        note: appointment_transition.reason.clone(),
+                    };
+                    let current_time = appointment_transition.current_time;
+                    let result = AppointmentLedgerPostgres::apply_transition(
+                        conn,
+                        booking_policy,
+                        appointment_id,
+                        slot_id,
+                        Actor::staff(staff_id),
+                        appointment_transition,
+                    )
+                    .await?;
+
+                    StaffActionDao::add(conn, staff_action_record, current_time).await?;
+
+                    Ok::<_, DomainError>(result)
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn restore_appointment(&self, appointment_id: Uuid, staff_id: i32) -> DomainResult<Uuid> {
+        let mut conn = self.db_pool.get().await?;
+        let booking_policy = self.booking_policy;
+
+        let result = conn
+            .transaction(|conn| {
+                async move {
+                    let (patient_id, slot_id, status) =
+                        AppointmentOpsDao::lock_removed_appointment(conn, appointment_id).await?;
+
+                    let end_time = SlotViewingDao::get_end_time_by_slot_id(conn, slot_id).await?;
+                    let now = chrono::Utc::now();
+
+                    if now > end_time {
+                        return Err(DomainError::past_time("Slot is already ended!!!"));
+                    }
+
+                    // A restored appointment that holds a place is booked again,
+                    // so it has to fit the slot and the booking policy once more.
+                    if status.holds_slot_place() {
+                        AppointmentOpsPostgres::enforce_booking_policy(
+                            conn,
+                            booking_policy,
+                            patient_id,
+                            slot_id,
+                            None,
+                            now,
+                        )
+                        .await?;
+
+                        SlotOpsDao::lock(conn, slot_id).await?;
+
+                        let slot_is_not_full = SlotOpsDao::has_free_place(conn, slot_id).await?;
+
+                        if !slot_is_not_full {
+                            return Err(DomainError::slot_full("Slot is full!!!"));
+                        }
+                    }
+
+                    let current_time = now.naive_utc();
+                    let appointment_id =
+                        AppointmentOpsDao::restore(conn, appointment_id, current_time).await?;
+
+                    let appointment_history_record = AppointmentHistoryRecord {
+                        action: AppointmentHistoryAction::Restore,
+                        previous_status: None,
+                        new_status: Some(status),
+                        actor: Actor::staff(staff_id),
+                        note: None,
+                    };
+                    AppointmentStatusHistoryDao::add(
+                        conn,
+                        appointment_id,
+                        appointment_history_record,
+                        current_time,
+                    )
+                    .await?;
+
+                    let appointment_restored = AppointmentRestored {
+                        appointment_id,
+                        slot_id,
+                        patient_id,
+                        status,
+                        actor: Actor::staff(staff_id),
+                    };
+                    OutboxEventDao::add(
+                        conn,
+                        DomainEvent::AppointmentRestored(appointment_restored),
+                        current_time,
+                    )
+                    .await?;
+
+                    let staff_action_record = StaffActionRecord {
+                        action: StaffAction::RestoreAppointment,
+                        staff_id,
+                        target_id: appointment_id,
+                        note: None,
+                    };
+                    StaffActionDao::add(conn, staff_action_record, current_time).await?;
+
+                    Ok(appointment_id)
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn get_appointments(
+        &self,
+        appointment_filter: AppointmentFilter,
+    ) -> DomainResult<(Vec<ScheduleViewEntity>, i64)> {
+        let mut conn = self.db_pool.get().await?;
+
+        let total = ScheduleViewingDao::count_appointments(&mut conn, &appointment_filter).await?;
+        let appointments =
+            ScheduleViewingDao::get_appointments(&mut conn, &appointment_filter).await?;
+
+        Ok((appointments, total))
+    }
+
+    async fn edit_slot(
+        &self,
+        slot_id: Uuid,
+        edit_slot_entity: EditSlotEntity,
+        staff_id: i32,
+    ) -> DomainResult<Uuid> {
+        let mut conn = self.db_pool.get().await?;
+
+        let effected_slot_id = conn
+            .transaction(|conn| {
+                async move {
+                    let slot = SlotViewingDao::get_slot_by_id(conn, slot_id).await?;
+                    let updated_at = edit_slot_entity.updated_at;
+                    let effected_slot_id = SlotOpsPostgres::apply_edit(
+                        conn,
+                        slot_id,
+                        slot.doctor_id,
+                        edit_slot_entity,
+                    )
+                    .await?;
+
+                    let staff_action_record = StaffActionRecord {
+                        action: StaffAction::EditSlot,
+                        staff_id,
+                        target_id: effected_slot_id,
+                        note: Some(format!("Slot of doctor {}", slot.doctor_id)),
+                    };
+                    StaffActionDao::add(conn, staff_action_record, updated_at).await?;
+
+                    Ok::<_, DomainError>(effected_slot_id)
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        Ok(effected_slot_id)
+    }
+
+    async fn restore_slot(&self, slot_id: Uuid, staff_id: i32) -> DomainResult<Uuid> {
+        let mut conn = self.db_pool.get().await?;
+
+        let result = conn
+            .transaction(|conn| {
+                async move {
+                    let slot = SlotOpsDao::lock_removed(conn, slot_id).await?;
+                    let now = chrono::Utc::now();
+
+                    if now > slot.end_time {
+                        return Err(DomainError::past_time("Slot is already ended!!!"));
+                    }
+
+                    let current_time = now.naive_utc();
+                    let slot_id = SlotOpsDao::restore(conn, slot_id, current_time).await?;
+
+                    let slot_restored = SlotRestored {
+                        slot_id,
+                        doctor_id: slot.doctor_id,
+                        actor: Actor::staff(staff_id),
+                    };
+                    OutboxEventDao::add(
+                        conn,
+                        DomainEvent::SlotRestored(slot_restored),
+                        current_time,
+                    )
+                    .await?;
+
+                    let staff_action_record = StaffActionRecord {
+                        action: StaffAction::RestoreSlot,
+                        staff_id,
+                        target_id: slot_id,
+                        note: Some(format!("Slot of doctor {}", slot.doctor_id)),
+                    };
+                    StaffActionDao::add(conn, staff_action_record, current_time).await?;
+
+                    Ok(slot_id)
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        Ok(result)
+    }
+}
@@ -1,136 +1,187 @@
 use std::sync::Arc;
 
-use anyhow::Result;
-use diesel_async::{AsyncConnection, scoped_futures::ScopedFutureExt};
+use diesel_async::{AsyncConnection, AsyncPgConnection, scoped_futures::ScopedFutureExt};
 use uuid::Uuid;
 
 use crate::{
     domain::{
+        errors::{DomainError, DomainResult},
         repositories::appointment_ledger::AppointmentLedgerRepository,
-        value_objects::appointment_status::AppointmentStatus,
+        value_objects::{
+            actor::Actor,
+            appointment_history_model::{AppointmentHistoryAction, AppointmentHistoryRecord},
+            appointment_model::{
+                AppointmentTransition, AppointmentTransitionTarget,
+                TransitionAppointmentResponseModel,
+            },
+            appointment_status::AppointmentStatus,
+            booking_policy::BookingPolicy,
+            outbox_model::{AppointmentStatusChanged, DomainEvent},
+        },
     },
     infrastructure::postgres::{
         postgres_connection::PgPoolSquad,
-        repositories::data_access_objects::{
-            appointment_ledger::AppointmentLedgerDao, appointment_viewing::AppointmentViewingDao,
+        repositories::{
+            data_access_objects::{
+                appointment_ledger::AppointmentLedgerDao,
+                appointment_status_history::AppointmentStatusHistoryDao,
+                appointment_viewing::AppointmentViewingDao, outbox_event::OutboxEventDao,
+                slot_ops::SlotOpsDao,
+            },
+            waitlist::WaitlistPostgres,
         },
     },
 };
 
 pub struct AppointmentLedgerPostgres {
     db_pool: Arc<PgPoolSquad>,
+    booking_policy: BookingPolicy,
 }
 
 impl AppointmentLedgerPostgres {
-    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
-        Self { db_pool }
+    pub fn new(db_pool: Arc<PgPoolSquad>, booking_policy: BookingPolicy) -> Self {
+        Self {
+            db_pool,
+            booking_policy,
+        }
     }
-}
-
-impl AppointmentLedgerRepository for AppointmentLedgerPostgres {
-    async fn to_ready(&self, appointment_id: Uuid) -> Result<Uuid> {
-        let mut conn = self.db_pool.get().await?;
-
-        let result = conn
-            .transaction(|conn| {
-                async move {
-                    let current_appointment_status =
-                        AppointmentViewingDao::get_appointment_status_by_appointment_id(
-                            conn,
-                            appointment_id,
-                        )
-                        .await?;
-
-                    let condition_to_update =
-                        current_appointment_status == AppointmentStatus::Waiting.to_string();
-
-                    if !condition_to_update {
-                        return Err(anyhow::anyhow!("Invalid condition to change status"));
-                    }
 
-                    let appointment_status = AppointmentStatus::Ready;
-                    let appointment = AppointmentLedgerDao::change_appointment_status(
-                        conn,
-                        appointment_id,
-                        appointment_status,
-                    )
-                    .await?;
-
-                    anyhow::Ok(appointment)
-                }
-                .scope_boxed()
-            })
+    /// Changes the status of an appointment whose slot is already locked, taking
+    /// or giving back its place in the slot when needed, and records it in the
+    /// appointment's history. A place given back goes to the slot's waitlist.
+    pub(crate) async fn apply_transition(
+        conn: &mut AsyncPgConnection,
+        booking_policy: BookingPolicy,
+        appointment_id: Uuid,
+        slot_id: Uuid,
+        actor: Actor,
+        appointment_transition: AppointmentTransition,
+    ) -> DomainResult<TransitionAppointmentResponseModel> {
+        let previous_status =
+            AppointmentViewingDao::get_appointment_status_by_appointment_id(conn, appointment_id)
+                .await?
+                .parse::<AppointmentStatus>()?;
+        let status_before = match appointment_transition.target {
+            AppointmentTransitionTarget::Revert => AppointmentStatusHistoryDao::get_status_before(
+                conn,
+                appointment_id,
+                previous_status.to_string(),
+                previous_status
+                    .revert_targets()
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect(),
+            )
+            .await?
+            .map(|status| status.parse::<AppointmentStatus>())
+            .transpose()?,
+            AppointmentTransitionTarget::Status(_) => None,
+        };
+        let status = appointment_transition.resolve(previous_status, status_before)?;
+
+        if !previous_status.holds_slot_place() && status.holds_slot_place() {
+            let slot_is_not_full = SlotOpsDao::has_free_place(conn, slot_id).await?;
+
+            if !slot_is_not_full {
+                return Err(DomainError::slot_full("Slot is full!!!"));
+            }
+        }
+
+        let change_appointment_status_entity = appointment_transition.to_entity(status);
+        AppointmentLedgerDao::change_appointment_status(
+            conn,
+            appointment_id,
+            change_appointment_status_entity,
+        )
+        .await?;
+
+        let appointment_history_record = AppointmentHistoryRecord {
+            action: AppointmentHistoryAction::Transition,
+            previous_status: Some(previous_status),
+            new_status: Some(status),
+            actor,
+            note: appointment_transition.reason.clone(),
+        };
+        AppointmentStatusHistoryDao::add(
+            conn,
+            appointment_id,
+            appointment_history_record,
+            appointment_transition.current_time,
+        )
+        .await?;
+
+        let (patient_id, _) =
+            AppointmentViewingDao::get_patient_id_and_doctor_id_by_appointment_id(
+                conn,
+                appointment_id,
+            )
             .await?;
-
-        Ok(result)
-    }
-
-    async fn to_waiting_for_prescription(&self, appointment_id: Uuid) -> Result<Uuid> {
-        let mut conn = self.db_pool.get().await?;
-
-        let result = conn
-            .transaction(|conn| {
-                async move {
-                    let current_appointment_status =
-                        AppointmentViewingDao::get_appointment_status_by_appointment_id(
-                            conn,
-                            appointment_id,
-                        )
-                        .await?;
-
-                    let condition_to_update =
-                        current_appointment_status == AppointmentStatus::Ready.to_string();
-
-                    if !condition_to_update {
-                        return Err(anyhow::anyhow!("Invalid condition to change status"));
-                    }
-
-                    let appointment_status = AppointmentStatus::WaitingForPrescription;
-                    let appointment = AppointmentLedgerDao::change_appointment_status(
-                        conn,
-                        appointment_id,
-                        appointment_status,
-                    )
-                    .await?;
-
-                    anyhow::Ok(appointment)
-                }
-                .scope_boxed()
-            })
+        let appointment_status_changed = AppointmentStatusChanged {
+            appointment_id,
+            slot_id,
+            patient_id,
+            previous_status,
+            status,
+            reason: appointment_transition.reason,
+            actor,
+        };
+        OutboxEventDao::add(
+            conn,
+            DomainEvent::AppointmentStatusChanged(appointment_status_changed),
+            appointment_transition.current_time,
+        )
+        .await?;
+
+        if previous_status.holds_slot_place() && !status.holds_slot_place() {
+            WaitlistPostgres::promote_first(
+                conn,
+                booking_policy,
+                slot_id,
+                appointment_transition.current_time.and_utc(),
+            )
             .await?;
+        }
 
-        Ok(result)
+        Ok(TransitionAppointmentResponseModel {
+            appointment_id,
+            previous_status,
+            status,
+        })
     }
+}
 
-    async fn to_completed(&self, appointment_id: Uuid) -> Result<Uuid> {
+impl AppointmentLedgerRepository for AppointmentLedgerPostgres {
+    async fn transition(
+        &self,
+        appointment_id: Uuid,
+        actor: Actor,
+        appointment_transition: AppointmentTransition,
+    ) -> DomainResult<TransitionAppointmentResponseModel> {
         let mut conn = self.db_pool.get().await?;
+        let booking_policy = self.booking_policy;
 
         let result = conn
             .transaction(|conn| {
                 async move {
-                    let current_appointment_status =
-                        AppointmentViewingDao::get_appointment_status_by_appointment_id(
-                            conn,
-                            appointment_id,
-                        )
-                        .await?;
-
-                    let condition_to_update = current_appointment_status
-                        == AppointmentStatus::WaitingForPrescription.to_string();
-
-                    if !condition_to_update {
-                        return Err(anyhow::anyhow!("Invalid condition to change status"));
+                    let (slot_id, doctor_id) =
+                        AppointmentLedgerDao::lock_with_slot_doctor_id(conn, appointment_id)
+                            .await?;
+
+                    if !actor.is_privileged() && actor != Actor::doctor(doctor_id) {
+                        return Err(DomainError::forbidden(
+                            "Appointment does not belong to your slot",
+                        ));
                     }
 
-                    let appointment_status = AppointmentStatus::Completed;
-                    let appointment = AppointmentLedgerDao::change_appointment_status(
+                    Self::apply_transition(
                         conn,
+                        booking_policy,
                         appointment_id,
-                        appointment_status,
+                        slot_id,
+                        actor,
+                        appointment_transition,
                     )
-                    .await?;
-
-                    anyhow::Ok(appointment)
+                    .await
                 }
                 .scope_boxed()
             })
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
-use anyhow::Result;
-use diesel_async::{AsyncConnection, scoped_futures::ScopedFutureExt};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use diesel_async::{AsyncConnection, AsyncPgConnection, scoped_futures::ScopedFutureExt};
 use uuid::Uuid;
 
 use crate::{
@@ -9,55 +9,421 @@ use crate::{
         entities::appointments::{
             AddAppointmentEntity, EditAppointmentEntity, RescheduleAppointmentEntity,
         },
+        errors::{DomainError, DomainResult},
         repositories::appointment_ops::AppointmentOpsRepository,
+        value_objects::{
+            actor::Actor,
+            appointment_history_model::{AppointmentHistoryAction, AppointmentHistoryRecord},
+            appointment_status::AppointmentStatus,
+            booking_policy::BookingPolicy,
+            booking_window::BookingWindow,
+            idempotency_model::IdempotencyKey,
+            outbox_model::{
+                AppointmentBooked, AppointmentEdited, AppointmentRemoved, AppointmentRescheduled,
+                DomainEvent,
+            },
+        },
     },
     infrastructure::postgres::{
         postgres_connection::PgPoolSquad,
-        repositories::data_access_objects::{
-            appointment_ops::AppointmentOpsDao, appointment_viewing::AppointmentViewingDao,
-            slot_ops::SlotOpsDao, slot_viewing::SlotViewingDao,
+        repositories::{
+            data_access_objects::{
+                appointment_ops::AppointmentOpsDao,
+                appointment_status_history::AppointmentStatusHistoryDao,
+                appointment_viewing::AppointmentViewingDao,
+                doctor_booking_window::DoctorBookingWindowDao, idempotency_key::IdempotencyKeyDao,
+                outbox_event::OutboxEventDao, slot_ops::SlotOpsDao, slot_viewing::SlotViewingDao,
+            },
+            waitlist::WaitlistPostgres,
         },
     },
 };
 
 pub struct AppointmentOpsPostgres {
     db_pool: Arc<PgPoolSquad>,
+    booking_policy: BookingPolicy,
 }
 
 impl AppointmentOpsPostgres {
-    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
-        Self { db_pool }
+    pub fn new(db_pool: Arc<PgPoolSquad>, booking_policy: BookingPolicy) -> Self {
+        Self {
+            db_pool,
+            booking_policy,
+        }
     }
-}
 
-impl AppointmentOpsRepository for AppointmentOpsPostgres {
-    async fn add(&self, add_appointment_entity: AddAppointmentEntity) -> Result<Uuid> {
-        let mut conn = self.db_pool.get().await?;
+    /// The booking window enforced for the slots of `doctor_id`.
+    pub(crate) async fn booking_window_of(
+        conn: &mut AsyncPgConnection,
+        booking_window: BookingWindow,
+        doctor_id: i32,
+    ) -> DomainResult<BookingWindow> {
+        let doctor_booking_window =
+            DoctorBookingWindowDao::get_by_doctor_id(conn, doctor_id).await?;
 
-        let appointment_id = conn
-            .transaction(|conn| {
-                async move {
-                    let slot_id = add_appointment_entity.slot_id;
+        Ok(booking_window.with_doctor_override(doctor_booking_window.as_ref()))
+    }
+
+    /// Refuses a cancellation or reschedule of an appointment in `slot_id` that
+    /// comes too close to the appointment.
+    async fn enforce_change_notice(
+        conn: &mut AsyncPgConnection,
+        booking_window: BookingWindow,
+        slot_id: Uuid,
+        current_time: DateTime<Utc>,
+    ) -> DomainResult<()> {
+        let slot = SlotViewingDao::get_slot_by_id(conn, slot_id).await?;
+
+        Self::booking_window_of(conn, booking_window, slot.doctor_id)
+            .await?
+            .check_change(slot.start_time, current_time)
+    }
+
+    /// Refuses a booking into `slot_id` that the booking policy does not allow.
+    /// `moving_appointment_id` is the appointment being rescheduled, if any.
+    pub(crate) async fn enforce_booking_policy(
+        conn: &mut AsyncPgConnection,
+        booking_policy: BookingPolicy,
+        patient_id: i32,
+        slot_id: Uuid,
+        moving_appointment_id: Option<Uuid>,
+        current_time: DateTime<Utc>,
+    ) -> DomainResult<()> {
+        AppointmentOpsDao::lock_patient(conn, patient_id).await?;
+
+        let slot = SlotViewingDao::get_slot_by_id(conn, slot_id).await?;
+        Self::booking_window_of(conn, booking_policy.booking_window, slot.doctor_id)
+            .await?
+            .check_booking(slot.start_time, current_time)?;
+
+        let open_appointments =
+            AppointmentViewingDao::get_open_patient_appointments(conn, patient_id, current_time)
+                .await?
+                .into_iter()
+                .filter(|appointment| Some(appointment.id) != moving_appointment_id)
+                .collect::<Vec<_>>();
+
+        booking_policy.check(&slot, &open_appointments)
+    }
+
+    /// Returns the appointment id stored for a retried request, so it can be
+    /// answered again without touching the slot's capacity. The caller holds
+    /// the patient's lock, so a retry sent while the first request is still
+    /// running waits for it and then replays its answer.
+    async fn replay(
+        conn: &mut AsyncPgConnection,
+        patient_id: i32,
+        idempotency_key: Option<&IdempotencyKey>,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<Option<Uuid>> {
+        let Some(idempotency_key) = idempotency_key else {
+            return Ok(None);
+        };
+
+        let expired_before = IdempotencyKey::expired_before(current_time);
+        let Some(stored) =
+            IdempotencyKeyDao::get(conn, patient_id, idempotency_key, expired_before).await?
+        else {
+            return Ok(None);
+        };
+
+        if stored.request_hash != idempotency_key.request_hash {
+            return Err(DomainError::conflict(
+                "Idempotency-Key was already used for a different request",
+            ));
+        }
+
+        let appointment_id = serde_json::from_value::<Uuid>(stored.response)
+            .map_err(|e| DomainError::Internal(e.into()))?;
+        Ok(Some(appointment_id))
+    }
+
+    async fn remember(
+        conn: &mut AsyncPgConnection,
+        patient_id: i32,
+        idempotency_key: Option<IdempotencyKey>,
+        appointment_id: Uuid,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<()> {
+        if let Some(idempotency_key) = idempotency_key {
+            let expired_before = IdempotencyKey::expired_before(current_time);
+            IdempotencyKeyDao::delete_expired(conn, patient_id, expired_before).await?;
+
+            let add_idempotency_key_entity = idempotency_key.to_entity(
+                patient_id,
+                serde_json::json!(appointment_id),
+                current_time,
+            );
+            IdempotencyKeyDao::add(conn, add_idempotency_key_entity).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Books an appointment on behalf of `actor`, answering a retried request
+    /// with the appointment it already created.
+    pub(crate) async fn apply_add(
+        conn: &mut AsyncPgConnection,
+        booking_policy: BookingPolicy,
+        add_appointment_entity: AddAppointmentEntity,
+        actor: Actor,
+        idempotency_key: Option<IdempotencyKey>,
+    ) -> DomainResult<Uuid> {
+        let patient_id = add_appointment_entity.patient_id;
+        AppointmentOpsDao::lock_patient(conn, patient_id).await?;
+
+        if let Some(appointment_id) = Self::replay(
+            conn,
+            patient_id,
+            idempotency_key.as_ref(),
+            add_appointment_entity.created_at,
+        )
+        .await?
+        {
+            return Ok(appointment_id);
+        }
+
+        let slot_id = add_appointment_entity.slot_id;
+
+        let end_time = SlotViewingDao::get_end_time_by_slot_id(conn, slot_id).await?;
+        let now = chrono::Utc::now();
+
+        if now > end_time {
+            return Err(DomainError::past_time("Slot is already ended!!!"));
+        }
 
-                    let end_time = SlotViewingDao::get_end_time_by_slot_id(conn, slot_id).await?;
-                    let now = chrono::Utc::now().naive_utc();
+        Self::enforce_booking_policy(conn, booking_policy, patient_id, slot_id, None, now).await?;
 
-                    if now > end_time {
-                        return Err(anyhow::anyhow!("Slot is already ended!!!"));
-                    }
+        SlotOpsDao::lock(conn, slot_id).await?;
 
-                    SlotOpsDao::lock(conn, slot_id).await?;
+        let slot_is_not_full = SlotOpsDao::has_free_place(conn, slot_id).await?;
 
-                    let slot_is_not_full =
-                        SlotOpsDao::try_add_slot_appointment_count(conn, slot_id).await?;
+        if !slot_is_not_full {
+            return Err(DomainError::slot_full("Slot is full!!!"));
+        }
 
-                    if !slot_is_not_full {
-                        return Err(anyhow::anyhow!("Slot is full!!!"));
-                    }
+        let created_at = add_appointment_entity.created_at;
+        let appointment_id = AppointmentOpsDao::add(conn, add_appointment_entity).await?;
 
-                    let appointment_id =
-                        AppointmentOpsDao::add(conn, add_appointment_entity).await?;
-                    anyhow::Ok(appointment_id)
+        let appointment_history_record = AppointmentHistoryRecord {
+            action: AppointmentHistoryAction::Create,
+            previous_status: None,
+            new_status: Some(AppointmentStatus::Waiting),
+            actor,
+            note: None,
+        };
+        AppointmentStatusHistoryDao::add(
+            conn,
+            appointment_id,
+            appointment_history_record,
+            created_at,
+        )
+        .await?;
+
+        let appointment_booked = AppointmentBooked {
+            appointment_id,
+            slot_id,
+            patient_id,
+            actor,
+        };
+        OutboxEventDao::add(
+            conn,
+            DomainEvent::AppointmentBooked(appointment_booked),
+            created_at,
+        )
+        .await?;
+
+        Self::remember(
+            conn,
+            patient_id,
+            idempotency_key,
+            appointment_id,
+            created_at,
+        )
+        .await?;
+
+        Ok(appointment_id)
+    }
+
+    /// Edits the details of a `Waiting` appointment of `patient_id` on behalf
+    /// of `actor`.
+    pub(crate) async fn apply_edit(
+        conn: &mut AsyncPgConnection,
+        appointment_id: Uuid,
+        patient_id: i32,
+        edit_appointment_entity: EditAppointmentEntity,
+        actor: Actor,
+    ) -> DomainResult<Uuid> {
+        AppointmentOpsDao::lock_patient_appointment(conn, appointment_id, patient_id).await?;
+
+        let updated_at = edit_appointment_entity.updated_at;
+        let appointment_id =
+            AppointmentOpsDao::edit(conn, appointment_id, patient_id, edit_appointment_entity)
+                .await?;
+
+        let appointment_history_record = AppointmentHistoryRecord {
+            action: AppointmentHistoryAction::Edit,
+            previous_status: Some(AppointmentStatus::Waiting),
+            new_status: Some(AppointmentStatus::Waiting),
+            actor,
+            note: None,
+        };
+        AppointmentStatusHistoryDao::add(
+            conn,
+            appointment_id,
+            appointment_history_record,
+            updated_at,
+        )
+        .await?;
+
+        let appointment_edited = AppointmentEdited {
+            appointment_id,
+            patient_id,
+            actor,
+        };
+        OutboxEventDao::add(
+            conn,
+            DomainEvent::AppointmentEdited(appointment_edited),
+            updated_at,
+        )
+        .await?;
+
+        Ok(appointment_id)
+    }
+
+    /// Moves a `Waiting` appointment of `patient_id` to another slot on behalf
+    /// of `actor`, handing the place it frees to the old slot's waitlist.
+    pub(crate) async fn apply_reschedule(
+        conn: &mut AsyncPgConnection,
+        booking_policy: BookingPolicy,
+        appointment_id: Uuid,
+        patient_id: i32,
+        reschedule_appointment_entity: RescheduleAppointmentEntity,
+        actor: Actor,
+        idempotency_key: Option<IdempotencyKey>,
+    ) -> DomainResult<Uuid> {
+        AppointmentOpsDao::lock_patient(conn, patient_id).await?;
+
+        if let Some(appointment_effected_id) = Self::replay(
+            conn,
+            patient_id,
+            idempotency_key.as_ref(),
+            reschedule_appointment_entity.updated_at,
+        )
+        .await?
+        {
+            return Ok(appointment_effected_id);
+        }
+
+        let old_slot_id =
+            AppointmentOpsDao::lock_patient_appointment(conn, appointment_id, patient_id).await?;
+
+        let new_slot_id = reschedule_appointment_entity.slot_id;
+        let end_time = SlotViewingDao::get_end_time_by_slot_id(conn, new_slot_id).await?;
+        let now = chrono::Utc::now();
+
+        if now > end_time {
+            return Err(DomainError::past_time("Slot is already ended!!!"));
+        }
+
+        Self::enforce_change_notice(conn, booking_policy.booking_window, old_slot_id, now).await?;
+
+        Self::enforce_booking_policy(
+            conn,
+            booking_policy,
+            patient_id,
+            new_slot_id,
+            Some(appointment_id),
+            now,
+        )
+        .await?;
+
+        SlotOpsDao::lock(conn, new_slot_id).await?;
+        SlotOpsDao::lock(conn, old_slot_id).await?;
+
+        let slot_is_not_full = SlotOpsDao::has_free_place(conn, new_slot_id).await?;
+
+        if !slot_is_not_full {
+            return Err(DomainError::slot_full("Slot is full!!!"));
+        }
+
+        let updated_at = reschedule_appointment_entity.updated_at;
+        let appointment_effected_id = AppointmentOpsDao::reschedule(
+            conn,
+            appointment_id,
+            patient_id,
+            reschedule_appointment_entity,
+        )
+        .await?;
+
+        let appointment_history_record = AppointmentHistoryRecord {
+            action: AppointmentHistoryAction::Reschedule,
+            previous_status: Some(AppointmentStatus::Waiting),
+            new_status: Some(AppointmentStatus::Waiting),
+            actor,
+            note: Some(format!(
+                "Moved from slot {} to slot {}",
+                old_slot_id, new_slot_id
+            )),
+        };
+        AppointmentStatusHistoryDao::add(
+            conn,
+            appointment_effected_id,
+            appointment_history_record,
+            updated_at,
+        )
+        .await?;
+
+        let appointment_rescheduled = AppointmentRescheduled {
+            appointment_id: appointment_effected_id,
+            patient_id,
+            previous_slot_id: old_slot_id,
+            slot_id: new_slot_id,
+            actor,
+        };
+        OutboxEventDao::add(
+            conn,
+            DomainEvent::AppointmentRescheduled(appointment_rescheduled),
+            updated_at,
+        )
+        .await?;
+
+        Self::remember(
+            conn,
+            patient_id,
+            idempotency_key,
+            appointment_effected_id,
+            updated_at,
+        )
+        .await?;
+
+        WaitlistPostgres::promote_first(conn, booking_policy, old_slot_id, now).await?;
+
+        Ok(appointment_effected_id)
+    }
+}
+
+impl AppointmentOpsRepository for AppointmentOpsPostgres {
+    async fn add(
+        &self,
+        add_appointment_entity: AddAppointmentEntity,
+        idempotency_key: Option<IdempotencyKey>,
+    ) -> DomainResult<Uuid> {
+        let mut conn = self.db_pool.get().await?;
+        let booking_policy = self.booking_policy;
+
+        let appointment_id = conn
+            .transaction(|conn| {
+                async move {
+                    let actor = Actor::patient(add_appointment_entity.patient_id);
+                    Self::apply_add(
+                        conn,
+                        booking_policy,
+                        add_appointment_entity,
+                        actor,
+                        idempotency_key,
+                    )
+                    .await
                 }
                 .scope_boxed()
             })
@@ -71,16 +437,24 @@ impl AppointmentOpsRepository for AppointmentOpsPostgres {
         appointment_id: Uuid,
         patient_id: i32,
         edit_appointment_entity: EditAppointmentEntity,
-    ) -> Result<Uuid> {
+    ) -> DomainResult<Uuid> {
         let mut conn = self.db_pool.get().await?;
 
-        let result = AppointmentOpsDao::edit(
-            &mut conn,
-            appointment_id,
-            patient_id,
-            edit_appointment_entity,
-        )
-        .await?;
+        let result = conn
+            .transaction(|conn| {
+                async move {
+                    Self::apply_edit(
+                        conn,
+                        appointment_id,
+                        patient_id,
+                        edit_appointment_entity,
+                        Actor::patient(patient_id),
+                    )
+                    .await
+                }
+                .scope_boxed()
+            })
+            .await?;
 
         Ok(result)
     }
@@ -90,45 +464,24 @@ impl AppointmentOpsRepository for AppointmentOpsPostgres {
         appointment_id: Uuid,
         patient_id: i32,
         reschedule_appointment_entity: RescheduleAppointmentEntity,
-    ) -> Result<Uuid> {
+        idempotency_key: Option<IdempotencyKey>,
+    ) -> DomainResult<Uuid> {
         let mut conn = self.db_pool.get().await?;
+        let booking_policy = self.booking_policy;
 
         let appointment_effected_id = conn
             .transaction(|conn| {
                 async move {
-                    let new_slot_id = reschedule_appointment_entity.slot_id;
-                    let end_time =
-                        SlotViewingDao::get_end_time_by_slot_id(conn, new_slot_id).await?;
-                    let now = chrono::Utc::now().naive_utc();
-
-                    if now > end_time {
-                        return Err(anyhow::anyhow!("Slot is already ended!!!"));
-                    }
-
-                    let old_slot_id =
-                        AppointmentViewingDao::get_slot_id_by_appointment_id(conn, appointment_id)
-                            .await?;
-
-                    SlotOpsDao::lock(conn, new_slot_id).await?;
-                    SlotOpsDao::lock(conn, old_slot_id).await?;
-
-                    let slot_is_not_full =
-                        SlotOpsDao::try_add_slot_appointment_count(conn, new_slot_id).await?;
-
-                    if !slot_is_not_full {
-                        return Err(anyhow::anyhow!("Slot is full!!!"));
-                    }
-
-                    SlotOpsDao::dec_slot_appointment_count(conn, old_slot_id).await?;
-                    let appointment_effected_id = AppointmentOpsDao::reschedule(
+                    Self::apply_reschedule(
                         conn,
+                        booking_policy,
                         appointment_id,
                         patient_id,
                         reschedule_appointment_entity,
+                        Actor::patient(patient_id),
+                        idempotency_key,
                     )
-                    .await?;
-
-                    Ok(appointment_effected_id)
+                    .await
                 }
                 .scope_boxed()
             })
@@ -137,18 +490,59 @@ impl AppointmentOpsRepository for AppointmentOpsPostgres {
         Ok(appointment_effected_id)
     }
 
-    async fn remove(&self, appointment_id: Uuid, patient_id: i32) -> Result<()> {
+    async fn remove(&self, appointment_id: Uuid, patient_id: i32) -> DomainResult<()> {
         let mut conn = self.db_pool.get().await?;
+        let booking_policy = self.booking_policy;
+
         conn.transaction(|conn| {
             async move {
+                let current_time = chrono::Utc::now();
                 let slot_id =
-                    AppointmentViewingDao::get_slot_id_by_appointment_id(conn, appointment_id)
+                    AppointmentOpsDao::lock_patient_appointment(conn, appointment_id, patient_id)
                         .await?;
+                Self::enforce_change_notice(
+                    conn,
+                    booking_policy.booking_window,
+                    slot_id,
+                    current_time,
+                )
+                .await?;
                 SlotOpsDao::lock(conn, slot_id).await?;
-                SlotOpsDao::dec_slot_appointment_count(conn, slot_id).await?;
 
                 AppointmentOpsDao::remove(conn, appointment_id, patient_id).await?;
-                anyhow::Ok(())
+
+                let appointment_history_record = AppointmentHistoryRecord {
+                    action: AppointmentHistoryAction::Remove,
+                    previous_status: Some(AppointmentStatus::Waiting),
+                    new_status: None,
+                    actor: Actor::patient(patient_id),
+                    note: None,
+                };
+                AppointmentStatusHistoryDao::add(
+                    conn,
+                    appointment_id,
+                    appointment_history_record,
+                    current_time.naive_utc(),
+                )
+                .await?;
+
+                let appointment_removed = AppointmentRemoved {
+                    appointment_id,
+                    slot_id,
+                    patient_id,
+                    actor: Actor::patient(patient_id),
+                };
+                OutboxEventDao::add(
+                    conn,
+                    DomainEvent::AppointmentRemoved(appointment_removed),
+                    current_time.naive_utc(),
+                )
+                .await?;
+
+                WaitlistPostgres::promote_first(conn, booking_policy, slot_id, current_time)
+                    .await?;
+
+                Ok::<(), DomainError>(())
             }
             .scope_boxed()
         })
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        entities::{
+            appointment_reminders::{AddAppointmentReminderEntity, AppointmentReminderEntity},
+            schedule_view::ScheduleViewEntity,
+        },
+        errors::DomainResult,
+        repositories::appointment_reminder::AppointmentReminderRepository,
+    },
+    infrastructure::postgres::{
+        postgres_connection::PgPoolSquad,
+        repositories::data_access_objects::appointment_reminder::AppointmentReminderDao,
+    },
+};
+
+pub struct AppointmentReminderPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl AppointmentReminderPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+impl AppointmentReminderRepository for AppointmentReminderPostgres {
+    async fn get_due(
+        &self,
+        offset_minutes: i32,
+        after: DateTime<Utc>,
+        until: DateTime<Utc>,
+        current_time: NaiveDateTime,
+        limit: i64,
+    ) -> DomainResult<Vec<ScheduleViewEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        AppointmentReminderDao::get_due(
+            &mut conn,
+            offset_minutes,
+            after,
+            until,
+            current_time,
+            limit,
+        )
+        .await
+    }
+
+    async fn claim(
+        &self,
+        add_appointment_reminder_entity: AddAppointmentReminderEntity,
+    ) -> DomainResult<Option<AppointmentReminderEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        AppointmentReminderDao::claim(&mut conn, add_appointment_reminder_entity).await
+    }
+
+    async fn mark_sent(&self, id: Uuid, current_time: NaiveDateTime) -> DomainResult<()> {
+        let mut conn = self.db_pool.get().await?;
+        AppointmentReminderDao::mark_sent(&mut conn, id, current_time).await
+    }
+
+    async fn release(&self, id: Uuid) -> DomainResult<()> {
+        let mut conn = self.db_pool.get().await?;
+        AppointmentReminderDao::remove(&mut conn, id).await
+    }
+}
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::doctor_booking_windows::SetDoctorBookingWindowEntity,
+        errors::DomainResult,
+        repositories::booking_window::BookingWindowRepository,
+        value_objects::booking_window::{BookingWindow, GetDoctorBookingWindowResponseModel},
+    },
+    infrastructure::postgres::{
+        postgres_connection::PgPoolSquad,
+        repositories::data_access_objects::doctor_booking_window::DoctorBookingWindowDao,
+    },
+};
+
+pub struct BookingWindowPostgres {
+    db_pool: Arc<PgPoolSquad>,
+    booking_window: BookingWindow,
+}
+
+impl BookingWindowPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>, booking_window: BookingWindow) -> Self {
+        Self {
+            db_pool,
+            booking_window,
+        }
+    }
+}
+
+impl BookingWindowRepository for BookingWindowPostgres {
+    async fn get_doctor_booking_window(
+        &self,
+        doctor_id: i32,
+    ) -> DomainResult<GetDoctorBookingWindowResponseModel> {
+        let mut conn = self.db_pool.get().await?;
+
+        let doctor_booking_window =
+            DoctorBookingWindowDao::get_by_doctor_id(&mut conn, doctor_id).await?;
+        let effective_booking_window = self
+            .booking_window
+            .with_doctor_override(doctor_booking_window.as_ref());
+
+        Ok(GetDoctorBookingWindowResponseModel {
+            doctor_booking_window,
+            effective_booking_window,
+        })
+    }
+
+    async fn set_doctor_booking_window(
+        &self,
+        set_doctor_booking_window_entity: SetDoctorBookingWindowEntity,
+    ) -> DomainResult<GetDoctorBookingWindowResponseModel> {
+        let mut conn = self.db_pool.get().await?;
+
+        let doctor_booking_window =
+            DoctorBookingWindowDao::set(&mut conn, set_doctor_booking_window_entity).await?;
+        let effective_booking_window = self
+            .booking_window
+            .with_doctor_override(Some(&doctor_booking_window));
+
+        Ok(GetDoctorBookingWindowResponseModel {
+            doctor_booking_window: Some(doctor_booking_window),
+            effective_booking_window,
+        })
+    }
+}
@@ -1,28 +1,53 @@
-use anyhow::Result;
-use diesel::ExpressionMethods;
+use diesel::prelude::*;
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
 use uuid::Uuid;
 
 use crate::{
-    domain::value_objects::appointment_status::AppointmentStatus,
-    infrastructure::postgres::schema::appointments,
+    domain::{
+        entities::appointments::ChangeAppointmentStatusEntity,
+        errors::{DomainError, DomainResult},
+    },
+    infrastructure::postgres::schema::{appointments, slots},
 };
 
 pub struct AppointmentLedgerDao;
 
 impl AppointmentLedgerDao {
+    /// Locks an appointment together with its slot and returns
+    /// `(slot_id, doctor_id)` of that slot.
+    pub async fn lock_with_slot_doctor_id(
+        conn: &mut AsyncPgConnection,
+        appointment_id: Uuid,
+    ) -> DomainResult<(Uuid, i32)> {
+        let result = appointments::table
+            .inner_join(slots::table.on(slots::id.eq(appointments::slot_id)))
+            .filter(appointments::id.eq(appointment_id))
+            .filter(appointments::deleted_at.is_null())
+            .filter(slots::deleted_at.is_null())
+            .select((slots::id, slots::doctor_id))
+            .for_update()
+            .first::<(Uuid, i32)>(conn)
+            .await
+            .optional()?
+            .ok_or_else(|| DomainError::not_found("Appointment not found"))?;
+
+        Ok(result)
+    }
+
     pub async fn change_appointment_status(
         conn: &mut AsyncPgConnection,
         appointment_id: Uuid,
-        appointment_status: AppointmentStatus,
-    ) -> Result<Uuid> {
+        change_appointment_status_entity: ChangeAppointmentStatusEntity,
+    ) -> DomainResult<Uuid> {
         let result = diesel::update(appointments::table)
             .filter(appointments::id.eq(appointment_id))
             .filter(appointments::deleted_at.is_null())
-            .set((appointments::status.eq(appointment_status.to_string()),))
+            .set(change_appointment_status_entity)
             .returning(appointments::id)
             .get_result::<Uuid>(conn)
-            .await?;
+            .await
+            .optional()?
+            .ok_or_else(|| DomainError::not_found("Appointment not found"))?;
 
         Ok(result)
     }
@@ -1,43 +1,90 @@
-use anyhow::Result;
-use diesel::ExpressionMethods;
 use diesel::dsl::insert_into;
+use diesel::prelude::*;
+use diesel::sql_types::{Bool, Integer};
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
 use uuid::Uuid;
 
 use crate::domain::entities::appointments::{EditAppointmentEntity, RescheduleAppointmentEntity};
+use crate::domain::errors::{DomainError, DomainResult};
 use crate::domain::value_objects::appointment_status::AppointmentStatus;
 use crate::{
     domain::entities::appointments::AddAppointmentEntity,
     infrastructure::postgres::schema::appointments,
 };
 
+/// First key of the advisory lock taken per patient while booking.
+const PATIENT_BOOKING_LOCK_NAMESPACE: i32 = 1;
+
 pub struct AppointmentOpsDao;
 
 impl AppointmentOpsDao {
-    // pub async fn lock(conn: &mut AsyncPgConnection, appointment_id: Uuid) -> Result<()> {
-    //     let n = diesel::sql_query(
-    //         r#"
-    //         SELECT 1
-    //           FROM appointments
-    //          WHERE id = $1
-    //            AND deleted_at IS NULL
-    //          FOR UPDATE
-    //     "#,
-    //     )
-    //     .bind::<SqlUuid, _>(appointment_id)
-    //     .execute(conn)
-    //     .await?;
-
-    //     if n == 0 {
-    //         return Err(anyhow!("Appointment not found"));
-    //     }
-    //     Ok(())
-    // }
+    /// Locks a patient's own appointment before it is changed and returns its
+    /// slot id. Only `Waiting` appointments can still be changed by the patient.
+    pub async fn lock_patient_appointment(
+        conn: &mut AsyncPgConnection,
+        appointment_id: Uuid,
+        patient_id: i32,
+    ) -> DomainResult<Uuid> {
+        let (owner_patient_id, slot_id, status) = appointments::table
+            .filter(appointments::id.eq(appointment_id))
+            .filter(appointments::deleted_at.is_null())
+            .select((
+                appointments::patient_id,
+                appointments::slot_id,
+                appointments::status,
+            ))
+            .for_update()
+            .first::<(i32, Uuid, String)>(conn)
+            .await
+            .optional()?
+            .ok_or_else(|| DomainError::not_found("Appointment not found"))?;
+
+        if owner_patient_id != patient_id {
+            return Err(DomainError::forbidden("Appointment does not belong to you"));
+        }
+
+        let status = status.parse::<AppointmentStatus>()?;
+        if status != AppointmentStatus::Waiting {
+            return Err(DomainError::invalid_transition(format!(
+                "Cannot change an appointment that is {}",
+                status
+            )));
+        }
+
+        Ok(slot_id)
+    }
+
+    /// Serializes bookings of the same patient until the transaction ends, so
+    /// the booking policy is checked against an up to date view of them.
+    pub async fn lock_patient(conn: &mut AsyncPgConnection, patient_id: i32) -> DomainResult<()> {
+        diesel::sql_query("SELECT pg_advisory_xact_lock($1, $2)")
+            .bind::<Integer, _>(PATIENT_BOOKING_LOCK_NAMESPACE)
+            .bind::<Integer, _>(patient_id)
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Like `lock_patient`, but gives up at once when another transaction
+    /// holds the lock, returns whether it was taken.
+    pub async fn try_lock_patient(
+        conn: &mut AsyncPgConnection,
+        patient_id: i32,
+    ) -> DomainResult<bool> {
+        let result = diesel::sql_query("SELECT pg_try_advisory_xact_lock($1, $2) AS locked")
+            .bind::<Integer, _>(PATIENT_BOOKING_LOCK_NAMESPACE)
+            .bind::<Integer, _>(patient_id)
+            .get_result::<AdvisoryLockRow>(conn)
+            .await?;
+
+        Ok(result.locked)
+    }
 
     pub async fn add(
         conn: &mut AsyncPgConnection,
         add_appointment_entity: AddAppointmentEntity,
-    ) -> Result<Uuid> {
+    ) -> DomainResult<Uuid> {
         let result = insert_into(appointments::table)
             .values(add_appointment_entity)
             .returning(appointments::id)
@@ -52,7 +99,7 @@ impl AppointmentOpsDao {
         appointment_id: Uuid,
         patient_id: i32,
         edit_appointment_entity: EditAppointmentEntity,
-    ) -> Result<Uuid> {
+    ) -> DomainResult<Uuid> {
 
         let result = diesel::update(appointments::table)
             .filter(appointments::id.eq(appointment_id))
@@ -62,7 +109,9 @@ impl AppointmentOpsDao {
             .set(edit_appointment_entity)
             .returning(appointments::id)
             .get_result::<Uuid>(conn)
-            .await?;
+            .await
+            .optional()?
+            .ok_or_else(|| DomainError::not_found("Appointment not found"))?;
 
         Ok(result)
     }
@@ -72,7 +121,7 @@ impl AppointmentOpsDao {
         appointment_id: Uuid,
         patient_id: i32,
         reschedule_appointment_entity: RescheduleAppointmentEntity,
-    ) -> Result<Uuid> {
+    ) -> DomainResult<Uuid> {
 
         let result = diesel::update(appointments::table)
             .filter(appointments::id.eq(appointment_id))
@@ -82,7 +131,28 @@ impl AppointmentOpsDao {
             .set(reschedule_appointment_entity)
             .returning(appointments::id)
             .get_result::<Uuid>(conn)
-            .await?;
+            .await
+            .optional()?
+            .ok_or_else(|| DomainError::not_found("Appointment not found"))?;
+
+        Ok(result)
+    }
+
+    /// Moves an appointment to another slot on behalf of the slot's doctor.
+    pub async fn move_to_slot(
+        conn: &mut AsyncPgConnection,
+        appointment_id: Uuid,
+        reschedule_appointment_entity: RescheduleAppointmentEntity,
+    ) -> DomainResult<Uuid> {
+        let result = diesel::update(appointments::table)
+            .filter(appointments::id.eq(appointment_id))
+            .filter(appointments::deleted_at.is_null())
+            .set(reschedule_appointment_entity)
+            .returning(appointments::id)
+            .get_result::<Uuid>(conn)
+            .await
+            .optional()?
+            .ok_or_else(|| DomainError::not_found("Appointment not found"))?;
 
         Ok(result)
     }
@@ -91,16 +161,69 @@ impl AppointmentOpsDao {
         conn: &mut AsyncPgConnection,
         appointment_id: Uuid,
         patient_id: i32,
-    ) -> Result<()> {
+    ) -> DomainResult<()> {
         diesel::update(appointments::table)
             .filter(appointments::id.eq(appointment_id))
             .filter(appointments::patient_id.eq(patient_id))
             .filter(appointments::deleted_at.is_null())
             .filter(appointments::status.eq(AppointmentStatus::Waiting.to_string()))
             .set((appointments::deleted_at.eq(chrono::Utc::now().naive_utc()),))
-            .execute(conn)
-            .await?;
+            .returning(appointments::id)
+            .get_result::<Uuid>(conn)
+            .await
+            .optional()?
+            .ok_or_else(|| DomainError::not_found("Appointment not found"))?;
 
         Ok(())
     }
+
+    /// Locks a removed appointment before it is restored and returns
+    /// `(patient_id, slot_id, status)` of it.
+    pub async fn lock_removed_appointment(
+        conn: &mut AsyncPgConnection,
+        appointment_id: Uuid,
+    ) -> DomainResult<(i32, Uuid, AppointmentStatus)> {
+        let (patient_id, slot_id, status) = appointments::table
+            .filter(appointments::id.eq(appointment_id))
+            .filter(appointments::deleted_at.is_not_null())
+            .select((
+                appointments::patient_id,
+                appointments::slot_id,
+                appointments::status,
+            ))
+            .for_update()
+            .first::<(i32, Uuid, String)>(conn)
+            .await
+            .optional()?
+            .ok_or_else(|| DomainError::not_found("Removed appointment not found"))?;
+
+        Ok((patient_id, slot_id, status.parse::<AppointmentStatus>()?))
+    }
+
+    pub async fn restore(
+        conn: &mut AsyncPgConnection,
+        appointment_id: Uuid,
+        current_time: chrono::NaiveDateTime,
+    ) -> DomainResult<Uuid> {
+        let result = diesel::update(appointments::table)
+            .filter(appointments::id.eq(appointment_id))
+            .filter(appointments::deleted_at.is_not_null())
+            .set((
+                appointments::deleted_at.eq(None::<chrono::NaiveDateTime>),
+                appointments::updated_at.eq(current_time),
+            ))
+            .returning(appointments::id)
+            .get_result::<Uuid>(conn)
+            .await
+            .optional()?
+            .ok_or_else(|| DomainError::not_found("Removed appointment not found"))?;
+
+        Ok(result)
+    }
+}
+
+#[derive(QueryableByName)]
+struct AdvisoryLockRow {
+    #[diesel(sql_type = Bool)]
+    locked: bool,
 }
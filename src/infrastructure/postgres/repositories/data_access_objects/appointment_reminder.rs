@@ -0,0 +1,135 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use diesel::{
+    dsl::{exists, insert_into, not},
+    prelude::*,
+    upsert::excluded,
+};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        entities::{
+            appointment_reminders::{AddAppointmentReminderEntity, AppointmentReminderEntity},
+            schedule_view::ScheduleViewEntity,
+        },
+        errors::DomainResult,
+        value_objects::appointment_status::OPEN_APPOINTMENT_STATUSES,
+    },
+    infrastructure::postgres::schema::{appointment_reminders, appointments, slots},
+};
+
+pub struct AppointmentReminderDao;
+
+impl AppointmentReminderDao {
+    pub async fn get_due(
+        conn: &mut AsyncPgConnection,
+        offset_minutes: i32,
+        after: DateTime<Utc>,
+        until: DateTime<Utc>,
+        current_time: NaiveDateTime,
+        limit: i64,
+    ) -> DomainResult<Vec<ScheduleViewEntity>> {
+        let open_statuses = OPEN_APPOINTMENT_STATUSES
+            .iter()
+            .map(|status| status.to_string())
+            .collect::<Vec<_>>();
+
+        let rows = appointments::table
+            .inner_join(slots::table.on(slots::id.eq(appointments::slot_id)))
+            .filter(appointments::deleted_at.is_null())
+            .filter(slots::deleted_at.is_null())
+            .filter(appointments::status.eq_any(open_statuses))
+            .filter(slots::start_time.gt(after))
+            .filter(slots::start_time.le(until))
+            .filter(not(exists(
+                appointment_reminders::table
+                    .filter(appointment_reminders::appointment_id.eq(appointments::id))
+                    .filter(appointment_reminders::offset_minutes.eq(offset_minutes))
+                    .filter(appointment_reminders::start_time.eq(slots::start_time))
+                    .filter(
+                        appointment_reminders::sent_at
+                            .is_not_null()
+                            .or(appointment_reminders::lease_until.gt(current_time)),
+                    ),
+            )))
+            .select((
+                appointments::id,
+                appointments::slot_id,
+                appointments::patient_id,
+                appointments::patient_abnormal_symptom,
+                appointments::patient_is_missed_medication,
+                appointments::patient_blood_test_status,
+                appointments::patient_is_overdue_medication,
+                appointments::patient_is_partner_hiv_positive,
+                appointments::status,
+                slots::doctor_id,
+                slots::start_time,
+                slots::end_time,
+                appointments::cancellation_reason,
+                appointments::cancelled_at,
+            ))
+            .order((slots::start_time.asc(), appointments::created_at.asc()))
+            .limit(limit)
+            .load::<ScheduleViewEntity>(conn)
+            .await?;
+
+        Ok(rows)
+    }
+
+    /// Relies on the unique constraint, so only one caller wins a reminder. An
+    /// unsent reminder whose lease ran out by `created_at` gets the new lease.
+    pub async fn claim(
+        conn: &mut AsyncPgConnection,
+        add_appointment_reminder_entity: AddAppointmentReminderEntity,
+    ) -> DomainResult<Option<AppointmentReminderEntity>> {
+        // `filter` of an upsert is its `DO UPDATE ... WHERE`, which `QueryDsl`
+        // does not provide.
+        use diesel::query_dsl::methods::FilterDsl;
+
+        let current_time = add_appointment_reminder_entity.created_at;
+
+        let result = insert_into(appointment_reminders::table)
+            .values(add_appointment_reminder_entity)
+            .on_conflict((
+                appointment_reminders::appointment_id,
+                appointment_reminders::offset_minutes,
+                appointment_reminders::start_time,
+            ))
+            .do_update()
+            .set(
+                appointment_reminders::lease_until.eq(excluded(appointment_reminders::lease_until)),
+            )
+            .filter(appointment_reminders::sent_at.is_null())
+            .filter(appointment_reminders::lease_until.le(current_time))
+            .returning(AppointmentReminderEntity::as_returning())
+            .get_result::<AppointmentReminderEntity>(conn)
+            .await
+            .optional()?;
+
+        Ok(result)
+    }
+
+    pub async fn mark_sent(
+        conn: &mut AsyncPgConnection,
+        id: Uuid,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<()> {
+        diesel::update(appointment_reminders::table)
+            .filter(appointment_reminders::id.eq(id))
+            .set(appointment_reminders::sent_at.eq(current_time))
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn remove(conn: &mut AsyncPgConnection, id: Uuid) -> DomainResult<()> {
+        diesel::delete(appointment_reminders::table)
+            .filter(appointment_reminders::id.eq(id))
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use chrono::NaiveDateTime;
+use diesel::{dsl::insert_into, prelude::*};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        entities::appointment_status_history::AppointmentStatusHistoryEntity, errors::DomainResult,
+        value_objects::appointment_history_model::AppointmentHistoryRecord,
+    },
+    infrastructure::postgres::schema::appointment_status_history,
+};
+
+pub struct AppointmentStatusHistoryDao;
+
+impl AppointmentStatusHistoryDao {
+    pub async fn add(
+        conn: &mut AsyncPgConnection,
+        appointment_id: Uuid,
+        appointment_history_record: AppointmentHistoryRecord,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<Uuid> {
+        let result = insert_into(appointment_status_history::table)
+            .values(appointment_history_record.to_entity(appointment_id, current_time))
+            .returning(appointment_status_history::id)
+            .get_result::<Uuid>(conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    pub async fn get_appointment_history(
+        conn: &mut AsyncPgConnection,
+        appointment_id: Uuid,
+    ) -> DomainResult<Vec<AppointmentStatusHistoryEntity>> {
+        let result = appointment_status_history::table
+            .filter(appointment_status_history::appointment_id.eq(appointment_id))
+            .order((
+                appointment_status_history::created_at.asc(),
+                appointment_status_history::id.asc(),
+            ))
+            .load::<AppointmentStatusHistoryEntity>(conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    /// The status the appointment was in before it last moved into `status`
+    /// from one of `previous_statuses`.
+    pub async fn get_status_before(
+        conn: &mut AsyncPgConnection,
+        appointment_id: Uuid,
+        status: String,
+        previous_statuses: Vec<String>,
+    ) -> DomainResult<Option<String>> {
+        let result = appointment_status_history::table
+            .filter(appointment_status_history::appointment_id.eq(appointment_id))
+            .filter(appointment_status_history::new_status.eq(status))
+            .filter(appointment_status_history::previous_status.eq_any(previous_statuses))
+            .order((
+                appointment_status_history::created_at.desc(),
+                appointment_status_history::id.desc(),
+            ))
+            .select(appointment_status_history::previous_status)
+            .first::<Option<String>>(conn)
+            .await
+            .optional()?
+            .flatten();
+
+        Ok(result)
+    }
+}
@@ -1,36 +1,101 @@
-use anyhow::Result;
+use chrono::{DateTime, Utc};
 use diesel::prelude::*;
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
 use uuid::Uuid;
 
-use crate::infrastructure::postgres::schema::appointments;
+use crate::{
+    domain::{
+        entities::appointments::{PatientOpenAppointmentEntity, SlotAppointmentEntity},
+        errors::{DomainError, DomainResult},
+        value_objects::appointment_status::{AppointmentStatus, OPEN_APPOINTMENT_STATUSES},
+    },
+    infrastructure::postgres::schema::{appointments, slots},
+};
 
 pub struct AppointmentViewingDao;
 
 impl AppointmentViewingDao {
-    pub async fn get_slot_id_by_appointment_id(
+    pub async fn get_appointment_status_by_appointment_id(
         conn: &mut AsyncPgConnection,
         appointment_id: Uuid,
-    ) -> Result<Uuid> {
+    ) -> DomainResult<String> {
         let result = appointments::table
             .filter(appointments::deleted_at.is_null())
             .filter(appointments::id.eq(appointment_id))
-            .select(appointments::slot_id)
-            .first::<Uuid>(conn)
+            .select(appointments::status)
+            .first::<String>(conn)
+            .await
+            .optional()?
+            .ok_or_else(|| DomainError::not_found("Appointment not found"))?;
+
+        Ok(result)
+    }
+
+    /// Every appointment counted in the slot's occupancy, most recently booked first.
+    pub async fn get_slot_appointments_by_slot_id(
+        conn: &mut AsyncPgConnection,
+        slot_id: Uuid,
+    ) -> DomainResult<Vec<SlotAppointmentEntity>> {
+        let result = appointments::table
+            .filter(appointments::deleted_at.is_null())
+            .filter(appointments::slot_id.eq(slot_id))
+            .filter(appointments::status.ne(AppointmentStatus::Cancelled.to_string()))
+            .select((
+                appointments::id,
+                appointments::patient_id,
+                appointments::status,
+            ))
+            .order((appointments::created_at.desc(), appointments::id.desc()))
+            .load::<SlotAppointmentEntity>(conn)
             .await?;
 
         Ok(result)
     }
 
-    pub async fn get_appointment_status_by_appointment_id(
+    /// Returns `(patient_id, doctor_id)` of an appointment, including removed ones.
+    pub async fn get_patient_id_and_doctor_id_by_appointment_id(
         conn: &mut AsyncPgConnection,
         appointment_id: Uuid,
-    ) -> Result<String> {
+    ) -> DomainResult<(i32, i32)> {
         let result = appointments::table
-            .filter(appointments::deleted_at.is_null())
+            .inner_join(slots::table.on(slots::id.eq(appointments::slot_id)))
             .filter(appointments::id.eq(appointment_id))
-            .select(appointments::status)
-            .first::<String>(conn)
+            .select((appointments::patient_id, slots::doctor_id))
+            .first::<(i32, i32)>(conn)
+            .await
+            .optional()?
+            .ok_or_else(|| DomainError::not_found("Appointment not found"))?;
+
+        Ok(result)
+    }
+
+    /// Open appointments of a patient in slots that have not ended yet.
+    pub async fn get_open_patient_appointments(
+        conn: &mut AsyncPgConnection,
+        patient_id: i32,
+        current_time: DateTime<Utc>,
+    ) -> DomainResult<Vec<PatientOpenAppointmentEntity>> {
+        let open_statuses = OPEN_APPOINTMENT_STATUSES
+            .iter()
+            .map(|status| status.to_string())
+            .collect::<Vec<_>>();
+
+        let result = appointments::table
+            .inner_join(slots::table.on(slots::id.eq(appointments::slot_id)))
+            .filter(appointments::deleted_at.is_null())
+            .filter(slots::deleted_at.is_null())
+            .filter(appointments::patient_id.eq(patient_id))
+            .filter(appointments::status.eq_any(open_statuses))
+            .filter(slots::end_time.gt(current_time))
+            .select((
+                appointments::id,
+                appointments::slot_id,
+                slots::doctor_id,
+                slots::start_time,
+                slots::end_time,
+            ))
+            .order(slots::start_time.asc())
+            .load::<PatientOpenAppointmentEntity>(conn)
             .await?;
 
         Ok(result)
@@ -0,0 +1,54 @@
+use diesel::{dsl::insert_into, prelude::*, upsert::excluded};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+use crate::{
+    domain::{
+        entities::doctor_booking_windows::{
+            DoctorBookingWindowEntity, SetDoctorBookingWindowEntity,
+        },
+        errors::DomainResult,
+    },
+    infrastructure::postgres::schema::doctor_booking_windows,
+};
+
+pub struct DoctorBookingWindowDao;
+
+impl DoctorBookingWindowDao {
+    pub async fn get_by_doctor_id(
+        conn: &mut AsyncPgConnection,
+        doctor_id: i32,
+    ) -> DomainResult<Option<DoctorBookingWindowEntity>> {
+        let result = doctor_booking_windows::table
+            .filter(doctor_booking_windows::doctor_id.eq(doctor_id))
+            .select(DoctorBookingWindowEntity::as_select())
+            .first::<DoctorBookingWindowEntity>(conn)
+            .await
+            .optional()?;
+
+        Ok(result)
+    }
+
+    pub async fn set(
+        conn: &mut AsyncPgConnection,
+        set_doctor_booking_window_entity: SetDoctorBookingWindowEntity,
+    ) -> DomainResult<DoctorBookingWindowEntity> {
+        let result = insert_into(doctor_booking_windows::table)
+            .values(&set_doctor_booking_window_entity)
+            .on_conflict(doctor_booking_windows::doctor_id)
+            .do_update()
+            .set((
+                doctor_booking_windows::min_booking_lead_minutes
+                    .eq(excluded(doctor_booking_windows::min_booking_lead_minutes)),
+                doctor_booking_windows::max_booking_horizon_days
+                    .eq(excluded(doctor_booking_windows::max_booking_horizon_days)),
+                doctor_booking_windows::min_change_notice_minutes
+                    .eq(excluded(doctor_booking_windows::min_change_notice_minutes)),
+                doctor_booking_windows::updated_at.eq(excluded(doctor_booking_windows::updated_at)),
+            ))
+            .returning(DoctorBookingWindowEntity::as_returning())
+            .get_result::<DoctorBookingWindowEntity>(conn)
+            .await?;
+
+        Ok(result)
+    }
+}
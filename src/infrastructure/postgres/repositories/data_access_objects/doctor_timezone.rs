@@ -0,0 +1,47 @@
+use diesel::{dsl::insert_into, prelude::*, upsert::excluded};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+use crate::{
+    domain::{
+        entities::doctor_timezones::{DoctorTimezoneEntity, SetDoctorTimezoneEntity},
+        errors::DomainResult,
+    },
+    infrastructure::postgres::schema::doctor_timezones,
+};
+
+pub struct DoctorTimezoneDao;
+
+impl DoctorTimezoneDao {
+    pub async fn get_by_doctor_id(
+        conn: &mut AsyncPgConnection,
+        doctor_id: i32,
+    ) -> DomainResult<Option<DoctorTimezoneEntity>> {
+        let result = doctor_timezones::table
+            .filter(doctor_timezones::doctor_id.eq(doctor_id))
+            .select(DoctorTimezoneEntity::as_select())
+            .first::<DoctorTimezoneEntity>(conn)
+            .await
+            .optional()?;
+
+        Ok(result)
+    }
+
+    pub async fn set(
+        conn: &mut AsyncPgConnection,
+        set_doctor_timezone_entity: SetDoctorTimezoneEntity,
+    ) -> DomainResult<DoctorTimezoneEntity> {
+        let result = insert_into(doctor_timezones::table)
+            .values(&set_doctor_timezone_entity)
+            .on_conflict(doctor_timezones::doctor_id)
+            .do_update()
+            .set((
+                doctor_timezones::timezone.eq(excluded(doctor_timezones::timezone)),
+                doctor_timezones::updated_at.eq(excluded(doctor_timezones::updated_at)),
+            ))
+            .returning(DoctorTimezoneEntity::as_returning())
+            .get_result::<DoctorTimezoneEntity>(conn)
+            .await?;
+
+        Ok(result)
+    }
+}
@@ -0,0 +1,76 @@
+use chrono::NaiveDateTime;
+use diesel::{
+    dsl::{delete, insert_into},
+    prelude::*,
+    result::{DatabaseErrorKind, Error as DieselError},
+};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        entities::idempotency_keys::{AddIdempotencyKeyEntity, IdempotencyKeyEntity},
+        errors::{DomainError, DomainResult},
+        value_objects::idempotency_model::IdempotencyKey,
+    },
+    infrastructure::postgres::schema::idempotency_keys,
+};
+
+pub struct IdempotencyKeyDao;
+
+impl IdempotencyKeyDao {
+    pub async fn get(
+        conn: &mut AsyncPgConnection,
+        patient_id: i32,
+        idempotency_key: &IdempotencyKey,
+        expired_before: NaiveDateTime,
+    ) -> DomainResult<Option<IdempotencyKeyEntity>> {
+        let result = idempotency_keys::table
+            .filter(idempotency_keys::patient_id.eq(patient_id))
+            .filter(idempotency_keys::created_at.ge(expired_before))
+            .filter(idempotency_keys::operation.eq(idempotency_key.operation.to_string()))
+            .filter(idempotency_keys::idempotency_key.eq(&idempotency_key.key))
+            .select(IdempotencyKeyEntity::as_select())
+            .first::<IdempotencyKeyEntity>(conn)
+            .await
+            .optional()?;
+
+        Ok(result)
+    }
+
+    /// Removes the expired keys of `patient_id`, returns how many were removed.
+    pub async fn delete_expired(
+        conn: &mut AsyncPgConnection,
+        patient_id: i32,
+        expired_before: NaiveDateTime,
+    ) -> DomainResult<usize> {
+        let result = delete(idempotency_keys::table)
+            .filter(idempotency_keys::patient_id.eq(patient_id))
+            .filter(idempotency_keys::created_at.lt(expired_before))
+            .execute(conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    pub async fn add(
+        conn: &mut AsyncPgConnection,
+        add_idempotency_key_entity: AddIdempotencyKeyEntity,
+    ) -> DomainResult<Uuid> {
+        let result = insert_into(idempotency_keys::table)
+            .values(add_idempotency_key_entity)
+            .returning(idempotency_keys::id)
+            .get_result::<Uuid>(conn)
+            .await
+            .map_err(|e| match e {
+                DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+                    DomainError::conflict(
+                        "A request with this Idempotency-Key is already being processed",
+                    )
+                }
+                e => e.into(),
+            })?;
+
+        Ok(result)
+    }
+}
@@ -1,6 +1,18 @@
 pub mod appointment_ledger;
 pub mod appointment_ops;
+pub mod appointment_reminder;
+pub mod appointment_status_history;
 pub mod appointment_viewing;
+pub mod doctor_booking_window;
+pub mod doctor_timezone;
+pub mod idempotency_key;
+pub mod outbox_event;
 pub mod schedule_viewing;
+pub mod service_api_key;
 pub mod slot_ops;
+pub mod slot_template;
 pub mod slot_viewing;
+pub mod staff_action;
+pub mod waitlist_entry;
+pub mod webhook_delivery;
+pub mod webhook_subscription;
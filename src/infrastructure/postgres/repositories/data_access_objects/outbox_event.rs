@@ -0,0 +1,128 @@
+use chrono::NaiveDateTime;
+use diesel::{dsl::insert_into, prelude::*, sql_types::Text};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        entities::outbox_events::OutboxEventEntity, errors::DomainResult,
+        value_objects::outbox_model::DomainEvent,
+    },
+    infrastructure::postgres::schema::outbox_events,
+};
+
+pub struct OutboxEventDao;
+
+impl OutboxEventDao {
+    /// Must be called in the same transaction as the change `domain_event`
+    /// describes, so the event is published if and only if it is committed.
+    pub async fn add(
+        conn: &mut AsyncPgConnection,
+        domain_event: DomainEvent,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<Uuid> {
+        let result = insert_into(outbox_events::table)
+            .values(domain_event.to_entity(current_time)?)
+            .returning(outbox_events::id)
+            .get_result::<Uuid>(conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    /// Locks up to `limit` pending events that are due, oldest first, skipping
+    /// the ones another dispatcher already holds.
+    pub async fn lock_due(
+        conn: &mut AsyncPgConnection,
+        limit: i64,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<Vec<Uuid>> {
+        let result = outbox_events::table
+            .filter(outbox_events::dispatched_at.is_null())
+            .filter(outbox_events::failed_at.is_null())
+            .filter(outbox_events::next_attempt_at.le(current_time))
+            .order(outbox_events::created_at.asc())
+            .limit(limit)
+            .select(outbox_events::id)
+            .for_update()
+            .skip_locked()
+            .load::<Uuid>(conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    /// Pushes `next_attempt_at` of locked events to `lease_until`, so they are
+    /// not picked up again while they are being delivered.
+    pub async fn lease(
+        conn: &mut AsyncPgConnection,
+        ids: &[Uuid],
+        lease_until: NaiveDateTime,
+    ) -> DomainResult<Vec<OutboxEventEntity>> {
+        let result = diesel::update(outbox_events::table)
+            .filter(outbox_events::id.eq_any(ids))
+            .set(outbox_events::next_attempt_at.eq(lease_until))
+            .returning(OutboxEventEntity::as_returning())
+            .get_results::<OutboxEventEntity>(conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    pub async fn mark_dispatched(
+        conn: &mut AsyncPgConnection,
+        id: Uuid,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<()> {
+        diesel::update(outbox_events::table)
+            .filter(outbox_events::id.eq(id))
+            .set((
+                outbox_events::attempts.eq(outbox_events::attempts + 1),
+                outbox_events::dispatched_at.eq(current_time),
+                outbox_events::last_error.eq(None::<String>),
+            ))
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Records a failed delivery. The event is tried again at
+    /// `next_attempt_at`, or never again when it is `None`.
+    pub async fn mark_failed(
+        conn: &mut AsyncPgConnection,
+        id: Uuid,
+        error: &str,
+        next_attempt_at: Option<NaiveDateTime>,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<()> {
+        diesel::update(outbox_events::table)
+            .filter(outbox_events::id.eq(id))
+            .set((
+                outbox_events::attempts.eq(outbox_events::attempts + 1),
+                outbox_events::last_error.eq(error),
+                outbox_events::next_attempt_at.eq(next_attempt_at.unwrap_or(current_time)),
+                outbox_events::failed_at.eq(next_attempt_at.is_none().then_some(current_time)),
+            ))
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Sends `payload` to the listeners of `channel`. Postgres delivers it
+    /// when the surrounding transaction, if any, commits.
+    pub async fn notify(
+        conn: &mut AsyncPgConnection,
+        channel: &str,
+        payload: &str,
+    ) -> DomainResult<()> {
+        diesel::sql_query("SELECT pg_notify($1, $2)")
+            .bind::<Text, _>(channel)
+            .bind::<Text, _>(payload)
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -1,20 +1,29 @@
-use anyhow::Result;
+use diesel::helper_types::{Eq, InnerJoinOn, IntoBoxed};
+use diesel::pg::Pg;
 use diesel::prelude::*;
 use diesel_async::AsyncPgConnection;
 use diesel_async::RunQueryDsl;
+use uuid::Uuid;
 
 use crate::{
-    domain::entities::schedule_view::ScheduleViewEntity,
+    domain::{
+        entities::schedule_view::ScheduleViewEntity,
+        errors::{DomainError, DomainResult},
+        value_objects::admin_model::AppointmentFilter,
+    },
     infrastructure::postgres::schema::{appointments, slots},
 };
 
+type AppointmentsWithSlots =
+    InnerJoinOn<appointments::table, slots::table, Eq<slots::id, appointments::slot_id>>;
+
 pub struct ScheduleViewingDao;
 
 impl ScheduleViewingDao {
     pub async fn get_patient_schedules(
         conn: &mut AsyncPgConnection,
         patient_id: i32,
-    ) -> Result<Vec<ScheduleViewEntity>> {
+    ) -> DomainResult<Vec<ScheduleViewEntity>> {
         let rows = appointments::table
             .inner_join(slots::table.on(slots::id.eq(appointments::slot_id)))
             .filter(appointments::deleted_at.is_null())
@@ -33,18 +42,20 @@ impl ScheduleViewingDao {
                 slots::doctor_id,
                 slots::start_time,
                 slots::end_time,
+                appointments::cancellation_reason,
+                appointments::cancelled_at,
             ))
             .order((slots::start_time.asc(), appointments::created_at.asc()))
             .load::<ScheduleViewEntity>(conn)
             .await?;
 
-        Ok(rows.into_iter().map(ScheduleViewEntity::from).collect())
+        Ok(rows)
     }
 
     pub async fn get_doctor_schedules(
         conn: &mut AsyncPgConnection,
         doctor_id: i32,
-    ) -> Result<Vec<ScheduleViewEntity>> {
+    ) -> DomainResult<Vec<ScheduleViewEntity>> {
         let rows = appointments::table
             .inner_join(slots::table.on(slots::id.eq(appointments::slot_id)))
             .filter(appointments::deleted_at.is_null())
@@ -63,11 +74,122 @@ impl ScheduleViewingDao {
                 slots::doctor_id,
                 slots::start_time,
                 slots::end_time,
+                appointments::cancellation_reason,
+                appointments::cancelled_at,
             ))
             .order((slots::start_time.asc(), appointments::created_at.asc()))
             .load::<ScheduleViewEntity>(conn)
             .await?;
 
-        Ok(rows.into_iter().map(ScheduleViewEntity::from).collect())
+        Ok(rows)
+    }
+
+    pub async fn get_schedule(
+        conn: &mut AsyncPgConnection,
+        appointment_id: Uuid,
+    ) -> DomainResult<ScheduleViewEntity> {
+        let row = appointments::table
+            .inner_join(slots::table.on(slots::id.eq(appointments::slot_id)))
+            .filter(appointments::deleted_at.is_null())
+            .filter(slots::deleted_at.is_null())
+            .filter(appointments::id.eq(appointment_id))
+            .select((
+                appointments::id,
+                appointments::slot_id,
+                appointments::patient_id,
+                appointments::patient_abnormal_symptom,
+                appointments::patient_is_missed_medication,
+                appointments::patient_blood_test_status,
+                appointments::patient_is_overdue_medication,
+                appointments::patient_is_partner_hiv_positive,
+                appointments::status,
+                slots::doctor_id,
+                slots::start_time,
+                slots::end_time,
+                appointments::cancellation_reason,
+                appointments::cancelled_at,
+            ))
+            .first::<ScheduleViewEntity>(conn)
+            .await
+            .optional()?
+            .ok_or_else(|| DomainError::not_found("Appointment not found"))?;
+
+        Ok(row)
+    }
+
+    fn filtered_appointments(
+        appointment_filter: &AppointmentFilter,
+    ) -> IntoBoxed<'static, AppointmentsWithSlots, Pg> {
+        let mut query = appointments::table
+            .inner_join(slots::table.on(slots::id.eq(appointments::slot_id)))
+            .filter(appointments::deleted_at.is_null())
+            .filter(slots::deleted_at.is_null())
+            .into_boxed();
+
+        if let Some(doctor_id) = appointment_filter.doctor_id {
+            query = query.filter(slots::doctor_id.eq(doctor_id));
+        }
+        if let Some(patient_id) = appointment_filter.patient_id {
+            query = query.filter(appointments::patient_id.eq(patient_id));
+        }
+        if let Some(status) = appointment_filter.status {
+            query = query.filter(appointments::status.eq(status.to_string()));
+        }
+        if let Some(start_from) = appointment_filter.start_from {
+            query = query.filter(slots::start_time.ge(start_from));
+        }
+        if let Some(start_to) = appointment_filter.start_to {
+            query = query.filter(slots::start_time.le(start_to));
+        }
+
+        query
+    }
+
+    pub async fn count_appointments(
+        conn: &mut AsyncPgConnection,
+        appointment_filter: &AppointmentFilter,
+    ) -> DomainResult<i64> {
+        let result = Self::filtered_appointments(appointment_filter)
+            .count()
+            .get_result::<i64>(conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    /// Appointments of every doctor matching `appointment_filter`, one page at a
+    /// time.
+    pub async fn get_appointments(
+        conn: &mut AsyncPgConnection,
+        appointment_filter: &AppointmentFilter,
+    ) -> DomainResult<Vec<ScheduleViewEntity>> {
+        let rows = Self::filtered_appointments(appointment_filter)
+            .select((
+                appointments::id,
+                appointments::slot_id,
+                appointments::patient_id,
+                appointments::patient_abnormal_symptom,
+                appointments::patient_is_missed_medication,
+                appointments::patient_blood_test_status,
+                appointments::patient_is_overdue_medication,
+                appointments::patient_is_partner_hiv_positive,
+                appointments::status,
+                slots::doctor_id,
+                slots::start_time,
+                slots::end_time,
+                appointments::cancellation_reason,
+                appointments::cancelled_at,
+            ))
+            .order((
+                slots::start_time.asc(),
+                appointments::created_at.asc(),
+                appointments::id.asc(),
+            ))
+            .limit(appointment_filter.limit)
+            .offset(appointment_filter.offset()?)
+            .load::<ScheduleViewEntity>(conn)
+            .await?;
+
+        Ok(rows)
     }
 }
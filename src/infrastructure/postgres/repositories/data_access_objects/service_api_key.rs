@@ -0,0 +1,61 @@
+use chrono::NaiveDateTime;
+use diesel::{dsl::insert_into, prelude::*};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+use crate::{
+    domain::{
+        entities::service_api_keys::{AddServiceApiKeyEntity, ServiceApiKeyEntity},
+        errors::{DomainError, DomainResult},
+    },
+    infrastructure::postgres::schema::service_api_keys,
+};
+
+pub struct ServiceApiKeyDao;
+
+impl ServiceApiKeyDao {
+    pub async fn add(
+        conn: &mut AsyncPgConnection,
+        add_service_api_key_entity: AddServiceApiKeyEntity,
+    ) -> DomainResult<i32> {
+        let result = insert_into(service_api_keys::table)
+            .values(add_service_api_key_entity)
+            .returning(service_api_keys::id)
+            .get_result::<i32>(conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    pub async fn get_active_by_key_prefix(
+        conn: &mut AsyncPgConnection,
+        key_prefix: &str,
+    ) -> DomainResult<Option<ServiceApiKeyEntity>> {
+        let result = service_api_keys::table
+            .filter(service_api_keys::key_prefix.eq(key_prefix))
+            .filter(service_api_keys::revoked_at.is_null())
+            .select(ServiceApiKeyEntity::as_select())
+            .first::<ServiceApiKeyEntity>(conn)
+            .await
+            .optional()?;
+
+        Ok(result)
+    }
+
+    pub async fn revoke(
+        conn: &mut AsyncPgConnection,
+        key_prefix: &str,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<()> {
+        diesel::update(service_api_keys::table)
+            .filter(service_api_keys::key_prefix.eq(key_prefix))
+            .filter(service_api_keys::revoked_at.is_null())
+            .set(service_api_keys::revoked_at.eq(current_time))
+            .returning(service_api_keys::id)
+            .get_result::<i32>(conn)
+            .await
+            .optional()?
+            .ok_or_else(|| DomainError::not_found("No active API key with this prefix"))?;
+
+        Ok(())
+    }
+}
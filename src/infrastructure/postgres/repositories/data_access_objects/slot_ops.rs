@@ -1,17 +1,16 @@
-use anyhow::{Result, anyhow};
-use diesel::query_dsl::methods::FilterDsl;
-use diesel::sql_types::Uuid as SqlUuid;
-use diesel::{ExpressionMethods, dsl::insert_into};
+use diesel::sql_types::{Integer, Uuid as SqlUuid};
+use diesel::{dsl::insert_into, prelude::*};
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
 use uuid::Uuid;
 
-use crate::domain::entities::slots::EditSlotEntity;
+use crate::domain::entities::slots::{EditSlotEntity, SlotEntity};
+use crate::domain::errors::{DomainError, DomainResult};
 use crate::{domain::entities::slots::AddSlotEntity, infrastructure::postgres::schema::slots};
 
 pub struct SlotOpsDao;
 
 impl SlotOpsDao {
-    pub async fn lock(conn: &mut AsyncPgConnection, slot_id: uuid::Uuid) -> Result<()> {
+    pub async fn lock(conn: &mut AsyncPgConnection, slot_id: uuid::Uuid) -> DomainResult<()> {
         let n = diesel::sql_query(
             r#"
             SELECT 1
@@ -26,12 +25,15 @@ impl SlotOpsDao {
         .await?;
 
         if n == 0 {
-            return Err(anyhow!("slot not found"));
+            return Err(DomainError::not_found("Slot not found"));
         }
         Ok(())
     }
 
-    pub async fn add(conn: &mut AsyncPgConnection, add_slot_entity: AddSlotEntity) -> Result<Uuid> {
+    pub async fn add(
+        conn: &mut AsyncPgConnection,
+        add_slot_entity: AddSlotEntity,
+    ) -> DomainResult<Uuid> {
         let result = insert_into(slots::table)
             .values(add_slot_entity)
             .returning(slots::id)
@@ -46,7 +48,7 @@ impl SlotOpsDao {
         slot_id: Uuid,
         doctor_id: i32,
         edit_slot_entity: EditSlotEntity,
-    ) -> Result<Uuid> {
+    ) -> DomainResult<Uuid> {
         let result = diesel::update(slots::table)
             .filter(slots::id.eq(slot_id))
             .filter(slots::doctor_id.eq(doctor_id))
@@ -54,61 +56,148 @@ impl SlotOpsDao {
             .set(edit_slot_entity)
             .returning(slots::id)
             .get_result::<Uuid>(conn)
-            .await?;
+            .await
+            .optional()?
+            .ok_or_else(|| DomainError::not_found("Slot not found"))?;
 
         Ok(result)
     }
 
-    pub async fn remove(conn: &mut AsyncPgConnection, slot_id: Uuid, doctor_id: i32) -> Result<()> {
+    pub async fn remove(
+        conn: &mut AsyncPgConnection,
+        slot_id: Uuid,
+        doctor_id: i32,
+    ) -> DomainResult<()> {
         diesel::update(slots::table)
             .filter(slots::id.eq(slot_id))
             .filter(slots::doctor_id.eq(doctor_id))
             .filter(slots::deleted_at.is_null())
             .set((slots::deleted_at.eq(chrono::Utc::now().naive_utc()),))
-            .execute(conn)
-            .await?;
+            .returning(slots::id)
+            .get_result::<Uuid>(conn)
+            .await
+            .optional()?
+            .ok_or_else(|| DomainError::not_found("Slot not found"))?;
 
         Ok(())
     }
 
-    pub async fn try_add_slot_appointment_count(
+    /// Locks a removed slot before it is restored.
+    pub async fn lock_removed(
         conn: &mut AsyncPgConnection,
-        id: Uuid,
-    ) -> Result<bool> {
-        let result = diesel::update(
-            slots::table
-                .filter(slots::id.eq(id))
-                .filter(slots::deleted_at.is_null())
-                .filter(slots::current_appointment_count.lt(slots::max_appointment_count)),
-        )
-        .set((
-            slots::current_appointment_count.eq(slots::current_appointment_count + 1),
-            slots::updated_at.eq(chrono::Utc::now().naive_utc()),
-        ))
-        .returning(slots::id)
-        .get_result::<Uuid>(conn)
-        .await;
-
-        match result {
-            Ok(_) => Ok(true),
-            Err(diesel::result::Error::NotFound) => Ok(false),
-            Err(e) => Err(e.into()),
-        }
+        slot_id: Uuid,
+    ) -> DomainResult<SlotEntity> {
+        let result = slots::table
+            .filter(slots::id.eq(slot_id))
+            .filter(slots::deleted_at.is_not_null())
+            .for_update()
+            .first::<SlotEntity>(conn)
+            .await
+            .optional()?
+            .ok_or_else(|| DomainError::not_found("Removed slot not found"))?;
+
+        Ok(result)
+    }
+
+    /// Puts a removed slot back. The `excl_slots_doctor_id_time_overlap`
+    /// constraint rejects it when another slot of the doctor took its time.
+    pub async fn restore(
+        conn: &mut AsyncPgConnection,
+        slot_id: Uuid,
+        current_time: chrono::NaiveDateTime,
+    ) -> DomainResult<Uuid> {
+        let result = diesel::update(slots::table)
+            .filter(slots::id.eq(slot_id))
+            .filter(slots::deleted_at.is_not_null())
+            .set((
+                slots::deleted_at.eq(None::<chrono::NaiveDateTime>),
+                slots::updated_at.eq(current_time),
+            ))
+            .returning(slots::id)
+            .get_result::<Uuid>(conn)
+            .await
+            .optional()?
+            .ok_or_else(|| DomainError::not_found("Removed slot not found"))?;
+
+        Ok(result)
     }
 
-    pub async fn dec_slot_appointment_count(conn: &mut AsyncPgConnection, id: Uuid) -> Result<()> {
-        diesel::update(
-            slots::table
-                .filter(slots::id.eq(id))
-                .filter(slots::deleted_at.is_null()),
+    /// Soft deletes every slot of the template that starts after `starts_after`
+    /// and has no appointment yet. Booked slots are left untouched.
+    pub async fn remove_unbooked_slots_by_template_id(
+        conn: &mut AsyncPgConnection,
+        template_id: Uuid,
+        starts_after: chrono::DateTime<chrono::Utc>,
+    ) -> DomainResult<Vec<Uuid>> {
+        let result = diesel::update(slots::table)
+            .filter(slots::template_id.eq(template_id))
+            .filter(slots::deleted_at.is_null())
+            .filter(slots::start_time.gt(starts_after))
+            .filter(slots::current_appointment_count.eq(0))
+            .set((slots::deleted_at.eq(chrono::Utc::now().naive_utc()),))
+            .returning(slots::id)
+            .get_results::<Uuid>(conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    /// Whether a slot locked with `lock` can take one more appointment.
+    ///
+    /// `current_appointment_count` itself is kept up to date by the
+    /// `trg_appointments_sync_slot_appointment_count` trigger.
+    pub async fn has_free_place(conn: &mut AsyncPgConnection, id: Uuid) -> DomainResult<bool> {
+        let result = slots::table
+            .filter(slots::id.eq(id))
+            .filter(slots::deleted_at.is_null())
+            .filter(slots::current_appointment_count.lt(slots::max_appointment_count))
+            .select(slots::id)
+            .first::<Uuid>(conn)
+            .await
+            .optional()?;
+
+        Ok(result.is_some())
+    }
+
+    /// Recounts the appointments holding a place in a slot and stores the result,
+    /// returning the new count, or `None` when it would exceed the slot's capacity.
+    pub async fn reset_appointment_count(
+        conn: &mut AsyncPgConnection,
+        id: Uuid,
+    ) -> DomainResult<Option<i32>> {
+        // Locked first so the recount below sees every appointment whose
+        // trigger already touched the slot.
+        diesel::sql_query("SELECT 1 FROM slots WHERE id = $1 FOR UPDATE")
+            .bind::<SqlUuid, _>(id)
+            .execute(conn)
+            .await?;
+
+        let result = diesel::sql_query(
+            r#"
+            UPDATE slots
+               SET current_appointment_count = actual.appointment_count
+              FROM (
+                    SELECT COUNT(*)::INTEGER AS appointment_count
+                      FROM appointments
+                     WHERE slot_id = $1
+                       AND appointment_holds_slot_place(status, deleted_at)
+                   ) AS actual
+             WHERE slots.id = $1
+               AND actual.appointment_count <= slots.max_appointment_count
+         RETURNING slots.current_appointment_count
+        "#,
         )
-        .set((
-            slots::current_appointment_count.eq(slots::current_appointment_count - 1),
-            slots::updated_at.eq(chrono::Utc::now().naive_utc()),
-        ))
-        .execute(conn)
-        .await?;
+        .bind::<SqlUuid, _>(id)
+        .get_result::<AppointmentCountRow>(conn)
+        .await
+        .optional()?;
 
-        Ok(())
+        Ok(result.map(|row| row.current_appointment_count))
     }
 }
+
+#[derive(QueryableByName)]
+struct AppointmentCountRow {
+    #[diesel(sql_type = Integer)]
+    current_appointment_count: i32,
+}
@@ -0,0 +1,124 @@
+use diesel::{dsl::insert_into, prelude::*};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        entities::slot_templates::{
+            AddSlotTemplateEntity, EditSlotTemplateEntity, SlotTemplateEntity,
+        },
+        errors::{DomainError, DomainResult},
+    },
+    infrastructure::postgres::schema::slot_templates,
+};
+
+pub struct SlotTemplateDao;
+
+impl SlotTemplateDao {
+    pub async fn lock(
+        conn: &mut AsyncPgConnection,
+        template_id: Uuid,
+        doctor_id: i32,
+    ) -> DomainResult<()> {
+        let owner_doctor_id = slot_templates::table
+            .filter(slot_templates::id.eq(template_id))
+            .filter(slot_templates::deleted_at.is_null())
+            .select(slot_templates::doctor_id)
+            .for_update()
+            .first::<i32>(conn)
+            .await
+            .optional()?
+            .ok_or_else(|| DomainError::not_found("Slot template not found"))?;
+
+        if owner_doctor_id != doctor_id {
+            return Err(DomainError::forbidden(
+                "Slot template does not belong to you",
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub async fn get_slot_template(
+        conn: &mut AsyncPgConnection,
+        template_id: Uuid,
+        doctor_id: i32,
+    ) -> DomainResult<SlotTemplateEntity> {
+        let result = slot_templates::table
+            .filter(slot_templates::id.eq(template_id))
+            .filter(slot_templates::doctor_id.eq(doctor_id))
+            .filter(slot_templates::deleted_at.is_null())
+            .first::<SlotTemplateEntity>(conn)
+            .await
+            .optional()?
+            .ok_or_else(|| DomainError::not_found("Slot template not found"))?;
+
+        Ok(result)
+    }
+
+    pub async fn get_doctor_slot_templates(
+        conn: &mut AsyncPgConnection,
+        doctor_id: i32,
+    ) -> DomainResult<Vec<SlotTemplateEntity>> {
+        let result = slot_templates::table
+            .filter(slot_templates::doctor_id.eq(doctor_id))
+            .filter(slot_templates::deleted_at.is_null())
+            .order(slot_templates::start_date.asc())
+            .load::<SlotTemplateEntity>(conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    pub async fn add(
+        conn: &mut AsyncPgConnection,
+        add_slot_template_entity: AddSlotTemplateEntity,
+    ) -> DomainResult<Uuid> {
+        let result = insert_into(slot_templates::table)
+            .values(add_slot_template_entity)
+            .returning(slot_templates::id)
+            .get_result::<Uuid>(conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    pub async fn edit(
+        conn: &mut AsyncPgConnection,
+        template_id: Uuid,
+        doctor_id: i32,
+        edit_slot_template_entity: EditSlotTemplateEntity,
+    ) -> DomainResult<Uuid> {
+        let result = diesel::update(slot_templates::table)
+            .filter(slot_templates::id.eq(template_id))
+            .filter(slot_templates::doctor_id.eq(doctor_id))
+            .filter(slot_templates::deleted_at.is_null())
+            .set(edit_slot_template_entity)
+            .returning(slot_templates::id)
+            .get_result::<Uuid>(conn)
+            .await
+            .optional()?
+            .ok_or_else(|| DomainError::not_found("Slot template not found"))?;
+
+        Ok(result)
+    }
+
+    pub async fn remove(
+        conn: &mut AsyncPgConnection,
+        template_id: Uuid,
+        doctor_id: i32,
+    ) -> DomainResult<()> {
+        diesel::update(slot_templates::table)
+            .filter(slot_templates::id.eq(template_id))
+            .filter(slot_templates::doctor_id.eq(doctor_id))
+            .filter(slot_templates::deleted_at.is_null())
+            .set((slot_templates::deleted_at.eq(chrono::Utc::now().naive_utc()),))
+            .returning(slot_templates::id)
+            .get_result::<Uuid>(conn)
+            .await
+            .optional()?
+            .ok_or_else(|| DomainError::not_found("Slot template not found"))?;
+
+        Ok(())
+    }
+}
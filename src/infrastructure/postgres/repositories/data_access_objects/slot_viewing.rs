@@ -1,21 +1,27 @@
-use anyhow::Result;
-use chrono::NaiveDateTime;
-use diesel::{dsl::exists, prelude::*, select};
+use chrono::{DateTime, Utc};
+use diesel::{dsl::exists, pg::Pg, prelude::*, select};
 
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
 use uuid::Uuid;
 
-use crate::{domain::entities::slots::SlotEntity, infrastructure::postgres::schema::slots};
+use crate::{
+    domain::{
+        entities::slots::{SlotAppointmentCountDriftEntity, SlotEntity},
+        errors::{DomainError, DomainResult},
+        value_objects::slot_model::{SlotFilter, SlotSortOrder},
+    },
+    infrastructure::postgres::schema::slots,
+};
 
 pub struct SlotViewingDao;
 
 impl SlotViewingDao {
     pub async fn is_overlapping_slots_for_doctor_id(
         conn: &mut AsyncPgConnection,
-        start_time: NaiveDateTime,
-        end_time: NaiveDateTime,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
         doctor_id: i32,
-    ) -> Result<bool> {
+    ) -> DomainResult<bool> {
         let overlap_exists = select(exists(
             slots::table
                 .filter(slots::doctor_id.eq(doctor_id))
@@ -30,15 +36,51 @@ impl SlotViewingDao {
         Ok(overlap_exists)
     }
 
-    pub async fn get_current_appointment_count_by_slot_id(
+    pub async fn find_overlapping_slot_id_for_doctor_id(
+        conn: &mut AsyncPgConnection,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+        doctor_id: i32,
+    ) -> DomainResult<Option<Uuid>> {
+        let result = slots::table
+            .filter(slots::doctor_id.eq(doctor_id))
+            .filter(slots::deleted_at.is_null())
+            // overlap rule: [start, end)
+            .filter(slots::start_time.lt(end_time))
+            .filter(slots::end_time.gt(start_time))
+            .select(slots::id)
+            .first::<Uuid>(conn)
+            .await
+            .optional()?;
+
+        Ok(result)
+    }
+
+    pub async fn get_slot_by_id(
         conn: &mut AsyncPgConnection,
         slot_id: Uuid,
-    ) -> Result<i32> {
+    ) -> DomainResult<SlotEntity> {
         let result = slots::table
             .filter(slots::deleted_at.is_null())
             .filter(slots::id.eq(slot_id))
-            .select(slots::current_appointment_count)
-            .first::<i32>(conn)
+            .first::<SlotEntity>(conn)
+            .await
+            .optional()?
+            .ok_or_else(|| DomainError::not_found("Slot not found"))?;
+
+        Ok(result)
+    }
+
+    /// Doctors of the given slots, including removed ones.
+    pub async fn get_doctor_ids_by_slot_ids(
+        conn: &mut AsyncPgConnection,
+        slot_ids: Vec<Uuid>,
+    ) -> DomainResult<Vec<i32>> {
+        let result = slots::table
+            .filter(slots::id.eq_any(slot_ids))
+            .select(slots::doctor_id)
+            .distinct()
+            .load::<i32>(conn)
             .await?;
 
         Ok(result)
@@ -47,20 +89,68 @@ impl SlotViewingDao {
     pub async fn get_end_time_by_slot_id(
         conn: &mut AsyncPgConnection,
         slot_id: Uuid,
-    ) -> Result<NaiveDateTime> {
+    ) -> DomainResult<DateTime<Utc>> {
         let result = slots::table
             .filter(slots::deleted_at.is_null())
             .filter(slots::id.eq(slot_id))
             .select(slots::end_time)
-            .first::<NaiveDateTime>(conn)
-            .await?;
+            .first::<DateTime<Utc>>(conn)
+            .await
+            .optional()?
+            .ok_or_else(|| DomainError::not_found("Slot not found"))?;
 
         Ok(result)
     }
 
-    pub async fn get_slots(conn: &mut AsyncPgConnection) -> Result<Vec<SlotEntity>> {
-        let result = slots::table
+    fn filtered_slots(slot_filter: &SlotFilter) -> slots::BoxedQuery<'static, Pg> {
+        let mut query = slots::table
             .filter(slots::deleted_at.is_null())
+            .into_boxed();
+
+        if let Some(doctor_id) = slot_filter.doctor_id {
+            query = query.filter(slots::doctor_id.eq(doctor_id));
+        }
+        if let Some(start_from) = slot_filter.start_from {
+            query = query.filter(slots::start_time.ge(start_from));
+        }
+        if let Some(start_to) = slot_filter.start_to {
+            query = query.filter(slots::start_time.le(start_to));
+        }
+        if let Some(starts_after) = slot_filter.starts_after {
+            query = query.filter(slots::start_time.gt(starts_after));
+        }
+        if slot_filter.only_available {
+            query = query.filter(slots::current_appointment_count.lt(slots::max_appointment_count));
+        }
+
+        query
+    }
+
+    pub async fn count_slots(
+        conn: &mut AsyncPgConnection,
+        slot_filter: &SlotFilter,
+    ) -> DomainResult<i64> {
+        let result = Self::filtered_slots(slot_filter)
+            .count()
+            .get_result::<i64>(conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    pub async fn get_slots(
+        conn: &mut AsyncPgConnection,
+        slot_filter: &SlotFilter,
+    ) -> DomainResult<Vec<SlotEntity>> {
+        let query = Self::filtered_slots(slot_filter);
+        let query = match slot_filter.sort {
+            SlotSortOrder::Asc => query.order((slots::start_time.asc(), slots::id.asc())),
+            SlotSortOrder::Desc => query.order((slots::start_time.desc(), slots::id.asc())),
+        };
+
+        let result = query
+            .limit(slot_filter.limit)
+            .offset(slot_filter.offset()?)
             .load::<SlotEntity>(conn)
             .await?;
 
@@ -70,7 +160,7 @@ impl SlotViewingDao {
     pub async fn get_doctor_slots(
         conn: &mut AsyncPgConnection,
         doctor_id: i32,
-    ) -> Result<Vec<SlotEntity>> {
+    ) -> DomainResult<Vec<SlotEntity>> {
         let result = slots::table
             .filter(slots::deleted_at.is_null())
             .filter(slots::doctor_id.eq(doctor_id))
@@ -79,4 +169,29 @@ impl SlotViewingDao {
 
         Ok(result)
     }
+
+    pub async fn get_appointment_count_drifts(
+        conn: &mut AsyncPgConnection,
+    ) -> DomainResult<Vec<SlotAppointmentCountDriftEntity>> {
+        let result = diesel::sql_query(
+            r#"
+            SELECT slots.id AS slot_id,
+                   slots.doctor_id,
+                   slots.max_appointment_count,
+                   slots.current_appointment_count,
+                   COUNT(appointments.id)::INTEGER AS actual_appointment_count
+              FROM slots
+              LEFT JOIN appointments
+                ON appointments.slot_id = slots.id
+               AND appointment_holds_slot_place(appointments.status, appointments.deleted_at)
+             GROUP BY slots.id
+            HAVING slots.current_appointment_count <> COUNT(appointments.id)
+             ORDER BY slots.start_time, slots.id
+        "#,
+        )
+        .load::<SlotAppointmentCountDriftEntity>(conn)
+        .await?;
+
+        Ok(result)
+    }
 }
@@ -0,0 +1,27 @@
+use chrono::NaiveDateTime;
+use diesel::dsl::insert_into;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use uuid::Uuid;
+
+use crate::{
+    domain::{errors::DomainResult, value_objects::admin_model::StaffActionRecord},
+    infrastructure::postgres::schema::staff_actions,
+};
+
+pub struct StaffActionDao;
+
+impl StaffActionDao {
+    pub async fn add(
+        conn: &mut AsyncPgConnection,
+        staff_action_record: StaffActionRecord,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<Uuid> {
+        let result = insert_into(staff_actions::table)
+            .values(staff_action_record.to_entity(current_time))
+            .returning(staff_actions::id)
+            .get_result::<Uuid>(conn)
+            .await?;
+
+        Ok(result)
+    }
+}
@@ -0,0 +1,156 @@
+use chrono::{DateTime, Utc};
+use diesel::{
+    dsl::{delete, insert_into},
+    prelude::*,
+    result::{DatabaseErrorKind, Error as DieselError},
+};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        entities::waitlist_entries::{AddWaitlistEntryEntity, WaitlistEntryEntity},
+        errors::{DomainError, DomainResult},
+    },
+    infrastructure::postgres::schema::{slots, waitlist_entries},
+};
+
+pub struct WaitlistEntryDao;
+
+impl WaitlistEntryDao {
+    pub async fn add(
+        conn: &mut AsyncPgConnection,
+        add_waitlist_entry_entity: AddWaitlistEntryEntity,
+    ) -> DomainResult<WaitlistEntryEntity> {
+        let result = insert_into(waitlist_entries::table)
+            .values(add_waitlist_entry_entity)
+            .returning(WaitlistEntryEntity::as_returning())
+            .get_result::<WaitlistEntryEntity>(conn)
+            .await
+            .map_err(|e| match e {
+                DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+                    DomainError::conflict("You are already on the waitlist for this slot")
+                }
+                e => e.into(),
+            })?;
+
+        Ok(result)
+    }
+
+    /// The slot's queue, the patient who has waited the longest first.
+    pub async fn get_all_by_slot_id(
+        conn: &mut AsyncPgConnection,
+        slot_id: Uuid,
+    ) -> DomainResult<Vec<WaitlistEntryEntity>> {
+        let result = waitlist_entries::table
+            .filter(waitlist_entries::slot_id.eq(slot_id))
+            .order((
+                waitlist_entries::created_at.asc(),
+                waitlist_entries::id.asc(),
+            ))
+            .select(WaitlistEntryEntity::as_select())
+            .load::<WaitlistEntryEntity>(conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    /// 1-based place of an entry in its slot's queue.
+    pub async fn get_position(
+        conn: &mut AsyncPgConnection,
+        waitlist_entry: &WaitlistEntryEntity,
+    ) -> DomainResult<i64> {
+        let result = waitlist_entries::table
+            .filter(waitlist_entries::slot_id.eq(waitlist_entry.slot_id))
+            .filter(
+                waitlist_entries::created_at
+                    .lt(waitlist_entry.created_at)
+                    .or(waitlist_entries::created_at
+                        .eq(waitlist_entry.created_at)
+                        .and(waitlist_entries::id.le(waitlist_entry.id))),
+            )
+            .count()
+            .get_result::<i64>(conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    /// Waitlist entries of a patient for slots that have not ended yet, with
+    /// the doctor and time of each slot.
+    pub async fn get_patient_waitlist(
+        conn: &mut AsyncPgConnection,
+        patient_id: i32,
+        current_time: DateTime<Utc>,
+    ) -> DomainResult<Vec<(WaitlistEntryEntity, i32, DateTime<Utc>, DateTime<Utc>)>> {
+        let result = waitlist_entries::table
+            .inner_join(slots::table)
+            .filter(waitlist_entries::patient_id.eq(patient_id))
+            .filter(slots::deleted_at.is_null())
+            .filter(slots::end_time.gt(current_time))
+            .order(slots::start_time.asc())
+            .select((
+                WaitlistEntryEntity::as_select(),
+                slots::doctor_id,
+                slots::start_time,
+                slots::end_time,
+            ))
+            .load::<(WaitlistEntryEntity, i32, DateTime<Utc>, DateTime<Utc>)>(conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    /// Locks a patient's own waitlist entry before it is removed.
+    pub async fn lock_patient_entry(
+        conn: &mut AsyncPgConnection,
+        waitlist_entry_id: Uuid,
+        patient_id: i32,
+    ) -> DomainResult<()> {
+        let owner_patient_id = waitlist_entries::table
+            .filter(waitlist_entries::id.eq(waitlist_entry_id))
+            .select(waitlist_entries::patient_id)
+            .for_update()
+            .first::<i32>(conn)
+            .await
+            .optional()?
+            .ok_or_else(|| DomainError::not_found("Waitlist entry not found"))?;
+
+        if owner_patient_id != patient_id {
+            return Err(DomainError::forbidden(
+                "Waitlist entry does not belong to you",
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub async fn remove(
+        conn: &mut AsyncPgConnection,
+        waitlist_entry_id: Uuid,
+        patient_id: i32,
+    ) -> DomainResult<()> {
+        delete(waitlist_entries::table)
+            .filter(waitlist_entries::id.eq(waitlist_entry_id))
+            .filter(waitlist_entries::patient_id.eq(patient_id))
+            .returning(waitlist_entries::id)
+            .get_result::<Uuid>(conn)
+            .await
+            .optional()?
+            .ok_or_else(|| DomainError::not_found("Waitlist entry not found"))?;
+
+        Ok(())
+    }
+
+    pub async fn remove_by_id(
+        conn: &mut AsyncPgConnection,
+        waitlist_entry_id: Uuid,
+    ) -> DomainResult<()> {
+        delete(waitlist_entries::table)
+            .filter(waitlist_entries::id.eq(waitlist_entry_id))
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
+}
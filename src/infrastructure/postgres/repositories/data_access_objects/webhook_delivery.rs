@@ -0,0 +1,189 @@
+use chrono::NaiveDateTime;
+use diesel::{dsl::insert_into, prelude::*};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        entities::webhook_deliveries::{AddWebhookDeliveryEntity, WebhookDeliveryEntity},
+        errors::{DomainError, DomainResult},
+        value_objects::webhook_model::{WebhookAttemptModel, WebhookDeliveryStatus},
+    },
+    infrastructure::postgres::schema::{webhook_deliveries, webhook_subscriptions},
+};
+
+pub struct WebhookDeliveryDao;
+
+impl WebhookDeliveryDao {
+    /// Skips deliveries that already exist for the same subscription and
+    /// event, so an outbox event that is dispatched again is not duplicated.
+    pub async fn add_all(
+        conn: &mut AsyncPgConnection,
+        add_webhook_delivery_entities: Vec<AddWebhookDeliveryEntity>,
+    ) -> DomainResult<usize> {
+        let result = insert_into(webhook_deliveries::table)
+            .values(add_webhook_delivery_entities)
+            .on_conflict((
+                webhook_deliveries::subscription_id,
+                webhook_deliveries::outbox_event_id,
+            ))
+            .do_nothing()
+            .execute(conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    pub async fn get_by_id(
+        conn: &mut AsyncPgConnection,
+        id: Uuid,
+    ) -> DomainResult<WebhookDeliveryEntity> {
+        let result = webhook_deliveries::table
+            .filter(webhook_deliveries::id.eq(id))
+            .select(WebhookDeliveryEntity::as_select())
+            .first::<WebhookDeliveryEntity>(conn)
+            .await
+            .optional()?
+            .ok_or_else(|| DomainError::not_found("Webhook delivery not found"))?;
+
+        Ok(result)
+    }
+
+    pub async fn get_by_subscription_id(
+        conn: &mut AsyncPgConnection,
+        subscription_id: Uuid,
+        status: Option<String>,
+        limit: i64,
+    ) -> DomainResult<Vec<WebhookDeliveryEntity>> {
+        let mut query = webhook_deliveries::table
+            .filter(webhook_deliveries::subscription_id.eq(subscription_id))
+            .into_boxed();
+
+        if let Some(status) = status {
+            query = query.filter(webhook_deliveries::status.eq(status));
+        }
+
+        let result = query
+            .order(webhook_deliveries::created_at.desc())
+            .limit(limit)
+            .select(WebhookDeliveryEntity::as_select())
+            .load::<WebhookDeliveryEntity>(conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    /// Locks up to `limit` pending deliveries that are due, oldest first,
+    /// skipping the ones of disabled or removed subscriptions and the ones
+    /// another dispatcher already holds.
+    pub async fn lock_due(
+        conn: &mut AsyncPgConnection,
+        limit: i64,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<Vec<Uuid>> {
+        let enabled_subscription_ids = webhook_subscriptions::table
+            .filter(webhook_subscriptions::deleted_at.is_null())
+            .filter(webhook_subscriptions::disabled_at.is_null())
+            .select(webhook_subscriptions::id);
+
+        let result = webhook_deliveries::table
+            .filter(webhook_deliveries::status.eq(WebhookDeliveryStatus::Pending.to_string()))
+            .filter(webhook_deliveries::next_attempt_at.le(current_time))
+            .filter(webhook_deliveries::subscription_id.eq_any(enabled_subscription_ids))
+            .order(webhook_deliveries::created_at.asc())
+            .limit(limit)
+            .select(webhook_deliveries::id)
+            .for_update()
+            .skip_locked()
+            .load::<Uuid>(conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    /// Pushes `next_attempt_at` of locked deliveries to `lease_until`, so
+    /// they are not picked up again while they are being sent.
+    pub async fn lease(
+        conn: &mut AsyncPgConnection,
+        ids: &[Uuid],
+        lease_until: NaiveDateTime,
+    ) -> DomainResult<Vec<WebhookDeliveryEntity>> {
+        let result = diesel::update(webhook_deliveries::table)
+            .filter(webhook_deliveries::id.eq_any(ids))
+            .set(webhook_deliveries::next_attempt_at.eq(lease_until))
+            .returning(WebhookDeliveryEntity::as_returning())
+            .get_results::<WebhookDeliveryEntity>(conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    pub async fn mark_succeeded(
+        conn: &mut AsyncPgConnection,
+        id: Uuid,
+        webhook_attempt: &WebhookAttemptModel,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<()> {
+        diesel::update(webhook_deliveries::table)
+            .filter(webhook_deliveries::id.eq(id))
+            .set((
+                webhook_deliveries::status.eq(WebhookDeliveryStatus::Succeeded.to_string()),
+                webhook_deliveries::attempts.eq(webhook_deliveries::attempts + 1),
+                webhook_deliveries::last_response_status.eq(webhook_attempt.response_status),
+                webhook_deliveries::last_error.eq(None::<String>),
+                webhook_deliveries::delivered_at.eq(current_time),
+            ))
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Records a failed attempt. The delivery is tried again at
+    /// `next_attempt_at`, or marked `Failed` when it is `None`.
+    pub async fn mark_failed(
+        conn: &mut AsyncPgConnection,
+        id: Uuid,
+        webhook_attempt: &WebhookAttemptModel,
+        next_attempt_at: Option<NaiveDateTime>,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<()> {
+        let status = match next_attempt_at {
+            Some(_) => WebhookDeliveryStatus::Pending,
+            None => WebhookDeliveryStatus::Failed,
+        };
+
+        diesel::update(webhook_deliveries::table)
+            .filter(webhook_deliveries::id.eq(id))
+            .set((
+                webhook_deliveries::status.eq(status.to_string()),
+                webhook_deliveries::attempts.eq(webhook_deliveries::attempts + 1),
+                webhook_deliveries::next_attempt_at.eq(next_attempt_at.unwrap_or(current_time)),
+                webhook_deliveries::last_response_status.eq(webhook_attempt.response_status),
+                webhook_deliveries::last_error.eq(webhook_attempt.error.clone()),
+            ))
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn redeliver(
+        conn: &mut AsyncPgConnection,
+        id: Uuid,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<WebhookDeliveryEntity> {
+        let result = diesel::update(webhook_deliveries::table)
+            .filter(webhook_deliveries::id.eq(id))
+            .set((
+                webhook_deliveries::status.eq(WebhookDeliveryStatus::Pending.to_string()),
+                webhook_deliveries::attempts.eq(0),
+                webhook_deliveries::next_attempt_at.eq(current_time),
+            ))
+            .returning(WebhookDeliveryEntity::as_returning())
+            .get_result::<WebhookDeliveryEntity>(conn)
+            .await?;
+
+        Ok(result)
+    }
+}
@@ -0,0 +1,201 @@
+use chrono::NaiveDateTime;
+use diesel::{dsl::insert_into, prelude::*};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        entities::webhook_subscriptions::{
+            AddWebhookSubscriptionEntity, EditWebhookSubscriptionEntity, WebhookSubscriptionEntity,
+        },
+        errors::{DomainError, DomainResult},
+        value_objects::service_api_key_model::ServiceScope,
+    },
+    infrastructure::postgres::schema::{service_api_keys, webhook_subscriptions},
+};
+
+pub struct WebhookSubscriptionDao;
+
+impl WebhookSubscriptionDao {
+    pub async fn add(
+        conn: &mut AsyncPgConnection,
+        add_webhook_subscription_entity: AddWebhookSubscriptionEntity,
+    ) -> DomainResult<WebhookSubscriptionEntity> {
+        let result = insert_into(webhook_subscriptions::table)
+            .values(add_webhook_subscription_entity)
+            .returning(WebhookSubscriptionEntity::as_returning())
+            .get_result::<WebhookSubscriptionEntity>(conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    pub async fn get_all_by_service_api_key_id(
+        conn: &mut AsyncPgConnection,
+        service_api_key_id: i32,
+    ) -> DomainResult<Vec<WebhookSubscriptionEntity>> {
+        let result = webhook_subscriptions::table
+            .filter(webhook_subscriptions::service_api_key_id.eq(service_api_key_id))
+            .filter(webhook_subscriptions::deleted_at.is_null())
+            .order(webhook_subscriptions::created_at.asc())
+            .select(WebhookSubscriptionEntity::as_select())
+            .load::<WebhookSubscriptionEntity>(conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    pub async fn get_by_id_and_service_api_key_id(
+        conn: &mut AsyncPgConnection,
+        id: Uuid,
+        service_api_key_id: i32,
+    ) -> DomainResult<WebhookSubscriptionEntity> {
+        let result = webhook_subscriptions::table
+            .filter(webhook_subscriptions::id.eq(id))
+            .filter(webhook_subscriptions::service_api_key_id.eq(service_api_key_id))
+            .filter(webhook_subscriptions::deleted_at.is_null())
+            .select(WebhookSubscriptionEntity::as_select())
+            .first::<WebhookSubscriptionEntity>(conn)
+            .await
+            .optional()?
+            .ok_or_else(|| DomainError::not_found("Webhook subscription not found"))?;
+
+        Ok(result)
+    }
+
+    pub async fn get_by_ids(
+        conn: &mut AsyncPgConnection,
+        ids: &[Uuid],
+    ) -> DomainResult<Vec<WebhookSubscriptionEntity>> {
+        let result = webhook_subscriptions::table
+            .filter(webhook_subscriptions::id.eq_any(ids))
+            .select(WebhookSubscriptionEntity::as_select())
+            .load::<WebhookSubscriptionEntity>(conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    /// Enabled subscriptions that receive `event_type` about one of
+    /// `doctor_ids`, either listed or because they have no event or doctor
+    /// filter. Subscriptions of API keys that were revoked or cannot read
+    /// appointments are left out.
+    pub async fn get_enabled_ids_by_event(
+        conn: &mut AsyncPgConnection,
+        event_type: &str,
+        doctor_ids: Vec<i32>,
+    ) -> DomainResult<Vec<Uuid>> {
+        let result = webhook_subscriptions::table
+            .inner_join(service_api_keys::table)
+            .filter(webhook_subscriptions::deleted_at.is_null())
+            .filter(webhook_subscriptions::disabled_at.is_null())
+            .filter(
+                webhook_subscriptions::event_types
+                    .eq(Vec::<String>::new())
+                    .or(webhook_subscriptions::event_types.contains(vec![event_type.to_string()])),
+            )
+            .filter(
+                webhook_subscriptions::doctor_ids
+                    .eq(Vec::<i32>::new())
+                    .or(webhook_subscriptions::doctor_ids.overlaps_with(doctor_ids)),
+            )
+            .filter(service_api_keys::revoked_at.is_null())
+            .filter(
+                service_api_keys::scopes.contains(vec![ServiceScope::AppointmentsRead.to_string()]),
+            )
+            .select(webhook_subscriptions::id)
+            .load::<Uuid>(conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    pub async fn edit(
+        conn: &mut AsyncPgConnection,
+        id: Uuid,
+        service_api_key_id: i32,
+        edit_webhook_subscription_entity: EditWebhookSubscriptionEntity,
+    ) -> DomainResult<WebhookSubscriptionEntity> {
+        let result = diesel::update(webhook_subscriptions::table)
+            .filter(webhook_subscriptions::id.eq(id))
+            .filter(webhook_subscriptions::service_api_key_id.eq(service_api_key_id))
+            .filter(webhook_subscriptions::deleted_at.is_null())
+            .set(edit_webhook_subscription_entity)
+            .returning(WebhookSubscriptionEntity::as_returning())
+            .get_result::<WebhookSubscriptionEntity>(conn)
+            .await
+            .optional()?
+            .ok_or_else(|| DomainError::not_found("Webhook subscription not found"))?;
+
+        Ok(result)
+    }
+
+    pub async fn remove(
+        conn: &mut AsyncPgConnection,
+        id: Uuid,
+        service_api_key_id: i32,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<()> {
+        diesel::update(webhook_subscriptions::table)
+            .filter(webhook_subscriptions::id.eq(id))
+            .filter(webhook_subscriptions::service_api_key_id.eq(service_api_key_id))
+            .filter(webhook_subscriptions::deleted_at.is_null())
+            .set((
+                webhook_subscriptions::deleted_at.eq(current_time),
+                webhook_subscriptions::updated_at.eq(current_time),
+            ))
+            .returning(webhook_subscriptions::id)
+            .get_result::<Uuid>(conn)
+            .await
+            .optional()?
+            .ok_or_else(|| DomainError::not_found("Webhook subscription not found"))?;
+
+        Ok(())
+    }
+
+    pub async fn reset_failures(conn: &mut AsyncPgConnection, id: Uuid) -> DomainResult<()> {
+        diesel::update(webhook_subscriptions::table)
+            .filter(webhook_subscriptions::id.eq(id))
+            .filter(webhook_subscriptions::consecutive_failures.ne(0))
+            .set(webhook_subscriptions::consecutive_failures.eq(0))
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns the consecutive failures of the subscription, including this
+    /// one.
+    pub async fn add_failure(conn: &mut AsyncPgConnection, id: Uuid) -> DomainResult<i32> {
+        let result = diesel::update(webhook_subscriptions::table)
+            .filter(webhook_subscriptions::id.eq(id))
+            .set(
+                webhook_subscriptions::consecutive_failures
+                    .eq(webhook_subscriptions::consecutive_failures + 1),
+            )
+            .returning(webhook_subscriptions::consecutive_failures)
+            .get_result::<i32>(conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    /// Returns whether the subscription was enabled until now.
+    pub async fn disable(
+        conn: &mut AsyncPgConnection,
+        id: Uuid,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<bool> {
+        let result = diesel::update(webhook_subscriptions::table)
+            .filter(webhook_subscriptions::id.eq(id))
+            .filter(webhook_subscriptions::disabled_at.is_null())
+            .set((
+                webhook_subscriptions::disabled_at.eq(current_time),
+                webhook_subscriptions::updated_at.eq(current_time),
+            ))
+            .execute(conn)
+            .await?;
+
+        Ok(result > 0)
+    }
+}
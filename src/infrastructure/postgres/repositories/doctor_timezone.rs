@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        entities::doctor_timezones::SetDoctorTimezoneEntity,
+        errors::DomainResult,
+        repositories::doctor_timezone::DoctorTimezoneRepository,
+        value_objects::timezone_model::{GetDoctorTimezoneResponseModel, doctor_timezone},
+    },
+    infrastructure::postgres::{
+        postgres_connection::PgPoolSquad,
+        repositories::data_access_objects::doctor_timezone::DoctorTimezoneDao,
+    },
+};
+
+pub struct DoctorTimezonePostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl DoctorTimezonePostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+impl DoctorTimezoneRepository for DoctorTimezonePostgres {
+    async fn get_doctor_timezone(
+        &self,
+        doctor_id: i32,
+    ) -> DomainResult<GetDoctorTimezoneResponseModel> {
+        let mut conn = self.db_pool.get().await?;
+
+        let doctor_timezone_entity =
+            DoctorTimezoneDao::get_by_doctor_id(&mut conn, doctor_id).await?;
+        let effective_timezone = doctor_timezone(doctor_timezone_entity.as_ref())?;
+
+        Ok(GetDoctorTimezoneResponseModel {
+            doctor_timezone: doctor_timezone_entity,
+            effective_timezone: effective_timezone.name().to_string(),
+        })
+    }
+
+    async fn set_doctor_timezone(
+        &self,
+        set_doctor_timezone_entity: SetDoctorTimezoneEntity,
+    ) -> DomainResult<GetDoctorTimezoneResponseModel> {
+        let mut conn = self.db_pool.get().await?;
+
+        let doctor_timezone_entity =
+            DoctorTimezoneDao::set(&mut conn, set_doctor_timezone_entity).await?;
+        let effective_timezone = doctor_timezone_entity.timezone.clone();
+
+        Ok(GetDoctorTimezoneResponseModel {
+            doctor_timezone: Some(doctor_timezone_entity),
+            effective_timezone,
+        })
+    }
+}
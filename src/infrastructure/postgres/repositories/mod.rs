@@ -1,7 +1,17 @@
+pub mod admin;
 pub mod appointment_ledger;
 pub mod appointment_ops;
+pub mod appointment_reminder;
+pub mod booking_window;
+pub mod doctor_timezone;
+pub mod outbox;
 pub mod schedule_viewing;
+pub mod service_api_key;
 pub mod slot_ops;
+pub mod slot_reconciliation;
+pub mod slot_template;
 pub mod slot_viewing;
+pub mod waitlist;
+pub mod webhook;
 
 mod data_access_objects;
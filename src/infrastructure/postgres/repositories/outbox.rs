@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use chrono::NaiveDateTime;
+use diesel_async::{AsyncConnection, scoped_futures::ScopedFutureExt};
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        entities::outbox_events::OutboxEventEntity,
+        errors::{DomainError, DomainResult},
+        repositories::outbox::{OutboxRepository, OutboxSink},
+        value_objects::outbox_model::OutboxMessage,
+    },
+    infrastructure::postgres::{
+        postgres_connection::PgPoolSquad,
+        repositories::data_access_objects::outbox_event::OutboxEventDao,
+    },
+};
+
+pub struct OutboxPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl OutboxPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+impl OutboxRepository for OutboxPostgres {
+    async fn claim_due(
+        &self,
+        limit: i64,
+        current_time: NaiveDateTime,
+        lease_until: NaiveDateTime,
+    ) -> DomainResult<Vec<OutboxEventEntity>> {
+        let mut conn = self.db_pool.get().await?;
+
+        let mut outbox_events = conn
+            .transaction(|conn| {
+                async move {
+                    let ids = OutboxEventDao::lock_due(conn, limit, current_time).await?;
+                    if ids.is_empty() {
+                        return Ok(Vec::new());
+                    }
+
+                    OutboxEventDao::lease(conn, &ids, lease_until).await
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        outbox_events.sort_by_key(|outbox_event| outbox_event.created_at);
+        Ok(outbox_events)
+    }
+
+    async fn mark_dispatched(&self, id: Uuid, current_time: NaiveDateTime) -> DomainResult<()> {
+        let mut conn = self.db_pool.get().await?;
+        OutboxEventDao::mark_dispatched(&mut conn, id, current_time).await
+    }
+
+    async fn mark_failed(
+        &self,
+        id: Uuid,
+        error: &str,
+        next_attempt_at: Option<NaiveDateTime>,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<()> {
+        let mut conn = self.db_pool.get().await?;
+        OutboxEventDao::mark_failed(&mut conn, id, error, next_attempt_at, current_time).await
+    }
+}
+
+/// Publishes outbox events on a Postgres `LISTEN`/`NOTIFY` channel. Listeners
+/// that are not connected at that moment miss them.
+pub struct PgNotifyOutboxSink {
+    db_pool: Arc<PgPoolSquad>,
+    channel: String,
+}
+
+impl PgNotifyOutboxSink {
+    pub fn new(db_pool: Arc<PgPoolSquad>, channel: String) -> Self {
+        Self { db_pool, channel }
+    }
+}
+
+impl OutboxSink for PgNotifyOutboxSink {
+    async fn deliver(&self, outbox_message: &OutboxMessage) -> DomainResult<()> {
+        let payload =
+            serde_json::to_string(outbox_message).map_err(|e| DomainError::Internal(e.into()))?;
+
+        let mut conn = self.db_pool.get().await?;
+        OutboxEventDao::notify(&mut conn, &self.channel, &payload).await
+    }
+}
@@ -1,15 +1,23 @@
 use std::sync::Arc;
 
-use anyhow::Result;
+use uuid::Uuid;
 
 use crate::{
     domain::{
-        entities::schedule_view::ScheduleViewEntity,
+        entities::{
+            appointment_status_history::AppointmentStatusHistoryEntity,
+            schedule_view::ScheduleViewEntity,
+        },
+        errors::{DomainError, DomainResult},
         repositories::schedule_viewing::ScheduleViewingRepository,
+        value_objects::actor::Actor,
     },
     infrastructure::postgres::{
         postgres_connection::PgPoolSquad,
-        repositories::data_access_objects::schedule_viewing::ScheduleViewingDao,
+        repositories::data_access_objects::{
+            appointment_status_history::AppointmentStatusHistoryDao,
+            appointment_viewing::AppointmentViewingDao, schedule_viewing::ScheduleViewingDao,
+        },
     },
 };
 
@@ -24,17 +32,53 @@ impl ScheduleViewingPostgres {
 }
 
 impl ScheduleViewingRepository for ScheduleViewingPostgres {
-    async fn get_patient_schedules(&self, patient_id: i32) -> Result<Vec<ScheduleViewEntity>> {
+    async fn get_patient_schedules(
+        &self,
+        patient_id: i32,
+    ) -> DomainResult<Vec<ScheduleViewEntity>> {
         let mut conn = self.db_pool.get().await?;
         let schedules = ScheduleViewingDao::get_patient_schedules(&mut conn, patient_id).await?;
 
         Ok(schedules)
     }
 
-    async fn get_doctor_schedules(&self, doctor_id: i32) -> Result<Vec<ScheduleViewEntity>> {
+    async fn get_doctor_schedules(&self, doctor_id: i32) -> DomainResult<Vec<ScheduleViewEntity>> {
         let mut conn = self.db_pool.get().await?;
         let schedules = ScheduleViewingDao::get_doctor_schedules(&mut conn, doctor_id).await?;
 
         Ok(schedules)
     }
+
+    async fn get_schedule(&self, appointment_id: Uuid) -> DomainResult<ScheduleViewEntity> {
+        let mut conn = self.db_pool.get().await?;
+        let schedule = ScheduleViewingDao::get_schedule(&mut conn, appointment_id).await?;
+
+        Ok(schedule)
+    }
+
+    async fn get_appointment_history(
+        &self,
+        appointment_id: Uuid,
+        actor: Actor,
+    ) -> DomainResult<Vec<AppointmentStatusHistoryEntity>> {
+        let mut conn = self.db_pool.get().await?;
+
+        let (patient_id, doctor_id) =
+            AppointmentViewingDao::get_patient_id_and_doctor_id_by_appointment_id(
+                &mut conn,
+                appointment_id,
+            )
+            .await?;
+        let is_allowed = actor.is_privileged()
+            || actor == Actor::patient(patient_id)
+            || actor == Actor::doctor(doctor_id);
+        if !is_allowed {
+            return Err(DomainError::forbidden("Appointment does not belong to you"));
+        }
+
+        let history =
+            AppointmentStatusHistoryDao::get_appointment_history(&mut conn, appointment_id).await?;
+
+        Ok(history)
+    }
 }
@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use chrono::NaiveDateTime;
+
+use crate::{
+    domain::{
+        entities::service_api_keys::{AddServiceApiKeyEntity, ServiceApiKeyEntity},
+        errors::DomainResult,
+        repositories::service_api_key::ServiceApiKeyRepository,
+    },
+    infrastructure::postgres::{
+        postgres_connection::PgPoolSquad,
+        repositories::data_access_objects::service_api_key::ServiceApiKeyDao,
+    },
+};
+
+pub struct ServiceApiKeyPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl ServiceApiKeyPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+impl ServiceApiKeyRepository for ServiceApiKeyPostgres {
+    async fn add(&self, add_service_api_key_entity: AddServiceApiKeyEntity) -> DomainResult<i32> {
+        let mut conn = self.db_pool.get().await?;
+        let id = ServiceApiKeyDao::add(&mut conn, add_service_api_key_entity).await?;
+
+        Ok(id)
+    }
+
+    async fn get_active_by_key_prefix(
+        &self,
+        key_prefix: &str,
+    ) -> DomainResult<Option<ServiceApiKeyEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        let service_api_key =
+            ServiceApiKeyDao::get_active_by_key_prefix(&mut conn, key_prefix).await?;
+
+        Ok(service_api_key)
+    }
+
+    async fn revoke(&self, key_prefix: &str, current_time: NaiveDateTime) -> DomainResult<()> {
+        let mut conn = self.db_pool.get().await?;
+        ServiceApiKeyDao::revoke(&mut conn, key_prefix, current_time).await?;
+
+        Ok(())
+    }
+}
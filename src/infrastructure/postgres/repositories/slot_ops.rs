@@ -1,17 +1,39 @@
 use std::sync::Arc;
 
-use anyhow::{Result, anyhow};
-use diesel_async::{AsyncConnection, scoped_futures::ScopedFutureExt};
+use chrono_tz::Tz;
+use diesel_async::{AsyncConnection, AsyncPgConnection, scoped_futures::ScopedFutureExt};
 use uuid::Uuid;
 
 use crate::{
     domain::{
-        entities::slots::{AddSlotEntity, EditSlotEntity},
+        entities::{
+            appointments::{RescheduleAppointmentEntity, SlotAppointmentEntity},
+            slots::{AddSlotEntity, EditSlotEntity, SlotEntity},
+        },
+        errors::{DomainError, DomainResult},
         repositories::slot_ops::SlotOpsRepository,
+        value_objects::{
+            actor::Actor,
+            appointment_history_model::{AppointmentHistoryAction, AppointmentHistoryRecord},
+            appointment_status::AppointmentStatus,
+            outbox_model::{
+                AppointmentRescheduled, AppointmentStatusChanged, DomainEvent, SlotRemoved,
+            },
+            slot_model::{
+                AffectedAppointmentModel, AffectedAppointmentOutcome, BulkMode, BulkSlotOutcome,
+                SlotCascadeAction,
+            },
+            timezone_model::doctor_timezone,
+        },
     },
     infrastructure::postgres::{
         postgres_connection::PgPoolSquad,
-        repositories::data_access_objects::{slot_ops::SlotOpsDao, slot_viewing::SlotViewingDao},
+        repositories::data_access_objects::{
+            appointment_ledger::AppointmentLedgerDao, appointment_ops::AppointmentOpsDao,
+            appointment_status_history::AppointmentStatusHistoryDao,
+            appointment_viewing::AppointmentViewingDao, doctor_timezone::DoctorTimezoneDao,
+            outbox_event::OutboxEventDao, slot_ops::SlotOpsDao, slot_viewing::SlotViewingDao,
+        },
     },
 };
 
@@ -23,15 +45,202 @@ impl SlotOpsPostgres {
     pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
         Self { db_pool }
     }
+
+    /// Locks a slot that has not ended yet and belongs to the doctor.
+    async fn lock_own_future_slot(
+        conn: &mut AsyncPgConnection,
+        slot_id: Uuid,
+        doctor_id: i32,
+    ) -> DomainResult<SlotEntity> {
+        SlotOpsDao::lock(conn, slot_id).await?;
+        let slot = SlotViewingDao::get_slot_by_id(conn, slot_id).await?;
+
+        if slot.doctor_id != doctor_id {
+            return Err(DomainError::forbidden("Slot does not belong to you"));
+        }
+
+        let now = chrono::Utc::now();
+        if now > slot.end_time {
+            return Err(DomainError::past_time("Slot is already ended!!!"));
+        }
+
+        Ok(slot)
+    }
+
+    /// Cancels or moves `slot_appointments` out of `slot`, freeing their places.
+    async fn cascade(
+        conn: &mut AsyncPgConnection,
+        slot: &SlotEntity,
+        slot_appointments: Vec<SlotAppointmentEntity>,
+        cascade_action: SlotCascadeAction,
+    ) -> DomainResult<Vec<AffectedAppointmentModel>> {
+        for slot_appointment in &slot_appointments {
+            let status = slot_appointment.status.parse::<AppointmentStatus>()?;
+            if !status.can_transition_to(&AppointmentStatus::Cancelled) {
+                return Err(DomainError::invalid_transition(format!(
+                    "Appointment id: {} is already {}",
+                    slot_appointment.id, status
+                )));
+            }
+        }
+
+        if let SlotCascadeAction::Move {
+            slot_id: target_slot_id,
+        } = cascade_action
+        {
+            if target_slot_id == slot.id {
+                return Err(DomainError::validation(
+                    "move_to_slot_id must be another slot",
+                ));
+            }
+            Self::lock_own_future_slot(conn, target_slot_id, slot.doctor_id).await?;
+        }
+
+        let now = chrono::Utc::now().naive_utc();
+        let mut affected_appointments = Vec::with_capacity(slot_appointments.len());
+
+        for slot_appointment in slot_appointments {
+            let status = slot_appointment.status.parse::<AppointmentStatus>()?;
+            let (outcome, appointment_history_record, domain_event) = match &cascade_action {
+                SlotCascadeAction::Cancel(cancel_appointment_entity) => {
+                    AppointmentLedgerDao::change_appointment_status(
+                        conn,
+                        slot_appointment.id,
+                        cancel_appointment_entity.clone(),
+                    )
+                    .await?;
+
+                    let appointment_history_record = AppointmentHistoryRecord {
+                        action: AppointmentHistoryAction::Transition,
+                        previous_status: Some(status),
+                        new_status: Some(AppointmentStatus::Cancelled),
+                        actor: Actor::doctor(slot.doctor_id),
+                        note: cancel_appointment_entity.cancellation_reason.clone(),
+                    };
+                    let appointment_status_changed = AppointmentStatusChanged {
+                        appointment_id: slot_appointment.id,
+                        slot_id: slot.id,
+                        patient_id: slot_appointment.patient_id,
+                        previous_status: status,
+                        status: AppointmentStatus::Cancelled,
+                        reason: cancel_appointment_entity.cancellation_reason.clone(),
+                        actor: Actor::doctor(slot.doctor_id),
+                    };
+                    (
+                        AffectedAppointmentOutcome::Cancelled,
+                        appointment_history_record,
+                        DomainEvent::AppointmentStatusChanged(appointment_status_changed),
+                    )
+                }
+                SlotCascadeAction::Move { slot_id } => {
+                    let slot_is_not_full = SlotOpsDao::has_free_place(conn, *slot_id).await?;
+
+                    if !slot_is_not_full {
+                        return Err(DomainError::slot_full("Slot is full!!!"));
+                    }
+
+                    let reschedule_appointment_entity = RescheduleAppointmentEntity {
+                        slot_id: *slot_id,
+                        updated_at: now,
+                    };
+                    AppointmentOpsDao::move_to_slot(
+                        conn,
+                        slot_appointment.id,
+                        reschedule_appointment_entity,
+                    )
+                    .await?;
+
+                    let appointment_history_record = AppointmentHistoryRecord {
+                        action: AppointmentHistoryAction::Reschedule,
+                        previous_status: Some(status),
+                        new_status: Some(status),
+                        actor: Actor::doctor(slot.doctor_id),
+                        note: Some(format!("Moved from slot {} to slot {}", slot.id, slot_id)),
+                    };
+                    let appointment_rescheduled = AppointmentRescheduled {
+                        appointment_id: slot_appointment.id,
+                        patient_id: slot_appointment.patient_id,
+                        previous_slot_id: slot.id,
+                        slot_id: *slot_id,
+                        actor: Actor::doctor(slot.doctor_id),
+                    };
+                    (
+                        AffectedAppointmentOutcome::Moved { slot_id: *slot_id },
+                        appointment_history_record,
+                        DomainEvent::AppointmentRescheduled(appointment_rescheduled),
+                    )
+                }
+            };
+            AppointmentStatusHistoryDao::add(
+                conn,
+                slot_appointment.id,
+                appointment_history_record,
+                now,
+            )
+            .await?;
+            OutboxEventDao::add(conn, domain_event, now).await?;
+
+            affected_appointments.push(AffectedAppointmentModel {
+                appointment_id: slot_appointment.id,
+                patient_id: slot_appointment.patient_id,
+                outcome,
+            });
+        }
+
+        Ok(affected_appointments)
+    }
+
+    /// Edits a future slot of `doctor_id`, refusing to shrink it below the
+    /// appointments it already holds.
+    pub(crate) async fn apply_edit(
+        conn: &mut AsyncPgConnection,
+        slot_id: Uuid,
+        doctor_id: i32,
+        edit_slot_entity: EditSlotEntity,
+    ) -> DomainResult<Uuid> {
+        let slot = Self::lock_own_future_slot(conn, slot_id, doctor_id).await?;
+        let max_appointment_count = edit_slot_entity
+            .max_appointment_count
+            .unwrap_or(slot.max_appointment_count);
+        let end_time = edit_slot_entity.end_time.unwrap_or(slot.end_time);
+
+        if end_time <= slot.start_time {
+            return Err(DomainError::validation("end_time must be after start_time"));
+        }
+        if max_appointment_count < 1 {
+            return Err(DomainError::validation(
+                "max_appointment_count must be at least 1",
+            ));
+        }
+        if max_appointment_count < slot.current_appointment_count {
+            return Err(DomainError::conflict(format!(
+                "Slot already has {} appointments, force shrink it to cancel or move them",
+                slot.current_appointment_count
+            )));
+        }
+
+        let effected_slot_id = SlotOpsDao::edit(conn, slot_id, doctor_id, edit_slot_entity).await?;
+        Ok(effected_slot_id)
+    }
 }
 
 impl SlotOpsRepository for SlotOpsPostgres {
-    async fn add(&self, add_slot_entity: AddSlotEntity) -> Result<Uuid> {
+    async fn get_doctor_timezone(&self, doctor_id: i32) -> DomainResult<Tz> {
+        let mut conn = self.db_pool.get().await?;
+        let doctor_timezone_entity =
+            DoctorTimezoneDao::get_by_doctor_id(&mut conn, doctor_id).await?;
+
+        doctor_timezone(doctor_timezone_entity.as_ref())
+    }
+
+    async fn add(&self, add_slot_entity: AddSlotEntity) -> DomainResult<Uuid> {
         let mut conn = self.db_pool.get().await?;
 
         let slot_id = conn
             .transaction(|conn| {
                 async move {
+                    // Concurrent inserts can both pass this check, the
+                    // `excl_slots_doctor_id_time_overlap` constraint rejects the loser.
                     let is_overlapping_slot = SlotViewingDao::is_overlapping_slots_for_doctor_id(
                         conn,
                         add_slot_entity.start_time,
@@ -41,12 +250,12 @@ impl SlotOpsRepository for SlotOpsPostgres {
                     .await?;
 
                     if is_overlapping_slot {
-                        return Err(anyhow::anyhow!("Slot time is overlapping!!!"));
+                        return Err(DomainError::slot_overlap("Slot time is overlapping!!!"));
                     }
 
-                    let now = chrono::Utc::now().naive_utc();
+                    let now = chrono::Utc::now();
                     if now > add_slot_entity.end_time {
-                        return Err(anyhow::anyhow!("You cant go to the past"));
+                        return Err(DomainError::past_time("You cant go to the past"));
                     }
 
                     let slot_id = SlotOpsDao::add(conn, add_slot_entity).await?;
@@ -59,54 +268,127 @@ impl SlotOpsRepository for SlotOpsPostgres {
         Ok(slot_id)
     }
 
-    async fn edit(
+    async fn add_bulk(
         &self,
-        slot_id: Uuid,
-        doctor_id: i32,
-        edit_slot_entity: EditSlotEntity,
-    ) -> Result<Uuid> {
+        add_slot_entities: Vec<AddSlotEntity>,
+        mode: BulkMode,
+        has_invalid_items: bool,
+    ) -> DomainResult<Vec<BulkSlotOutcome>> {
+        let mut outcomes = Vec::with_capacity(add_slot_entities.len());
+        let mut rolled_back = false;
+
+        // Outcomes are collected outside of the transaction so they can still be
+        // reported after an all-or-nothing batch has been rolled back.
+        let outcomes_ref = &mut outcomes;
+        let rolled_back_ref = &mut rolled_back;
         let mut conn = self.db_pool.get().await?;
-        let effected_slot_id = conn
+        let transaction_result = conn
             .transaction(|conn| {
                 async move {
-                    let end_time = SlotViewingDao::get_end_time_by_slot_id(conn, slot_id).await?;
-                    let now = chrono::Utc::now().naive_utc();
+                    let now = chrono::Utc::now();
 
-                    if now > end_time {
-                        return Err(anyhow::anyhow!("Slot is already ended!!!"));
+                    for add_slot_entity in add_slot_entities {
+                        let outcome = if now > add_slot_entity.end_time {
+                            BulkSlotOutcome::Past
+                        } else if let Some(conflicting_slot_id) =
+                            SlotViewingDao::find_overlapping_slot_id_for_doctor_id(
+                                conn,
+                                add_slot_entity.start_time,
+                                add_slot_entity.end_time,
+                                add_slot_entity.doctor_id,
+                            )
+                            .await?
+                        {
+                            BulkSlotOutcome::Overlapping {
+                                conflicting_slot_id: Some(conflicting_slot_id),
+                            }
+                        } else {
+                            let slot_id = SlotOpsDao::add(conn, add_slot_entity).await?;
+                            BulkSlotOutcome::Created { slot_id }
+                        };
+                        outcomes_ref.push(outcome);
                     }
 
-                    SlotOpsDao::lock(conn, slot_id).await?;
+                    if mode == BulkMode::AllOrNothing
+                        && (has_invalid_items
+                            || outcomes_ref.iter().any(|outcome| !outcome.is_created()))
+                    {
+                        *rolled_back_ref = true;
+                        return Err(DomainError::conflict("Bulk slot creation rolled back"));
+                    }
 
-                    let effected_slot_id =
-                        SlotOpsDao::edit(conn, slot_id, doctor_id, edit_slot_entity).await?;
-                    Ok(effected_slot_id)
+                    Ok(())
                 }
                 .scope_boxed()
             })
+            .await;
+        drop(conn);
+
+        match transaction_result {
+            Ok(()) => Ok(outcomes),
+            Err(_) if rolled_back => {
+                let rolled_back_slot_ids = outcomes
+                    .iter()
+                    .filter_map(|outcome| match outcome {
+                        BulkSlotOutcome::Created { slot_id } => Some(*slot_id),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+
+                Ok(outcomes
+                    .into_iter()
+                    .map(|outcome| match outcome {
+                        BulkSlotOutcome::Created { .. } => BulkSlotOutcome::NotCreated,
+                        BulkSlotOutcome::Overlapping {
+                            conflicting_slot_id: Some(conflicting_slot_id),
+                        } if rolled_back_slot_ids.contains(&conflicting_slot_id) => {
+                            BulkSlotOutcome::Overlapping {
+                                conflicting_slot_id: None,
+                            }
+                        }
+                        outcome => outcome,
+                    })
+                    .collect())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn edit(
+        &self,
+        slot_id: Uuid,
+        doctor_id: i32,
+        edit_slot_entity: EditSlotEntity,
+    ) -> DomainResult<Uuid> {
+        let mut conn = self.db_pool.get().await?;
+        let effected_slot_id = conn
+            .transaction(|conn| {
+                async move { Self::apply_edit(conn, slot_id, doctor_id, edit_slot_entity).await }
+                    .scope_boxed()
+            })
             .await?;
 
         Ok(effected_slot_id)
     }
 
-    async fn remove(&self, slot_id: Uuid, doctor_id: i32) -> Result<()> {
+    async fn remove(&self, slot_id: Uuid, doctor_id: i32) -> DomainResult<()> {
         let mut conn = self.db_pool.get().await?;
         conn.transaction(|conn| {
             async move {
-                SlotOpsDao::lock(conn, slot_id).await?;
-                let appointment_count =
-                    SlotViewingDao::get_current_appointment_count_by_slot_id(conn, slot_id).await?;
-                if appointment_count > 0 {
-                    return Err(anyhow!("Patient already booked this slot!"));
+                let slot = Self::lock_own_future_slot(conn, slot_id, doctor_id).await?;
+                if slot.current_appointment_count > 0 {
+                    return Err(DomainError::conflict("Patient already booked this slot!"));
                 }
 
-                let end_time = SlotViewingDao::get_end_time_by_slot_id(conn, slot_id).await?;
-                let now = chrono::Utc::now().naive_utc();
-
-                if now > end_time {
-                    return Err(anyhow::anyhow!("Slot is already ended!!!"));
-                }
                 SlotOpsDao::remove(conn, slot_id, doctor_id).await?;
+
+                let slot_removed = SlotRemoved { slot_id, doctor_id };
+                OutboxEventDao::add(
+                    conn,
+                    DomainEvent::SlotRemoved(slot_removed),
+                    chrono::Utc::now().naive_utc(),
+                )
+                .await?;
                 Ok(())
             }
             .scope_boxed()
@@ -115,4 +397,80 @@ impl SlotOpsRepository for SlotOpsPostgres {
 
         Ok(())
     }
+
+    async fn force_shrink(
+        &self,
+        slot_id: Uuid,
+        doctor_id: i32,
+        edit_slot_entity: EditSlotEntity,
+        cascade_action: SlotCascadeAction,
+    ) -> DomainResult<Vec<AffectedAppointmentModel>> {
+        let mut conn = self.db_pool.get().await?;
+
+        let affected_appointments = conn
+            .transaction(|conn| {
+                async move {
+                    let slot = Self::lock_own_future_slot(conn, slot_id, doctor_id).await?;
+                    let max_appointment_count = edit_slot_entity
+                        .max_appointment_count
+                        .unwrap_or(slot.max_appointment_count);
+                    let excess_count = slot.current_appointment_count - max_appointment_count;
+
+                    let affected_appointments = if excess_count > 0 {
+                        let slot_appointments =
+                            AppointmentViewingDao::get_slot_appointments_by_slot_id(conn, slot_id)
+                                .await?
+                                .into_iter()
+                                .take(excess_count as usize)
+                                .collect();
+                        Self::cascade(conn, &slot, slot_appointments, cascade_action).await?
+                    } else {
+                        Vec::new()
+                    };
+
+                    SlotOpsDao::edit(conn, slot_id, doctor_id, edit_slot_entity).await?;
+                    Ok::<_, DomainError>(affected_appointments)
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        Ok(affected_appointments)
+    }
+
+    async fn force_remove(
+        &self,
+        slot_id: Uuid,
+        doctor_id: i32,
+        cascade_action: SlotCascadeAction,
+    ) -> DomainResult<Vec<AffectedAppointmentModel>> {
+        let mut conn = self.db_pool.get().await?;
+
+        let affected_appointments = conn
+            .transaction(|conn| {
+                async move {
+                    let slot = Self::lock_own_future_slot(conn, slot_id, doctor_id).await?;
+                    let slot_appointments =
+                        AppointmentViewingDao::get_slot_appointments_by_slot_id(conn, slot_id)
+                            .await?;
+                    let affected_appointments =
+                        Self::cascade(conn, &slot, slot_appointments, cascade_action).await?;
+
+                    SlotOpsDao::remove(conn, slot_id, doctor_id).await?;
+
+                    let slot_removed = SlotRemoved { slot_id, doctor_id };
+                    OutboxEventDao::add(
+                        conn,
+                        DomainEvent::SlotRemoved(slot_removed),
+                        chrono::Utc::now().naive_utc(),
+                    )
+                    .await?;
+                    Ok::<_, DomainError>(affected_appointments)
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        Ok(affected_appointments)
+    }
 }
@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use diesel_async::{AsyncConnection, scoped_futures::ScopedFutureExt};
+
+use crate::{
+    domain::{
+        errors::DomainResult,
+        repositories::slot_reconciliation::SlotReconciliationRepository,
+        value_objects::slot_reconciliation_model::{
+            SlotAppointmentCountDriftModel, SlotAppointmentCountDriftOutcome,
+            SlotReconciliationReportModel,
+        },
+    },
+    infrastructure::postgres::{
+        postgres_connection::PgPoolSquad,
+        repositories::data_access_objects::{slot_ops::SlotOpsDao, slot_viewing::SlotViewingDao},
+    },
+};
+
+pub struct SlotReconciliationPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl SlotReconciliationPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+impl SlotReconciliationRepository for SlotReconciliationPostgres {
+    async fn reconcile_appointment_counts(
+        &self,
+        repair: bool,
+    ) -> DomainResult<SlotReconciliationReportModel> {
+        let mut conn = self.db_pool.get().await?;
+
+        let drift_entities = SlotViewingDao::get_appointment_count_drifts(&mut conn).await?;
+
+        let mut drifts = Vec::with_capacity(drift_entities.len());
+        let mut repaired_count = 0;
+        for drift_entity in drift_entities {
+            let outcome =
+                if drift_entity.actual_appointment_count > drift_entity.max_appointment_count {
+                    SlotAppointmentCountDriftOutcome::OverCapacity
+                } else if !repair {
+                    SlotAppointmentCountDriftOutcome::Detected
+                } else {
+                    // One transaction per slot so a busy slot is never locked for
+                    // longer than its own recount.
+                    let slot_id = drift_entity.slot_id;
+                    let appointment_count = conn
+                        .transaction(|conn| {
+                            async move { SlotOpsDao::reset_appointment_count(conn, slot_id).await }
+                                .scope_boxed()
+                        })
+                        .await?;
+
+                    match appointment_count {
+                        Some(_) => {
+                            repaired_count += 1;
+                            SlotAppointmentCountDriftOutcome::Repaired
+                        }
+                        None => SlotAppointmentCountDriftOutcome::OverCapacity,
+                    }
+                };
+
+            drifts.push(SlotAppointmentCountDriftModel::from_entity(
+                drift_entity,
+                outcome,
+            ));
+        }
+
+        Ok(SlotReconciliationReportModel {
+            repair,
+            drifts,
+            repaired_count,
+        })
+    }
+}
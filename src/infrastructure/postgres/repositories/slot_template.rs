@@ -0,0 +1,281 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, FixedOffset, Utc};
+use chrono_tz::Tz;
+use diesel_async::{AsyncConnection, AsyncPgConnection, scoped_futures::ScopedFutureExt};
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        entities::{
+            slot_templates::{AddSlotTemplateEntity, EditSlotTemplateEntity, SlotTemplateEntity},
+            slots::AddSlotEntity,
+        },
+        errors::{DomainError, DomainResult},
+        repositories::slot_template::SlotTemplateRepository,
+        value_objects::outbox_model::{DomainEvent, SlotRemoved},
+        value_objects::slot_template_model::{
+            MaterializeSlotTemplateResponseModel, SlotOccurrenceModel, SlotOccurrenceOutcome,
+        },
+        value_objects::timezone_model::doctor_timezone,
+    },
+    infrastructure::postgres::{
+        postgres_connection::PgPoolSquad,
+        repositories::data_access_objects::{
+            doctor_timezone::DoctorTimezoneDao, outbox_event::OutboxEventDao, slot_ops::SlotOpsDao,
+            slot_template::SlotTemplateDao, slot_viewing::SlotViewingDao,
+        },
+    },
+};
+
+pub struct SlotTemplatePostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl SlotTemplatePostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+
+    /// Removes the future slots of a template that nobody booked yet,
+    /// returning how many were removed.
+    async fn remove_unbooked_slots(
+        conn: &mut AsyncPgConnection,
+        template_id: Uuid,
+        doctor_id: i32,
+    ) -> DomainResult<usize> {
+        let now = chrono::Utc::now();
+        let removed_slot_ids =
+            SlotOpsDao::remove_unbooked_slots_by_template_id(conn, template_id, now).await?;
+
+        for &slot_id in &removed_slot_ids {
+            let slot_removed = SlotRemoved { slot_id, doctor_id };
+            OutboxEventDao::add(
+                conn,
+                DomainEvent::SlotRemoved(slot_removed),
+                now.naive_utc(),
+            )
+            .await?;
+        }
+
+        Ok(removed_slot_ids.len())
+    }
+
+    /// Creates a slot for every time that is in the future and does not overlap
+    /// another slot of the doctor.
+    async fn materialize(
+        conn: &mut AsyncPgConnection,
+        template_id: Uuid,
+        doctor_id: i32,
+        max_appointment_count: i32,
+        slot_times: Vec<(DateTime<FixedOffset>, DateTime<FixedOffset>)>,
+    ) -> DomainResult<Vec<SlotOccurrenceModel>> {
+        let now = chrono::Utc::now();
+        let mut occurrences = Vec::with_capacity(slot_times.len());
+
+        for (start_time, end_time) in slot_times {
+            let outcome = if start_time <= now {
+                SlotOccurrenceOutcome::Past
+            } else if SlotViewingDao::is_overlapping_slots_for_doctor_id(
+                conn,
+                start_time.with_timezone(&Utc),
+                end_time.with_timezone(&Utc),
+                doctor_id,
+            )
+            .await?
+            {
+                SlotOccurrenceOutcome::Overlapping
+            } else {
+                let add_slot_entity = AddSlotEntity {
+                    doctor_id,
+                    current_appointment_count: 0,
+                    max_appointment_count,
+                    start_time: start_time.with_timezone(&Utc),
+                    end_time: end_time.with_timezone(&Utc),
+                    created_at: now.naive_utc(),
+                    updated_at: now.naive_utc(),
+                    deleted_at: None,
+                    template_id: Some(template_id),
+                };
+                let slot_id = SlotOpsDao::add(conn, add_slot_entity).await?;
+                SlotOccurrenceOutcome::Created { slot_id }
+            };
+
+            occurrences.push(SlotOccurrenceModel {
+                start_time,
+                end_time,
+                outcome,
+            });
+        }
+
+        Ok(occurrences)
+    }
+}
+
+impl SlotTemplateRepository for SlotTemplatePostgres {
+    async fn get_doctor_timezone(&self, doctor_id: i32) -> DomainResult<Tz> {
+        let mut conn = self.db_pool.get().await?;
+        let doctor_timezone_entity =
+            DoctorTimezoneDao::get_by_doctor_id(&mut conn, doctor_id).await?;
+
+        doctor_timezone(doctor_timezone_entity.as_ref())
+    }
+
+    async fn get_doctor_slot_templates(
+        &self,
+        doctor_id: i32,
+    ) -> DomainResult<Vec<SlotTemplateEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        let slot_templates =
+            SlotTemplateDao::get_doctor_slot_templates(&mut conn, doctor_id).await?;
+
+        Ok(slot_templates)
+    }
+
+    async fn get_slot_template(
+        &self,
+        template_id: Uuid,
+        doctor_id: i32,
+    ) -> DomainResult<SlotTemplateEntity> {
+        let mut conn = self.db_pool.get().await?;
+        let slot_template =
+            SlotTemplateDao::get_slot_template(&mut conn, template_id, doctor_id).await?;
+
+        Ok(slot_template)
+    }
+
+    async fn preview(
+        &self,
+        doctor_id: i32,
+        slot_times: Vec<(DateTime<FixedOffset>, DateTime<FixedOffset>)>,
+    ) -> DomainResult<Vec<SlotOccurrenceModel>> {
+        let mut conn = self.db_pool.get().await?;
+        let now = chrono::Utc::now();
+        let mut occurrences = Vec::with_capacity(slot_times.len());
+
+        for (start_time, end_time) in slot_times {
+            let outcome = if start_time <= now {
+                SlotOccurrenceOutcome::Past
+            } else if SlotViewingDao::is_overlapping_slots_for_doctor_id(
+                &mut conn,
+                start_time.with_timezone(&Utc),
+                end_time.with_timezone(&Utc),
+                doctor_id,
+            )
+            .await?
+            {
+                SlotOccurrenceOutcome::Overlapping
+            } else {
+                SlotOccurrenceOutcome::Available
+            };
+
+            occurrences.push(SlotOccurrenceModel {
+                start_time,
+                end_time,
+                outcome,
+            });
+        }
+
+        Ok(occurrences)
+    }
+
+    async fn add(
+        &self,
+        add_slot_template_entity: AddSlotTemplateEntity,
+        slot_times: Vec<(DateTime<FixedOffset>, DateTime<FixedOffset>)>,
+    ) -> DomainResult<MaterializeSlotTemplateResponseModel> {
+        let mut conn = self.db_pool.get().await?;
+
+        let result = conn
+            .transaction(|conn| {
+                async move {
+                    let doctor_id = add_slot_template_entity.doctor_id;
+                    let max_appointment_count = add_slot_template_entity.max_appointment_count;
+
+                    let template_id = SlotTemplateDao::add(conn, add_slot_template_entity).await?;
+                    let occurrences = Self::materialize(
+                        conn,
+                        template_id,
+                        doctor_id,
+                        max_appointment_count,
+                        slot_times,
+                    )
+                    .await?;
+
+                    Ok::<_, DomainError>(MaterializeSlotTemplateResponseModel {
+                        template_id,
+                        removed_slot_count: 0,
+                        occurrences,
+                    })
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn edit(
+        &self,
+        template_id: Uuid,
+        doctor_id: i32,
+        edit_slot_template_entity: EditSlotTemplateEntity,
+        slot_times: Vec<(DateTime<FixedOffset>, DateTime<FixedOffset>)>,
+    ) -> DomainResult<MaterializeSlotTemplateResponseModel> {
+        let mut conn = self.db_pool.get().await?;
+
+        let result = conn
+            .transaction(|conn| {
+                async move {
+                    SlotTemplateDao::lock(conn, template_id, doctor_id).await?;
+
+                    let max_appointment_count = edit_slot_template_entity.max_appointment_count;
+                    SlotTemplateDao::edit(conn, template_id, doctor_id, edit_slot_template_entity)
+                        .await?;
+
+                    let removed_slot_count =
+                        Self::remove_unbooked_slots(conn, template_id, doctor_id).await?;
+
+                    let occurrences = Self::materialize(
+                        conn,
+                        template_id,
+                        doctor_id,
+                        max_appointment_count,
+                        slot_times,
+                    )
+                    .await?;
+
+                    Ok::<_, DomainError>(MaterializeSlotTemplateResponseModel {
+                        template_id,
+                        removed_slot_count,
+                        occurrences,
+                    })
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn remove(&self, template_id: Uuid, doctor_id: i32) -> DomainResult<usize> {
+        let mut conn = self.db_pool.get().await?;
+
+        let removed_slot_count = conn
+            .transaction(|conn| {
+                async move {
+                    SlotTemplateDao::lock(conn, template_id, doctor_id).await?;
+
+                    let removed_slot_count =
+                        Self::remove_unbooked_slots(conn, template_id, doctor_id).await?;
+                    SlotTemplateDao::remove(conn, template_id, doctor_id).await?;
+
+                    Ok::<_, DomainError>(removed_slot_count)
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        Ok(removed_slot_count)
+    }
+}
@@ -1,9 +1,10 @@
 use std::sync::Arc;
 
-use anyhow::Result;
-
 use crate::{
-    domain::{entities::slots::SlotEntity, repositories::slot_viewing::SlotViewingRepository},
+    domain::{
+        entities::slots::SlotEntity, errors::DomainResult,
+        repositories::slot_viewing::SlotViewingRepository, value_objects::slot_model::SlotFilter,
+    },
     infrastructure::postgres::{
         postgres_connection::PgPoolSquad,
         repositories::data_access_objects::slot_viewing::SlotViewingDao,
@@ -21,14 +22,15 @@ impl SlotViewingPostgres {
 }
 
 impl SlotViewingRepository for SlotViewingPostgres {
-    async fn get_slots(&self) -> Result<Vec<SlotEntity>> {
+    async fn get_slots(&self, slot_filter: SlotFilter) -> DomainResult<(Vec<SlotEntity>, i64)> {
         let mut conn = self.db_pool.get().await?;
-        let slots = SlotViewingDao::get_slots(&mut conn).await?;
+        let total = SlotViewingDao::count_slots(&mut conn, &slot_filter).await?;
+        let slots = SlotViewingDao::get_slots(&mut conn, &slot_filter).await?;
 
-        Ok(slots)
+        Ok((slots, total))
     }
 
-    async fn get_doctor_slots(&self, doctor_id: i32) -> Result<Vec<SlotEntity>> {
+    async fn get_doctor_slots(&self, doctor_id: i32) -> DomainResult<Vec<SlotEntity>> {
         let mut conn = self.db_pool.get().await?;
         let slots = SlotViewingDao::get_doctor_slots(&mut conn, doctor_id).await?;
 
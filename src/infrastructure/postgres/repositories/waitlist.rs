@@ -0,0 +1,242 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use diesel_async::{AsyncConnection, AsyncPgConnection, scoped_futures::ScopedFutureExt};
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        entities::waitlist_entries::AddWaitlistEntryEntity,
+        errors::{DomainError, DomainResult},
+        repositories::waitlist::WaitlistRepository,
+        value_objects::{
+            actor::Actor,
+            appointment_history_model::{AppointmentHistoryAction, AppointmentHistoryRecord},
+            appointment_status::AppointmentStatus,
+            booking_policy::BookingPolicy,
+            outbox_model::{AppointmentBooked, DomainEvent},
+            waitlist_model::{
+                JoinWaitlistResponseModel, WaitlistPositionModel, to_promoted_appointment_entity,
+            },
+        },
+    },
+    infrastructure::postgres::{
+        postgres_connection::PgPoolSquad,
+        repositories::{
+            appointment_ops::AppointmentOpsPostgres,
+            data_access_objects::{
+                appointment_ops::AppointmentOpsDao,
+                appointment_status_history::AppointmentStatusHistoryDao,
+                outbox_event::OutboxEventDao, slot_ops::SlotOpsDao, slot_viewing::SlotViewingDao,
+                waitlist_entry::WaitlistEntryDao,
+            },
+        },
+    },
+};
+
+pub struct WaitlistPostgres {
+    db_pool: Arc<PgPoolSquad>,
+    booking_policy: BookingPolicy,
+}
+
+impl WaitlistPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>, booking_policy: BookingPolicy) -> Self {
+        Self {
+            db_pool,
+            booking_policy,
+        }
+    }
+
+    /// Gives a place that was just freed in a locked slot to the first patient
+    /// on its waitlist who may still book it under `booking_policy`, returning
+    /// the new appointment's id. Patients who may not, or who are booking
+    /// something else right now, keep their place in the queue.
+    ///
+    /// Must be called in the same transaction that freed the place.
+    pub(crate) async fn promote_first(
+        conn: &mut AsyncPgConnection,
+        booking_policy: BookingPolicy,
+        slot_id: Uuid,
+        current_time: DateTime<Utc>,
+    ) -> DomainResult<Option<Uuid>> {
+        let end_time = SlotViewingDao::get_end_time_by_slot_id(conn, slot_id).await?;
+        if current_time >= end_time {
+            return Ok(None);
+        }
+
+        let slot_is_not_full = SlotOpsDao::has_free_place(conn, slot_id).await?;
+        if !slot_is_not_full {
+            return Ok(None);
+        }
+
+        let mut promoted_waitlist_entry = None;
+        for waitlist_entry in WaitlistEntryDao::get_all_by_slot_id(conn, slot_id).await? {
+            // The slot is already locked, waiting for a patient who is booking
+            // (patient lock, then slot lock) could deadlock.
+            let patient_is_locked =
+                AppointmentOpsDao::try_lock_patient(conn, waitlist_entry.patient_id).await?;
+            if !patient_is_locked {
+                continue;
+            }
+
+            match AppointmentOpsPostgres::enforce_booking_policy(
+                conn,
+                booking_policy,
+                waitlist_entry.patient_id,
+                slot_id,
+                None,
+                current_time,
+            )
+            .await
+            {
+                Ok(()) => {
+                    promoted_waitlist_entry = Some(waitlist_entry);
+                    break;
+                }
+                Err(DomainError::Internal(e)) => return Err(DomainError::Internal(e)),
+                Err(_) => continue,
+            }
+        }
+        let Some(waitlist_entry) = promoted_waitlist_entry else {
+            return Ok(None);
+        };
+
+        let appointment_id = AppointmentOpsDao::add(
+            conn,
+            to_promoted_appointment_entity(&waitlist_entry, current_time.naive_utc()),
+        )
+        .await?;
+
+        let appointment_history_record = AppointmentHistoryRecord {
+            action: AppointmentHistoryAction::Create,
+            previous_status: None,
+            new_status: Some(AppointmentStatus::Waiting),
+            actor: Actor::patient(waitlist_entry.patient_id),
+            note: Some("Promoted from the waitlist".to_string()),
+        };
+        AppointmentStatusHistoryDao::add(
+            conn,
+            appointment_id,
+            appointment_history_record,
+            current_time.naive_utc(),
+        )
+        .await?;
+
+        let appointment_booked = AppointmentBooked {
+            appointment_id,
+            slot_id,
+            patient_id: waitlist_entry.patient_id,
+            actor: Actor::patient(waitlist_entry.patient_id),
+        };
+        OutboxEventDao::add(
+            conn,
+            DomainEvent::AppointmentBooked(appointment_booked),
+            current_time.naive_utc(),
+        )
+        .await?;
+
+        WaitlistEntryDao::remove_by_id(conn, waitlist_entry.id).await?;
+
+        Ok(Some(appointment_id))
+    }
+}
+
+impl WaitlistRepository for WaitlistPostgres {
+    async fn join(
+        &self,
+        add_waitlist_entry_entity: AddWaitlistEntryEntity,
+    ) -> DomainResult<JoinWaitlistResponseModel> {
+        let mut conn = self.db_pool.get().await?;
+        let booking_policy = self.booking_policy;
+
+        let result = conn
+            .transaction(|conn| {
+                async move {
+                    let slot_id = add_waitlist_entry_entity.slot_id;
+                    let patient_id = add_waitlist_entry_entity.patient_id;
+                    let current_time = add_waitlist_entry_entity.created_at.and_utc();
+
+                    let end_time = SlotViewingDao::get_end_time_by_slot_id(conn, slot_id).await?;
+                    if current_time > end_time {
+                        return Err(DomainError::past_time("Slot is already ended!!!"));
+                    }
+
+                    AppointmentOpsPostgres::enforce_booking_policy(
+                        conn,
+                        booking_policy,
+                        patient_id,
+                        slot_id,
+                        None,
+                        current_time,
+                    )
+                    .await?;
+
+                    SlotOpsDao::lock(conn, slot_id).await?;
+
+                    let slot = SlotViewingDao::get_slot_by_id(conn, slot_id).await?;
+                    if slot.current_appointment_count < slot.max_appointment_count {
+                        return Err(DomainError::conflict(
+                            "Slot still has free places, book it instead",
+                        ));
+                    }
+
+                    let waitlist_entry =
+                        WaitlistEntryDao::add(conn, add_waitlist_entry_entity).await?;
+                    let position = WaitlistEntryDao::get_position(conn, &waitlist_entry).await?;
+
+                    Ok(JoinWaitlistResponseModel {
+                        waitlist_entry_id: waitlist_entry.id,
+                        position,
+                    })
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn get_patient_waitlist(
+        &self,
+        patient_id: i32,
+    ) -> DomainResult<Vec<WaitlistPositionModel>> {
+        let mut conn = self.db_pool.get().await?;
+        let current_time = chrono::Utc::now();
+
+        let waitlist =
+            WaitlistEntryDao::get_patient_waitlist(&mut conn, patient_id, current_time).await?;
+
+        let mut result = Vec::with_capacity(waitlist.len());
+        for (waitlist_entry, doctor_id, start_time, end_time) in waitlist {
+            let position = WaitlistEntryDao::get_position(&mut conn, &waitlist_entry).await?;
+            result.push(WaitlistPositionModel {
+                id: waitlist_entry.id,
+                slot_id: waitlist_entry.slot_id,
+                doctor_id,
+                start_time,
+                end_time,
+                position,
+                created_at: waitlist_entry.created_at,
+            });
+        }
+
+        Ok(result)
+    }
+
+    async fn leave(&self, waitlist_entry_id: Uuid, patient_id: i32) -> DomainResult<()> {
+        let mut conn = self.db_pool.get().await?;
+
+        conn.transaction(|conn| {
+            async move {
+                WaitlistEntryDao::lock_patient_entry(conn, waitlist_entry_id, patient_id).await?;
+                WaitlistEntryDao::remove(conn, waitlist_entry_id, patient_id).await?;
+
+                Ok::<(), DomainError>(())
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,338 @@
+use std::sync::Arc;
+
+use chrono::NaiveDateTime;
+use diesel_async::{AsyncConnection, AsyncPgConnection, scoped_futures::ScopedFutureExt};
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        entities::{
+            webhook_deliveries::WebhookDeliveryEntity,
+            webhook_subscriptions::{
+                AddWebhookSubscriptionEntity, EditWebhookSubscriptionEntity,
+                WebhookSubscriptionEntity,
+            },
+        },
+        errors::{DomainError, DomainResult},
+        repositories::{
+            outbox::OutboxSink,
+            webhook::{WebhookDeliveryRepository, WebhookSubscriptionRepository},
+        },
+        value_objects::{
+            outbox_model::OutboxMessage,
+            webhook_model::{WebhookAttemptModel, WebhookEventModel},
+        },
+    },
+    infrastructure::postgres::{
+        postgres_connection::PgPoolSquad,
+        repositories::data_access_objects::{
+            appointment_viewing::AppointmentViewingDao, slot_viewing::SlotViewingDao,
+            webhook_delivery::WebhookDeliveryDao, webhook_subscription::WebhookSubscriptionDao,
+        },
+    },
+};
+
+pub struct WebhookSubscriptionPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl WebhookSubscriptionPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+impl WebhookSubscriptionRepository for WebhookSubscriptionPostgres {
+    async fn add(
+        &self,
+        add_webhook_subscription_entity: AddWebhookSubscriptionEntity,
+    ) -> DomainResult<WebhookSubscriptionEntity> {
+        let mut conn = self.db_pool.get().await?;
+        WebhookSubscriptionDao::add(&mut conn, add_webhook_subscription_entity).await
+    }
+
+    async fn get_all_by_service_api_key_id(
+        &self,
+        service_api_key_id: i32,
+    ) -> DomainResult<Vec<WebhookSubscriptionEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        WebhookSubscriptionDao::get_all_by_service_api_key_id(&mut conn, service_api_key_id).await
+    }
+
+    async fn edit(
+        &self,
+        id: Uuid,
+        service_api_key_id: i32,
+        edit_webhook_subscription_entity: EditWebhookSubscriptionEntity,
+    ) -> DomainResult<WebhookSubscriptionEntity> {
+        let mut conn = self.db_pool.get().await?;
+        WebhookSubscriptionDao::edit(
+            &mut conn,
+            id,
+            service_api_key_id,
+            edit_webhook_subscription_entity,
+        )
+        .await
+    }
+
+    async fn remove(
+        &self,
+        id: Uuid,
+        service_api_key_id: i32,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<()> {
+        let mut conn = self.db_pool.get().await?;
+        WebhookSubscriptionDao::remove(&mut conn, id, service_api_key_id, current_time).await
+    }
+
+    async fn get_deliveries(
+        &self,
+        id: Uuid,
+        service_api_key_id: i32,
+        status: Option<String>,
+        limit: i64,
+    ) -> DomainResult<Vec<WebhookDeliveryEntity>> {
+        let mut conn = self.db_pool.get().await?;
+        WebhookSubscriptionDao::get_by_id_and_service_api_key_id(&mut conn, id, service_api_key_id)
+            .await?;
+
+        WebhookDeliveryDao::get_by_subscription_id(&mut conn, id, status, limit).await
+    }
+
+    async fn redeliver(
+        &self,
+        delivery_id: Uuid,
+        service_api_key_id: i32,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<WebhookDeliveryEntity> {
+        let mut conn = self.db_pool.get().await?;
+
+        let webhook_delivery = conn
+            .transaction(|conn| {
+                async move {
+                    let webhook_delivery = WebhookDeliveryDao::get_by_id(conn, delivery_id).await?;
+                    // A delivery of another key's subscription does not exist
+                    // as far as the caller is concerned.
+                    let webhook_subscription =
+                        WebhookSubscriptionDao::get_by_id_and_service_api_key_id(
+                            conn,
+                            webhook_delivery.subscription_id,
+                            service_api_key_id,
+                        )
+                        .await
+                        .map_err(|e| match e {
+                            DomainError::NotFound(_) => {
+                                DomainError::not_found("Webhook delivery not found")
+                            }
+                            e => e,
+                        })?;
+                    if webhook_subscription.disabled_at.is_some() {
+                        return Err(DomainError::conflict(
+                            "Webhook subscription is disabled, enable it before redelivering",
+                        ));
+                    }
+
+                    WebhookDeliveryDao::redeliver(conn, delivery_id, current_time).await
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        Ok(webhook_delivery)
+    }
+}
+
+pub struct WebhookDeliveryPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl WebhookDeliveryPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+impl WebhookDeliveryRepository for WebhookDeliveryPostgres {
+    async fn claim_due(
+        &self,
+        limit: i64,
+        current_time: NaiveDateTime,
+        lease_until: NaiveDateTime,
+    ) -> DomainResult<Vec<(WebhookDeliveryEntity, WebhookSubscriptionEntity)>> {
+        let mut conn = self.db_pool.get().await?;
+
+        let (mut webhook_deliveries, webhook_subscriptions) = conn
+            .transaction(|conn| {
+                async move {
+                    let ids = WebhookDeliveryDao::lock_due(conn, limit, current_time).await?;
+                    if ids.is_empty() {
+                        return Ok((Vec::new(), Vec::new()));
+                    }
+
+                    let webhook_deliveries =
+                        WebhookDeliveryDao::lease(conn, &ids, lease_until).await?;
+                    let subscription_ids = webhook_deliveries
+                        .iter()
+                        .map(|webhook_delivery| webhook_delivery.subscription_id)
+                        .collect::<Vec<_>>();
+                    let webhook_subscriptions =
+                        WebhookSubscriptionDao::get_by_ids(conn, &subscription_ids).await?;
+
+                    Ok::<_, DomainError>((webhook_deliveries, webhook_subscriptions))
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        webhook_deliveries.sort_by_key(|webhook_delivery| webhook_delivery.created_at);
+        let result = webhook_deliveries
+            .into_iter()
+            .filter_map(|webhook_delivery| {
+                let webhook_subscription = webhook_subscriptions
+                    .iter()
+                    .find(|webhook_subscription| {
+                        webhook_subscription.id == webhook_delivery.subscription_id
+                    })?
+                    .clone();
+                Some((webhook_delivery, webhook_subscription))
+            })
+            .collect();
+
+        Ok(result)
+    }
+
+    async fn record_success(
+        &self,
+        delivery_id: Uuid,
+        subscription_id: Uuid,
+        webhook_attempt: &WebhookAttemptModel,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<()> {
+        let mut conn = self.db_pool.get().await?;
+
+        conn.transaction(|conn| {
+            async move {
+                WebhookDeliveryDao::mark_succeeded(
+                    conn,
+                    delivery_id,
+                    webhook_attempt,
+                    current_time,
+                )
+                .await?;
+                WebhookSubscriptionDao::reset_failures(conn, subscription_id).await
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    async fn record_failure(
+        &self,
+        delivery_id: Uuid,
+        subscription_id: Uuid,
+        webhook_attempt: &WebhookAttemptModel,
+        next_attempt_at: Option<NaiveDateTime>,
+        disable_after_failures: i32,
+        current_time: NaiveDateTime,
+    ) -> DomainResult<bool> {
+        let mut conn = self.db_pool.get().await?;
+
+        conn.transaction(|conn| {
+            async move {
+                WebhookDeliveryDao::mark_failed(
+                    conn,
+                    delivery_id,
+                    webhook_attempt,
+                    next_attempt_at,
+                    current_time,
+                )
+                .await?;
+
+                let consecutive_failures =
+                    WebhookSubscriptionDao::add_failure(conn, subscription_id).await?;
+                if consecutive_failures < disable_after_failures {
+                    return Ok(false);
+                }
+
+                WebhookSubscriptionDao::disable(conn, subscription_id, current_time).await
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+}
+
+/// Records a delivery for every enabled webhook subscription that receives
+/// the event, then hands the event on to `S`. Deliveries that were already
+/// recorded are kept as they are, so the event may be dispatched again.
+pub struct WebhookFanOutSink<S>
+where
+    S: OutboxSink,
+{
+    db_pool: Arc<PgPoolSquad>,
+    outbox_sink: S,
+}
+
+impl<S> WebhookFanOutSink<S>
+where
+    S: OutboxSink + Send + Sync,
+{
+    pub fn new(db_pool: Arc<PgPoolSquad>, outbox_sink: S) -> Self {
+        Self {
+            db_pool,
+            outbox_sink,
+        }
+    }
+
+    /// Doctors the event is about, from the slots in its payload or else from
+    /// the slot of its appointment.
+    async fn get_doctor_ids(
+        conn: &mut AsyncPgConnection,
+        outbox_message: &OutboxMessage,
+    ) -> DomainResult<Vec<i32>> {
+        let slot_ids = outbox_message.slot_ids();
+        if slot_ids.is_empty() {
+            let (_, doctor_id) =
+                AppointmentViewingDao::get_patient_id_and_doctor_id_by_appointment_id(
+                    conn,
+                    outbox_message.aggregate_id,
+                )
+                .await?;
+            return Ok(vec![doctor_id]);
+        }
+
+        SlotViewingDao::get_doctor_ids_by_slot_ids(conn, slot_ids).await
+    }
+}
+
+impl<S> OutboxSink for WebhookFanOutSink<S>
+where
+    S: OutboxSink + Send + Sync,
+{
+    async fn deliver(&self, outbox_message: &OutboxMessage) -> DomainResult<()> {
+        let mut conn = self.db_pool.get().await?;
+
+        let doctor_ids = Self::get_doctor_ids(&mut conn, outbox_message).await?;
+        let subscription_ids = WebhookSubscriptionDao::get_enabled_ids_by_event(
+            &mut conn,
+            &outbox_message.event_type,
+            doctor_ids,
+        )
+        .await?;
+        if !subscription_ids.is_empty() {
+            let current_time = chrono::Utc::now().naive_utc();
+            let webhook_event = WebhookEventModel::from_message(outbox_message);
+            let add_webhook_delivery_entities = subscription_ids
+                .into_iter()
+                .map(|subscription_id| {
+                    webhook_event.to_delivery_entity(subscription_id, current_time)
+                })
+                .collect::<DomainResult<Vec<_>>>()?;
+
+            WebhookDeliveryDao::add_all(&mut conn, add_webhook_delivery_entities).await?;
+        }
+        drop(conn);
+
+        self.outbox_sink.deliver(outbox_message).await
+    }
+}
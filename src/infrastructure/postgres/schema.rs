@@ -1,5 +1,36 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    appointment_reminders (id) {
+        id -> Uuid,
+        appointment_id -> Uuid,
+        offset_minutes -> Int4,
+        start_time -> Timestamptz,
+        created_at -> Timestamp,
+        sent_at -> Nullable<Timestamp>,
+        lease_until -> Timestamp,
+    }
+}
+
+diesel::table! {
+    appointment_status_history (id) {
+        id -> Uuid,
+        appointment_id -> Uuid,
+        #[max_length = 50]
+        action -> Varchar,
+        #[max_length = 50]
+        previous_status -> Nullable<Varchar>,
+        #[max_length = 50]
+        new_status -> Nullable<Varchar>,
+        #[max_length = 50]
+        actor_role -> Varchar,
+        actor_id -> Int4,
+        #[max_length = 500]
+        note -> Nullable<Varchar>,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     appointments (id) {
         id -> Uuid,
@@ -20,6 +51,94 @@ diesel::table! {
         created_at -> Timestamp,
         updated_at -> Timestamp,
         deleted_at -> Nullable<Timestamp>,
+        #[max_length = 500]
+        cancellation_reason -> Nullable<Varchar>,
+        cancelled_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    doctor_booking_windows (doctor_id) {
+        doctor_id -> Int4,
+        min_booking_lead_minutes -> Nullable<Int4>,
+        max_booking_horizon_days -> Nullable<Int4>,
+        min_change_notice_minutes -> Nullable<Int4>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    doctor_timezones (doctor_id) {
+        doctor_id -> Int4,
+        #[max_length = 64]
+        timezone -> Varchar,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    idempotency_keys (id) {
+        id -> Uuid,
+        patient_id -> Int4,
+        #[max_length = 50]
+        operation -> Varchar,
+        #[max_length = 255]
+        idempotency_key -> Varchar,
+        #[max_length = 64]
+        request_hash -> Varchar,
+        response -> Jsonb,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    outbox_events (id) {
+        id -> Uuid,
+        #[max_length = 50]
+        event_type -> Varchar,
+        aggregate_id -> Uuid,
+        payload -> Jsonb,
+        created_at -> Timestamp,
+        attempts -> Int4,
+        next_attempt_at -> Timestamp,
+        last_error -> Nullable<Text>,
+        dispatched_at -> Nullable<Timestamp>,
+        failed_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    service_api_keys (id) {
+        id -> Int4,
+        #[max_length = 100]
+        service_name -> Varchar,
+        #[max_length = 16]
+        key_prefix -> Varchar,
+        #[max_length = 255]
+        key_hash -> Varchar,
+        scopes -> Array<Text>,
+        created_at -> Timestamp,
+        revoked_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    slot_templates (id) {
+        id -> Uuid,
+        doctor_id -> Int4,
+        #[max_length = 27]
+        weekdays -> Varchar,
+        day_start_time -> Time,
+        day_end_time -> Time,
+        slot_duration_minutes -> Int4,
+        max_appointment_count -> Int4,
+        start_date -> Date,
+        end_date -> Date,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+        deleted_at -> Nullable<Timestamp>,
     }
 }
 
@@ -29,17 +148,106 @@ diesel::table! {
         doctor_id -> Int4,
         current_appointment_count -> Int4,
         max_appointment_count -> Int4,
-        start_time -> Timestamp,
-        end_time -> Timestamp,
+        start_time -> Timestamptz,
+        end_time -> Timestamptz,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+        deleted_at -> Nullable<Timestamp>,
+        template_id -> Nullable<Uuid>,
+    }
+}
+
+diesel::table! {
+    staff_actions (id) {
+        id -> Uuid,
+        staff_id -> Int4,
+        #[max_length = 50]
+        action -> Varchar,
+        target_id -> Uuid,
+        #[max_length = 500]
+        note -> Nullable<Varchar>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    waitlist_entries (id) {
+        id -> Uuid,
+        slot_id -> Uuid,
+        patient_id -> Int4,
+        #[max_length = 255]
+        patient_abnormal_symptom -> Varchar,
+        #[max_length = 255]
+        patient_is_missed_medication -> Varchar,
+        #[max_length = 255]
+        patient_blood_test_status -> Varchar,
+        #[max_length = 255]
+        patient_is_overdue_medication -> Varchar,
+        #[max_length = 255]
+        patient_is_partner_hiv_positive -> Varchar,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    webhook_deliveries (id) {
+        id -> Uuid,
+        subscription_id -> Uuid,
+        outbox_event_id -> Uuid,
+        #[max_length = 50]
+        event_type -> Varchar,
+        body -> Jsonb,
+        #[max_length = 20]
+        status -> Varchar,
+        attempts -> Int4,
+        next_attempt_at -> Timestamp,
+        last_response_status -> Nullable<Int4>,
+        last_error -> Nullable<Text>,
+        created_at -> Timestamp,
+        delivered_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    webhook_subscriptions (id) {
+        id -> Uuid,
+        service_api_key_id -> Int4,
+        #[max_length = 2048]
+        url -> Varchar,
+        event_types -> Array<Text>,
+        #[max_length = 255]
+        secret -> Varchar,
+        consecutive_failures -> Int4,
+        disabled_at -> Nullable<Timestamp>,
         created_at -> Timestamp,
         updated_at -> Timestamp,
         deleted_at -> Nullable<Timestamp>,
+        doctor_ids -> Array<Int4>,
     }
 }
 
+diesel::joinable!(appointment_reminders -> appointments (appointment_id));
+diesel::joinable!(appointment_status_history -> appointments (appointment_id));
 diesel::joinable!(appointments -> slots (slot_id));
+diesel::joinable!(slots -> slot_templates (template_id));
+diesel::joinable!(waitlist_entries -> slots (slot_id));
+diesel::joinable!(webhook_deliveries -> outbox_events (outbox_event_id));
+diesel::joinable!(webhook_deliveries -> webhook_subscriptions (subscription_id));
+diesel::joinable!(webhook_subscriptions -> service_api_keys (service_api_key_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    appointment_reminders,
+    appointment_status_history,
     appointments,
+    doctor_booking_windows,
+    doctor_timezones,
+    idempotency_keys,
+    outbox_events,
+    service_api_keys,
+    slot_templates,
     slots,
+    staff_actions,
+    waitlist_entries,
+    webhook_deliveries,
+    webhook_subscriptions,
 );
@@ -0,0 +1,40 @@
+use anyhow::anyhow;
+use tokio::{fs::OpenOptions, io::AsyncWriteExt};
+
+use crate::domain::{
+    errors::{DomainError, DomainResult},
+    repositories::appointment_reminder::ReminderNotifier,
+    value_objects::reminder_model::AppointmentReminderModel,
+};
+
+/// Appends each reminder as a line of JSON to a file, e.g. for tests or for
+/// another process to pick up.
+pub struct FileReminderNotifier {
+    path: String,
+}
+
+impl FileReminderNotifier {
+    pub fn new(path: String) -> Self {
+        Self { path }
+    }
+}
+
+impl ReminderNotifier for FileReminderNotifier {
+    async fn notify(&self, appointment_reminder: &AppointmentReminderModel) -> DomainResult<()> {
+        let mut line = serde_json::to_vec(appointment_reminder)
+            .map_err(|e| DomainError::Internal(e.into()))?;
+        line.push(b'\n');
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .map_err(|e| anyhow!("Failed to open {}: {}", self.path, e))?;
+        file.write_all(&line)
+            .await
+            .map_err(|e| anyhow!("Failed to write to {}: {}", self.path, e))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+use tracing::info;
+
+use crate::domain::{
+    errors::DomainResult, repositories::appointment_reminder::ReminderNotifier,
+    value_objects::reminder_model::AppointmentReminderModel,
+};
+
+/// Writes reminders to the service log. Never fails.
+pub struct LogReminderNotifier;
+
+impl ReminderNotifier for LogReminderNotifier {
+    async fn notify(&self, appointment_reminder: &AppointmentReminderModel) -> DomainResult<()> {
+        info!(
+            "Reminder for appointment {} of patient {} with doctor {} at {} ({} minutes ahead)",
+            appointment_reminder.appointment_id,
+            appointment_reminder.patient_id,
+            appointment_reminder.doctor_id,
+            appointment_reminder.start_time,
+            appointment_reminder.offset_minutes
+        );
+
+        Ok(())
+    }
+}
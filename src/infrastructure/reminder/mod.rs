@@ -0,0 +1,3 @@
+pub mod file_notifier;
+pub mod log_notifier;
+pub mod scheduler;
@@ -0,0 +1,97 @@
+use std::{sync::Arc, time::Duration};
+
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use crate::{
+    application::usecases::appointment_reminder::AppointmentReminderUseCase,
+    config::config_model::{AppointmentReminders, ReminderNotifierKind},
+    domain::repositories::appointment_reminder::{AppointmentReminderRepository, ReminderNotifier},
+    infrastructure::{
+        postgres::{
+            postgres_connection::PgPoolSquad,
+            repositories::appointment_reminder::AppointmentReminderPostgres,
+        },
+        reminder::{file_notifier::FileReminderNotifier, log_notifier::LogReminderNotifier},
+    },
+};
+
+/// Starts sending appointment reminders to the configured notifier in the
+/// background.
+pub fn spawn(
+    appointment_reminders: AppointmentReminders,
+    db_pool: Arc<PgPoolSquad>,
+) -> JoinHandle<()> {
+    let appointment_reminder_repository = Arc::new(AppointmentReminderPostgres::new(db_pool));
+    let poll_interval = Duration::from_secs(appointment_reminders.poll_interval_seconds);
+
+    info!(
+        "Appointment reminders are sent {:?} minutes ahead to {:?}",
+        appointment_reminders.schedule.offsets_minutes(),
+        appointment_reminders.notifier
+    );
+
+    match appointment_reminders.notifier.clone() {
+        ReminderNotifierKind::Log => tokio::spawn(run(
+            use_case(
+                &appointment_reminders,
+                appointment_reminder_repository,
+                LogReminderNotifier,
+            ),
+            poll_interval,
+        )),
+        ReminderNotifierKind::File { path } => tokio::spawn(run(
+            use_case(
+                &appointment_reminders,
+                appointment_reminder_repository,
+                FileReminderNotifier::new(path),
+            ),
+            poll_interval,
+        )),
+    }
+}
+
+fn use_case<T, N>(
+    appointment_reminders: &AppointmentReminders,
+    appointment_reminder_repository: Arc<T>,
+    reminder_notifier: N,
+) -> AppointmentReminderUseCase<T, N>
+where
+    T: AppointmentReminderRepository + Send + Sync,
+    N: ReminderNotifier + Send + Sync,
+{
+    AppointmentReminderUseCase::new(
+        appointment_reminder_repository,
+        Arc::new(reminder_notifier),
+        appointment_reminders.schedule.clone(),
+        appointment_reminders.batch_size,
+        appointment_reminders.lease_seconds,
+    )
+}
+
+/// Looks for due reminders forever, every `poll_interval`.
+async fn run<T, N>(
+    appointment_reminder_use_case: AppointmentReminderUseCase<T, N>,
+    poll_interval: Duration,
+) where
+    T: AppointmentReminderRepository + Send + Sync,
+    N: ReminderNotifier + Send + Sync,
+{
+    loop {
+        match appointment_reminder_use_case.send_due().await {
+            Ok(report) => {
+                for (appointment_id, offset_minutes) in &report.reclaimed {
+                    warn!(
+                        "Reminder of appointment {appointment_id} {offset_minutes} minutes ahead was claimed but not sent before its lease ran out, tried it again"
+                    );
+                }
+                for (appointment_id, e) in &report.failed {
+                    error!("Failed to send the reminder of appointment {appointment_id}: {e}");
+                }
+            }
+            Err(e) => error!("Failed to send appointment reminders: {e}"),
+        }
+
+        tokio::time::sleep(poll_interval).await;
+    }
+}
@@ -1,10 +1,21 @@
 use std::sync::Arc;
 
 use medbook_bookingservice::{
+    application::usecases::{
+        service_api_key::ServiceApiKeyUseCase, slot_reconciliation::SlotReconciliationUseCase,
+    },
     config::config_loader,
+    domain::{errors::DomainResult, value_objects::service_api_key_model::ServiceScope},
     infrastructure::{
         axum_http::http_serve::start,
-        postgres::{postgres_connection, postgres_migration},
+        postgres::{
+            postgres_connection::{self, PgPoolSquad},
+            postgres_migration,
+            repositories::{
+                service_api_key::ServiceApiKeyPostgres,
+                slot_reconciliation::SlotReconciliationPostgres,
+            },
+        },
     },
 };
 use tracing::{error, info};
@@ -43,7 +54,110 @@ async fn main() {
 
     info!("Database migrations have been applied successfully");
 
-    start(Arc::new(dotenvy_env), Arc::new(postgres_pool))
+    let postgres_pool = Arc::new(postgres_pool);
+
+    match std::env::args().nth(1).as_deref() {
+        None => {}
+        Some("reconcile-slot-counts") => {
+            let repair = std::env::args().skip(2).any(|arg| arg == "--repair");
+            reconcile_slot_counts(postgres_pool, repair).await;
+            return;
+        }
+        Some("create-service-key") => {
+            let mut args = std::env::args().skip(2);
+            let service_name = args.next().unwrap_or_default();
+            let scopes = args.map(|scope| scope.parse()).collect();
+            create_service_key(postgres_pool, &service_name, scopes).await;
+            return;
+        }
+        Some("revoke-service-key") => {
+            let key_prefix = std::env::args().nth(2).unwrap_or_default();
+            revoke_service_key(postgres_pool, &key_prefix).await;
+            return;
+        }
+        Some(command) => {
+            error!("Unknown command: {command}");
+            std::process::exit(1);
+        }
+    }
+
+    start(Arc::new(dotenvy_env), postgres_pool)
         .await
         .expect("Failed to start server")
 }
+
+/// Reports slots whose `current_appointment_count` drifted from their
+/// appointments, recounting them when `repair` is set.
+async fn reconcile_slot_counts(postgres_pool: Arc<PgPoolSquad>, repair: bool) {
+    let slot_reconciliation_repository = SlotReconciliationPostgres::new(postgres_pool);
+    let slot_reconciliation_use_case =
+        SlotReconciliationUseCase::new(Arc::new(slot_reconciliation_repository));
+
+    let report = match slot_reconciliation_use_case.reconcile(repair).await {
+        Ok(report) => report,
+        Err(e) => {
+            error!("Failed to reconcile slot appointment counts: {e}");
+            std::process::exit(1);
+        }
+    };
+
+    for drift in &report.drifts {
+        info!(
+            "Slot {} of doctor {}: recorded {}, actual {}, max {} ({:?})",
+            drift.slot_id,
+            drift.doctor_id,
+            drift.recorded_appointment_count,
+            drift.actual_appointment_count,
+            drift.max_appointment_count,
+            drift.outcome
+        );
+    }
+
+    info!(
+        "Slot appointment counts: {} drifted, {} repaired",
+        report.drifts.len(),
+        report.repaired_count
+    );
+}
+
+/// Issues an API key for another MedBook service. The key is printed once,
+/// only its hash is stored.
+async fn create_service_key(
+    postgres_pool: Arc<PgPoolSquad>,
+    service_name: &str,
+    scopes: DomainResult<Vec<ServiceScope>>,
+) {
+    let service_api_key_repository = ServiceApiKeyPostgres::new(postgres_pool);
+    let service_api_key_use_case = ServiceApiKeyUseCase::new(Arc::new(service_api_key_repository));
+
+    let created = match scopes {
+        Ok(scopes) => service_api_key_use_case.create(service_name, scopes).await,
+        Err(e) => Err(e),
+    };
+    let created = match created {
+        Ok(created) => created,
+        Err(e) => {
+            error!("Failed to create service API key: {e}");
+            error!("Usage: create-service-key <service name> <scope>...");
+            std::process::exit(1);
+        }
+    };
+
+    info!(
+        "Created API key {} for {} with scopes {:?}",
+        created.service_api_key.key_prefix, created.service_name, created.scopes
+    );
+    println!("{}", created.service_api_key.reveal());
+}
+
+async fn revoke_service_key(postgres_pool: Arc<PgPoolSquad>, key_prefix: &str) {
+    let service_api_key_repository = ServiceApiKeyPostgres::new(postgres_pool);
+    let service_api_key_use_case = ServiceApiKeyUseCase::new(Arc::new(service_api_key_repository));
+
+    if let Err(e) = service_api_key_use_case.revoke(key_prefix).await {
+        error!("Failed to revoke service API key {key_prefix}: {e}");
+        std::process::exit(1);
+    }
+
+    info!("Revoked API key {key_prefix}");
+}
@@ -0,0 +1,256 @@
+//! A doctor may only change the status of appointments in their own slots.
+
+mod common;
+
+use std::sync::Arc;
+
+use axum::http::StatusCode;
+use medbook_bookingservice::{
+    application::usecases::{
+        appointment_ledger::AppointmentLedgerUseCase, appointment_ops::AppointmentOpsUseCase,
+        schedule_viewing::ScheduleViewingUseCase, slot_ops::SlotOpsUseCase,
+    },
+    domain::{
+        errors::DomainError,
+        value_objects::{
+            actor::Actor,
+            appointment_model::{CancelAppointmentDto, TransitionAppointmentDto},
+            appointment_status::AppointmentStatus,
+        },
+    },
+    infrastructure::{
+        axum_http::api_response::status_code_of,
+        postgres::repositories::{
+            appointment_ledger::AppointmentLedgerPostgres, appointment_ops::AppointmentOpsPostgres,
+            schedule_viewing::ScheduleViewingPostgres, slot_ops::SlotOpsPostgres,
+        },
+    },
+};
+use uuid::Uuid;
+
+struct Fixture {
+    appointment_ledger: AppointmentLedgerUseCase<AppointmentLedgerPostgres>,
+    appointment_ops: AppointmentOpsUseCase<AppointmentOpsPostgres>,
+    schedule_viewing: ScheduleViewingUseCase<ScheduleViewingPostgres>,
+    doctor_id: i32,
+    patient_id: i32,
+    appointment_id: Uuid,
+}
+
+impl Fixture {
+    /// A `Ready` appointment of `patient_id` in a slot of `doctor_id`.
+    async fn new() -> Self {
+        let db_pool = common::db_pool().await;
+        let slot_ops = SlotOpsUseCase::new(Arc::new(SlotOpsPostgres::new(Arc::clone(&db_pool))));
+        let appointment_ops = AppointmentOpsUseCase::new(Arc::new(AppointmentOpsPostgres::new(
+            Arc::clone(&db_pool),
+            common::LENIENT_BOOKING_POLICY,
+        )));
+        let appointment_ledger = AppointmentLedgerUseCase::new(Arc::new(
+            AppointmentLedgerPostgres::new(Arc::clone(&db_pool), common::LENIENT_BOOKING_POLICY),
+        ));
+        let schedule_viewing =
+            ScheduleViewingUseCase::new(Arc::new(ScheduleViewingPostgres::new(db_pool)));
+
+        let doctor_id = common::unique_id();
+        let patient_id = common::unique_id();
+        let slot_id = slot_ops
+            .add(doctor_id, common::add_slot_dto(10, 2))
+            .await
+            .unwrap();
+        let appointment_id = appointment_ops
+            .add(common::add_appointment_dto(slot_id), patient_id, None)
+            .await
+            .unwrap();
+        appointment_ledger
+            .to_ready(appointment_id, doctor_id)
+            .await
+            .unwrap();
+
+        Self {
+            appointment_ledger,
+            appointment_ops,
+            schedule_viewing,
+            doctor_id,
+            patient_id,
+            appointment_id,
+        }
+    }
+
+    /// The status and the number of history entries of the appointment.
+    async fn snapshot(&self) -> (String, usize) {
+        let schedule = self
+            .schedule_viewing
+            .get_schedule(self.appointment_id, Default::default())
+            .await
+            .unwrap();
+        let history = self
+            .schedule_viewing
+            .get_appointment_history(self.appointment_id, Actor::doctor(self.doctor_id))
+            .await
+            .unwrap();
+
+        (schedule.status, history.history.len())
+    }
+}
+
+fn transition_dto(target: AppointmentStatus) -> TransitionAppointmentDto {
+    TransitionAppointmentDto {
+        target,
+        reason: None,
+    }
+}
+
+fn cancel_dto() -> CancelAppointmentDto {
+    CancelAppointmentDto {
+        reason: "Doctor is unavailable".to_string(),
+    }
+}
+
+fn assert_status_code<T: std::fmt::Debug>(result: Result<T, DomainError>, status_code: StatusCode) {
+    let e = result.expect_err("expected the request to be refused");
+    assert_eq!(status_code_of(&e), status_code, "{}", e);
+}
+
+#[tokio::test]
+#[ignore = "needs a Postgres database at DATABASE_URL"]
+async fn another_doctor_cannot_change_the_appointment() {
+    let fixture = Fixture::new().await;
+    let other_doctor_id = common::unique_id();
+    let before = fixture.snapshot().await;
+
+    assert_status_code(
+        fixture
+            .appointment_ledger
+            .transition(
+                fixture.appointment_id,
+                other_doctor_id,
+                transition_dto(AppointmentStatus::NoShow),
+            )
+            .await,
+        StatusCode::FORBIDDEN,
+    );
+    assert_status_code(
+        fixture
+            .appointment_ledger
+            .cancel(fixture.appointment_id, other_doctor_id, cancel_dto())
+            .await,
+        StatusCode::FORBIDDEN,
+    );
+    assert_status_code(
+        fixture
+            .appointment_ledger
+            .revert(fixture.appointment_id, other_doctor_id)
+            .await,
+        StatusCode::FORBIDDEN,
+    );
+
+    assert_eq!(fixture.snapshot().await, before);
+}
+
+#[tokio::test]
+#[ignore = "needs a Postgres database at DATABASE_URL"]
+async fn missing_appointment_is_not_found() {
+    let fixture = Fixture::new().await;
+
+    assert_status_code(
+        fixture
+            .appointment_ledger
+            .transition(
+                Uuid::new_v4(),
+                fixture.doctor_id,
+                transition_dto(AppointmentStatus::NoShow),
+            )
+            .await,
+        StatusCode::NOT_FOUND,
+    );
+    assert_status_code(
+        fixture
+            .appointment_ledger
+            .cancel(Uuid::new_v4(), fixture.doctor_id, cancel_dto())
+            .await,
+        StatusCode::NOT_FOUND,
+    );
+    assert_status_code(
+        fixture
+            .appointment_ledger
+            .revert(Uuid::new_v4(), fixture.doctor_id)
+            .await,
+        StatusCode::NOT_FOUND,
+    );
+}
+
+#[tokio::test]
+#[ignore = "needs a Postgres database at DATABASE_URL"]
+async fn removed_appointment_is_not_found() {
+    let fixture = Fixture::new().await;
+    fixture
+        .appointment_ledger
+        .revert(fixture.appointment_id, fixture.doctor_id)
+        .await
+        .unwrap();
+    fixture
+        .appointment_ops
+        .remove(fixture.appointment_id, fixture.patient_id)
+        .await
+        .unwrap();
+
+    assert_status_code(
+        fixture
+            .appointment_ledger
+            .transition(
+                fixture.appointment_id,
+                fixture.doctor_id,
+                transition_dto(AppointmentStatus::Ready),
+            )
+            .await,
+        StatusCode::NOT_FOUND,
+    );
+    assert_status_code(
+        fixture
+            .appointment_ledger
+            .cancel(fixture.appointment_id, fixture.doctor_id, cancel_dto())
+            .await,
+        StatusCode::NOT_FOUND,
+    );
+    assert_status_code(
+        fixture
+            .appointment_ledger
+            .revert(fixture.appointment_id, fixture.doctor_id)
+            .await,
+        StatusCode::NOT_FOUND,
+    );
+}
+
+#[tokio::test]
+#[ignore = "needs a Postgres database at DATABASE_URL"]
+async fn owning_doctor_can_change_the_appointment() {
+    let fixture = Fixture::new().await;
+
+    let result = fixture
+        .appointment_ledger
+        .cancel(fixture.appointment_id, fixture.doctor_id, cancel_dto())
+        .await
+        .unwrap();
+    assert_eq!(result, fixture.appointment_id);
+
+    let result = fixture
+        .appointment_ledger
+        .revert(fixture.appointment_id, fixture.doctor_id)
+        .await
+        .unwrap();
+    assert_eq!(result.previous_status, AppointmentStatus::Cancelled);
+    assert_eq!(result.status, AppointmentStatus::Ready);
+
+    let result = fixture
+        .appointment_ledger
+        .transition(
+            fixture.appointment_id,
+            fixture.doctor_id,
+            transition_dto(AppointmentStatus::NoShow),
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.status, AppointmentStatus::NoShow);
+    assert_eq!(fixture.snapshot().await.0, "NoShow");
+}
@@ -0,0 +1,116 @@
+//! Shared setup of the integration tests. They run against the Postgres
+//! database at `DATABASE_URL` and are `#[ignore]`d by default, run them with
+//! `cargo test -- --ignored`.
+
+#![allow(dead_code)]
+
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    body::{self, Body},
+    http::{Request, StatusCode, header},
+};
+use chrono::SubsecRound;
+use medbook_bookingservice::{
+    domain::value_objects::{
+        appointment_model::AddAppointmentDto, booking_policy::BookingPolicy,
+        booking_window::BookingWindow, slot_model::AddSlotDto,
+    },
+    infrastructure::{
+        axum_http::authenticated_user::AuthenticatedUser,
+        postgres::{
+            postgres_connection::{self, PgPoolSquad},
+            postgres_migration,
+        },
+    },
+};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+/// Only booking the same slot twice is refused, so tests can book freely.
+pub const LENIENT_BOOKING_POLICY: BookingPolicy = BookingPolicy {
+    prevent_overlapping_appointments: false,
+    one_active_appointment_per_doctor: false,
+    max_open_appointments: None,
+    booking_window: BookingWindow {
+        min_booking_lead_minutes: 0,
+        max_booking_horizon_days: None,
+        min_change_notice_minutes: 0,
+    },
+};
+
+pub async fn db_pool() -> Arc<PgPoolSquad> {
+    dotenvy::dotenv().ok();
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL is not set");
+
+    postgres_migration::run_migrations_blocking(&database_url)
+        .await
+        .expect("Failed to run migrations");
+    let db_pool = postgres_connection::establish_connection(&database_url)
+        .await
+        .expect("Failed to connect to the database");
+
+    Arc::new(db_pool)
+}
+
+/// An id no other test run uses, so tests do not see each other's rows.
+pub fn unique_id() -> i32 {
+    1_000_000 + (Uuid::new_v4().as_u128() % 1_000_000_000) as i32
+}
+
+pub fn add_slot_dto(days_ahead: i64, max_appointment_count: i32) -> AddSlotDto {
+    add_slot_dto_at(
+        chrono::Utc::now() + chrono::Duration::days(days_ahead),
+        max_appointment_count,
+    )
+}
+
+/// A 30 minute slot starting at `start_time`, to the second.
+pub fn add_slot_dto_at(
+    start_time: chrono::DateTime<chrono::Utc>,
+    max_appointment_count: i32,
+) -> AddSlotDto {
+    let start_time = start_time.trunc_subsecs(0);
+    let end_time = start_time + chrono::Duration::minutes(30);
+
+    serde_json::from_value(serde_json::json!({
+        "max_appointment_count": max_appointment_count,
+        "start_time": start_time.to_rfc3339(),
+        "end_time": end_time.to_rfc3339(),
+    }))
+    .expect("Invalid AddSlotDto")
+}
+
+pub fn add_appointment_dto(slot_id: Uuid) -> AddAppointmentDto {
+    AddAppointmentDto {
+        slot_id,
+        patient_abnormal_symptom: "None".to_string(),
+        patient_is_missed_medication: "No".to_string(),
+        patient_blood_test_status: "Done".to_string(),
+        patient_is_overdue_medication: "No".to_string(),
+        patient_is_partner_hiv_positive: "No".to_string(),
+    }
+}
+
+/// Answers with the role and id of the user the authorization middleware let
+/// through.
+pub async fn whoami(authenticated_user: AuthenticatedUser) -> String {
+    format!("{:?} {}", authenticated_user.role, authenticated_user.id)
+}
+
+/// Sends `GET /` to `router` with `token` as the bearer token, returns the
+/// status and the body.
+pub async fn get_with_token(router: Router, token: &str) -> (StatusCode, String) {
+    let request = Request::get("/")
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .body(Body::empty())
+        .expect("Invalid request");
+    let response = router.oneshot(request).await.expect("Router failed");
+
+    let status = response.status();
+    let body = body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read the body");
+    (status, String::from_utf8_lossy(&body).into_owned())
+}
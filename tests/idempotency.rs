@@ -0,0 +1,55 @@
+//! Retries of a booking sent with the same `Idempotency-Key` book once, even
+//! when they arrive while the first one is still running.
+
+mod common;
+
+use std::sync::Arc;
+
+use medbook_bookingservice::{
+    application::usecases::{appointment_ops::AppointmentOpsUseCase, slot_ops::SlotOpsUseCase},
+    infrastructure::postgres::repositories::{
+        appointment_ops::AppointmentOpsPostgres, slot_ops::SlotOpsPostgres,
+    },
+};
+use uuid::Uuid;
+
+#[tokio::test]
+#[ignore = "needs a Postgres database at DATABASE_URL"]
+async fn concurrent_retries_book_once() {
+    let db_pool = common::db_pool().await;
+    let slot_ops = SlotOpsUseCase::new(Arc::new(SlotOpsPostgres::new(Arc::clone(&db_pool))));
+    let appointment_ops = Arc::new(AppointmentOpsUseCase::new(Arc::new(
+        AppointmentOpsPostgres::new(Arc::clone(&db_pool), common::LENIENT_BOOKING_POLICY),
+    )));
+
+    let slot_id = slot_ops
+        .add(common::unique_id(), common::add_slot_dto(10, 5))
+        .await
+        .unwrap();
+    let patient_id = common::unique_id();
+    let idempotency_key = Uuid::new_v4().to_string();
+
+    let retries = (0..4)
+        .map(|_| {
+            let appointment_ops = Arc::clone(&appointment_ops);
+            let idempotency_key = idempotency_key.clone();
+            tokio::spawn(async move {
+                appointment_ops
+                    .add(
+                        common::add_appointment_dto(slot_id),
+                        patient_id,
+                        Some(idempotency_key),
+                    )
+                    .await
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut appointment_ids = Vec::new();
+    for retry in retries {
+        appointment_ids.push(retry.await.unwrap().unwrap());
+    }
+
+    appointment_ids.dedup();
+    assert_eq!(appointment_ids.len(), 1);
+}
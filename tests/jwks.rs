@@ -0,0 +1,340 @@
+//! Tokens signed by the auth service's RS256 and ES256 keys are verified
+//! against the JWKS, with the key their `kid` names, and the set is read again
+//! for an unknown `kid` at most once per refresh interval.
+
+mod common;
+
+use std::{sync::Once, time::Duration};
+
+use axum::{Router, http::StatusCode, middleware, routing::get};
+use jsonwebtoken::{
+    Algorithm, EncodingKey, Header, encode, errors::ErrorKind as JwtErrorKind, jwk::JwkSet,
+};
+use medbook_bookingservice::{
+    config::config_model::{JwksSource, JwtVerification},
+    infrastructure::{
+        axum_http::middleware::{patients_authorization, patients_or_doctors_authorization},
+        jwt_authentication::{
+            jwks::{self, JwksError, JwksKeyStore},
+            jwt_model::{Claims, Roles},
+        },
+    },
+};
+use uuid::Uuid;
+
+const JWKS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/jwt/jwks.json");
+const RS256_PRIVATE_KEY: &str = include_str!("fixtures/jwt/rs256_private.pem");
+const ES256_PRIVATE_KEY: &str = include_str!("fixtures/jwt/es256_private.pem");
+const RS256_KID: &str = "rs256-key";
+const ES256_KID: &str = "es256-key";
+const ISSUER: &str = "medbook-auth";
+const AUDIENCE: &str = "medbook-bookingservice";
+
+static JWKS_ENV: Once = Once::new();
+
+/// Points the authorization middleware at the fixture JWKS.
+fn use_jwks_env() {
+    JWKS_ENV.call_once(|| {
+        // SAFETY: the tests of this file only read these variables after this
+        // `Once` has set them, all to the same values.
+        unsafe {
+            std::env::set_var("JWT_JWKS_FILE", JWKS_PATH);
+            std::env::remove_var("JWT_JWKS_URL");
+            std::env::set_var("JWT_ISSUER", ISSUER);
+            std::env::set_var("JWT_AUDIENCE", AUDIENCE);
+        }
+    });
+}
+
+fn jwt_verification() -> JwtVerification {
+    JwtVerification {
+        jwks: Some(JwksSource::File(JWKS_PATH.to_string())),
+        jwks_cache_seconds: 300,
+        issuer: Some(ISSUER.to_string()),
+        audience: Some(AUDIENCE.to_string()),
+    }
+}
+
+fn claims(role: Roles, id: i32) -> serde_json::Value {
+    let now = chrono::Utc::now().timestamp();
+    serde_json::json!({
+        "sub": id.to_string(),
+        "role": role,
+        "iat": now,
+        "exp": now + 600,
+        "iss": ISSUER,
+        "aud": AUDIENCE,
+    })
+}
+
+/// Signs `claims` with the fixture key of `algorithm`, naming `kid`.
+fn sign(algorithm: Algorithm, kid: Option<&str>, claims: &serde_json::Value) -> String {
+    let encoding_key = match algorithm {
+        Algorithm::RS256 => EncodingKey::from_rsa_pem(RS256_PRIVATE_KEY.as_bytes()).unwrap(),
+        Algorithm::ES256 => EncodingKey::from_ec_pem(ES256_PRIVATE_KEY.as_bytes()).unwrap(),
+        _ => EncodingKey::from_secret(b"not-a-jwks-key"),
+    };
+    let mut header = Header::new(algorithm);
+    header.kid = kid.map(|kid| kid.to_string());
+
+    encode(&header, claims, &encoding_key).unwrap()
+}
+
+fn key_store(source: &str, cache_ttl: Duration) -> JwksKeyStore {
+    JwksKeyStore::new(JwksSource::File(source.to_string()), cache_ttl)
+}
+
+async fn verify(key_store: &JwksKeyStore, token: String) -> anyhow::Result<Claims> {
+    jwks::verify_token(key_store, token, &jwt_verification()).await
+}
+
+fn jwks_error(result: anyhow::Result<Claims>) -> JwksError {
+    result.unwrap_err().downcast::<JwksError>().unwrap()
+}
+
+fn jwt_error_kind(result: anyhow::Result<Claims>) -> JwtErrorKind {
+    result
+        .unwrap_err()
+        .downcast::<jsonwebtoken::errors::Error>()
+        .unwrap()
+        .into_kind()
+}
+
+/// A JWKS file of its own holding the fixture keys named by `kids`.
+fn write_jwks(path: &str, kids: &[&str]) {
+    let mut jwk_set =
+        serde_json::from_str::<JwkSet>(&std::fs::read_to_string(JWKS_PATH).unwrap()).unwrap();
+    jwk_set
+        .keys
+        .retain(|jwk| kids.contains(&jwk.common.key_id.as_deref().unwrap_or_default()));
+
+    std::fs::write(path, serde_json::to_string(&jwk_set).unwrap()).unwrap();
+}
+
+fn temp_jwks_path() -> String {
+    std::env::temp_dir()
+        .join(format!("jwks-{}.json", Uuid::new_v4()))
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[tokio::test]
+async fn rs256_and_es256_tokens_are_accepted() {
+    let key_store = key_store(JWKS_PATH, Duration::from_secs(300));
+
+    let rs256_claims = verify(
+        &key_store,
+        sign(
+            Algorithm::RS256,
+            Some(RS256_KID),
+            &claims(Roles::Patient, 7),
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        (rs256_claims.sub.as_str(), rs256_claims.role),
+        ("7", Roles::Patient)
+    );
+
+    let es256_claims = verify(
+        &key_store,
+        sign(Algorithm::ES256, Some(ES256_KID), &claims(Roles::Doctor, 8)),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        (es256_claims.sub.as_str(), es256_claims.role),
+        ("8", Roles::Doctor)
+    );
+}
+
+#[tokio::test]
+async fn tokens_without_a_known_kid_are_refused() {
+    let key_store = key_store(JWKS_PATH, Duration::from_secs(300));
+    let claims = claims(Roles::Patient, 7);
+
+    assert_eq!(
+        jwks_error(
+            verify(
+                &key_store,
+                sign(Algorithm::RS256, Some("retired-key"), &claims)
+            )
+            .await
+        ),
+        JwksError::UnknownKeyId("retired-key".to_string())
+    );
+    assert_eq!(
+        jwks_error(verify(&key_store, sign(Algorithm::RS256, None, &claims)).await),
+        JwksError::MissingKeyId
+    );
+}
+
+#[tokio::test]
+async fn tokens_of_other_algorithms_are_refused() {
+    let key_store = key_store(JWKS_PATH, Duration::from_secs(300));
+    let claims = claims(Roles::Patient, 7);
+
+    assert_eq!(
+        jwks_error(verify(&key_store, sign(Algorithm::HS256, Some(RS256_KID), &claims)).await),
+        JwksError::UnsupportedAlgorithm(Algorithm::HS256)
+    );
+    // Signed with the ES256 key, but naming the RS256 one.
+    assert_eq!(
+        jwks_error(verify(&key_store, sign(Algorithm::ES256, Some(RS256_KID), &claims)).await),
+        JwksError::UnsupportedAlgorithm(Algorithm::ES256)
+    );
+}
+
+#[tokio::test]
+async fn tokens_of_other_issuers_or_audiences_are_refused() {
+    let key_store = key_store(JWKS_PATH, Duration::from_secs(300));
+
+    let mut other_issuer = claims(Roles::Patient, 7);
+    other_issuer["iss"] = "someone-else".into();
+    assert_eq!(
+        jwt_error_kind(
+            verify(
+                &key_store,
+                sign(Algorithm::RS256, Some(RS256_KID), &other_issuer)
+            )
+            .await
+        ),
+        JwtErrorKind::InvalidIssuer
+    );
+
+    let mut other_audience = claims(Roles::Patient, 7);
+    other_audience["aud"] = "another-service".into();
+    assert_eq!(
+        jwt_error_kind(
+            verify(
+                &key_store,
+                sign(Algorithm::ES256, Some(ES256_KID), &other_audience)
+            )
+            .await
+        ),
+        JwtErrorKind::InvalidAudience
+    );
+}
+
+#[tokio::test]
+async fn unknown_kid_reads_the_set_again_at_most_once_per_refresh_interval() {
+    let path = temp_jwks_path();
+    write_jwks(&path, &[RS256_KID]);
+    let key_store = key_store(&path, Duration::from_secs(300));
+    let es256_token = sign(Algorithm::ES256, Some(ES256_KID), &claims(Roles::Doctor, 8));
+
+    assert_eq!(
+        jwks_error(verify(&key_store, es256_token.clone()).await),
+        JwksError::UnknownKeyId(ES256_KID.to_string())
+    );
+
+    // Published right after the set was read, so it is not read again yet.
+    write_jwks(&path, &[RS256_KID, ES256_KID]);
+    assert_eq!(
+        jwks_error(verify(&key_store, es256_token).await),
+        JwksError::UnknownKeyId(ES256_KID.to_string())
+    );
+    verify(
+        &key_store,
+        sign(
+            Algorithm::RS256,
+            Some(RS256_KID),
+            &claims(Roles::Patient, 7),
+        ),
+    )
+    .await
+    .unwrap();
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn key_published_later_is_found_once_the_refresh_interval_passed() {
+    let path = temp_jwks_path();
+    write_jwks(&path, &[RS256_KID]);
+    // The refresh interval is never longer than the cache lifetime.
+    let key_store = key_store(&path, Duration::from_secs(1));
+    key_store.refresh().await.unwrap();
+
+    write_jwks(&path, &[RS256_KID, ES256_KID]);
+    tokio::time::sleep(Duration::from_millis(1_100)).await;
+
+    let es256_claims = verify(
+        &key_store,
+        sign(Algorithm::ES256, Some(ES256_KID), &claims(Roles::Doctor, 8)),
+    )
+    .await
+    .unwrap();
+    assert_eq!(es256_claims.role, Roles::Doctor);
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn middleware_lets_through_tokens_of_allowed_roles_only() {
+    use_jwks_env();
+    let patients_or_doctors = Router::new()
+        .route("/", get(common::whoami))
+        .route_layer(middleware::from_fn(patients_or_doctors_authorization));
+    let patients = Router::new()
+        .route("/", get(common::whoami))
+        .route_layer(middleware::from_fn(patients_authorization));
+
+    let patient_token = sign(
+        Algorithm::RS256,
+        Some(RS256_KID),
+        &claims(Roles::Patient, 7),
+    );
+    let doctor_token = sign(Algorithm::ES256, Some(ES256_KID), &claims(Roles::Doctor, 8));
+
+    assert_eq!(
+        common::get_with_token(patients_or_doctors.clone(), &patient_token).await,
+        (StatusCode::OK, "Patient 7".to_string())
+    );
+    assert_eq!(
+        common::get_with_token(patients_or_doctors, &doctor_token).await,
+        (StatusCode::OK, "Doctor 8".to_string())
+    );
+
+    let (status, body) = common::get_with_token(patients, &doctor_token).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert!(body.contains("belongs to a Doctor"), "{}", body);
+}
+
+#[tokio::test]
+async fn middleware_refuses_tokens_it_cannot_verify() {
+    use_jwks_env();
+    let patients = Router::new()
+        .route("/", get(common::whoami))
+        .route_layer(middleware::from_fn(patients_authorization));
+
+    let mut other_issuer = claims(Roles::Patient, 7);
+    other_issuer["iss"] = "someone-else".into();
+    for (token, message) in [
+        (
+            sign(
+                Algorithm::RS256,
+                Some("retired-key"),
+                &claims(Roles::Patient, 7),
+            ),
+            "signed with an unknown key",
+        ),
+        (
+            sign(
+                Algorithm::HS256,
+                Some(RS256_KID),
+                &claims(Roles::Patient, 7),
+            ),
+            "algorithm HS256 is not accepted",
+        ),
+        (
+            sign(Algorithm::RS256, Some(RS256_KID), &other_issuer),
+            "issuer is not trusted",
+        ),
+    ] {
+        let (status, body) = common::get_with_token(patients.clone(), &token).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert!(body.contains(message), "{}", body);
+    }
+}
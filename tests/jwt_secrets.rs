@@ -0,0 +1,153 @@
+//! Without a JWKS, a token is only accepted when its `role` claim matches the
+//! role whose secret signed it.
+
+mod common;
+
+use std::sync::Once;
+
+use axum::{Router, http::StatusCode, middleware, routing::get};
+use medbook_bookingservice::infrastructure::{
+    axum_http::middleware::{
+        patients_authorization, patients_or_doctors_authorization, staff_authorization,
+    },
+    jwt_authentication::{
+        self,
+        jwt_model::{Claims, Roles},
+    },
+};
+
+const PATIENT_SECRET: &str = "patient-secret";
+const DOCTOR_SECRET: &str = "doctor-secret";
+const STAFF_SECRET: &str = "staff-secret";
+
+static SECRETS_ENV: Once = Once::new();
+
+/// Sets the secret of every role and leaves the JWKS unset.
+fn use_secrets_env() {
+    SECRETS_ENV.call_once(|| {
+        // SAFETY: the tests of this file only read these variables after this
+        // `Once` has set them, all to the same values.
+        unsafe {
+            std::env::set_var("JWT_PATIENT_SECRET", PATIENT_SECRET);
+            std::env::set_var("JWT_PATIENT_REFRESH_SECRET", "patient-refresh-secret");
+            std::env::set_var("JWT_DOCTOR_SECRET", DOCTOR_SECRET);
+            std::env::set_var("JWT_DOCTOR_REFRESH_SECRET", "doctor-refresh-secret");
+            std::env::set_var("JWT_STAFF_SECRET", STAFF_SECRET);
+            for name in [
+                "JWT_JWKS_FILE",
+                "JWT_JWKS_URL",
+                "JWT_ISSUER",
+                "JWT_AUDIENCE",
+            ] {
+                std::env::remove_var(name);
+            }
+        }
+    });
+}
+
+/// A token claiming `role`, signed with `secret`.
+fn token(secret: &str, role: Roles, id: i32) -> String {
+    let now = chrono::Utc::now().timestamp() as usize;
+    let claims = Claims {
+        sub: id.to_string(),
+        role,
+        iat: now,
+        exp: now + 600,
+    };
+
+    jwt_authentication::generate_token(secret.to_string(), &claims).unwrap()
+}
+
+fn patients() -> Router {
+    Router::new()
+        .route("/", get(common::whoami))
+        .route_layer(middleware::from_fn(patients_authorization))
+}
+
+fn patients_or_doctors() -> Router {
+    Router::new()
+        .route("/", get(common::whoami))
+        .route_layer(middleware::from_fn(patients_or_doctors_authorization))
+}
+
+fn staff() -> Router {
+    Router::new()
+        .route("/", get(common::whoami))
+        .route_layer(middleware::from_fn(staff_authorization))
+}
+
+async fn assert_unauthorized(router: Router, token: &str, message: &str) {
+    let (status, body) = common::get_with_token(router, token).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert!(body.contains(message), "{}", body);
+}
+
+#[tokio::test]
+async fn token_signed_with_the_secret_of_its_role_is_accepted() {
+    use_secrets_env();
+
+    assert_eq!(
+        common::get_with_token(patients(), &token(PATIENT_SECRET, Roles::Patient, 7)).await,
+        (StatusCode::OK, "Patient 7".to_string())
+    );
+    assert_eq!(
+        common::get_with_token(
+            patients_or_doctors(),
+            &token(DOCTOR_SECRET, Roles::Doctor, 8)
+        )
+        .await,
+        (StatusCode::OK, "Doctor 8".to_string())
+    );
+    assert_eq!(
+        common::get_with_token(staff(), &token(STAFF_SECRET, Roles::Staff, 9)).await,
+        (StatusCode::OK, "Staff 9".to_string())
+    );
+}
+
+#[tokio::test]
+async fn token_claiming_another_role_than_its_secret_is_refused() {
+    use_secrets_env();
+
+    assert_unauthorized(
+        patients_or_doctors(),
+        &token(PATIENT_SECRET, Roles::Doctor, 7),
+        "role Doctor does not match the Patient key",
+    )
+    .await;
+    assert_unauthorized(
+        patients(),
+        &token(PATIENT_SECRET, Roles::Staff, 7),
+        "role Staff does not match the Patient key",
+    )
+    .await;
+    assert_unauthorized(
+        staff(),
+        &token(STAFF_SECRET, Roles::Patient, 9),
+        "role Patient does not match the Staff key",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn token_of_a_role_the_route_is_not_for_is_refused() {
+    use_secrets_env();
+
+    assert_unauthorized(
+        patients(),
+        &token(DOCTOR_SECRET, Roles::Doctor, 8),
+        "belongs to a Doctor",
+    )
+    .await;
+    assert_unauthorized(
+        patients_or_doctors(),
+        &token(STAFF_SECRET, Roles::Staff, 9),
+        "belongs to a Staff",
+    )
+    .await;
+    assert_unauthorized(
+        patients(),
+        &token("not-a-medbook-secret", Roles::Patient, 7),
+        "signature is invalid",
+    )
+    .await;
+}
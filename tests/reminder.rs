@@ -0,0 +1,181 @@
+//! Each reminder reaches the notifier once per appointment and offset, and one
+//! left unsent by a pass that stopped is sent by a later one.
+
+mod common;
+
+use std::sync::Arc;
+
+use chrono::{DateTime, SubsecRound, TimeDelta, Utc};
+use medbook_bookingservice::{
+    application::usecases::{
+        appointment_ops::AppointmentOpsUseCase, appointment_reminder::AppointmentReminderUseCase,
+        slot_ops::SlotOpsUseCase,
+    },
+    domain::{
+        entities::appointment_reminders::AddAppointmentReminderEntity,
+        repositories::appointment_reminder::AppointmentReminderRepository,
+        value_objects::reminder_model::{AppointmentReminderModel, ReminderSchedule},
+    },
+    infrastructure::{
+        postgres::{
+            postgres_connection::PgPoolSquad,
+            repositories::{
+                appointment_ops::AppointmentOpsPostgres,
+                appointment_reminder::AppointmentReminderPostgres, slot_ops::SlotOpsPostgres,
+            },
+        },
+        reminder::file_notifier::FileReminderNotifier,
+    },
+};
+use uuid::Uuid;
+
+struct Fixture {
+    db_pool: Arc<PgPoolSquad>,
+    appointment_reminder:
+        AppointmentReminderUseCase<AppointmentReminderPostgres, FileReminderNotifier>,
+    path: String,
+}
+
+impl Fixture {
+    /// Reminders 24 hours and 1 hour ahead, written to a file of their own.
+    async fn new() -> Self {
+        let db_pool = common::db_pool().await;
+        let path = std::env::temp_dir()
+            .join(format!("reminders-{}.jsonl", Uuid::new_v4()))
+            .to_string_lossy()
+            .into_owned();
+        let appointment_reminder = AppointmentReminderUseCase::new(
+            Arc::new(AppointmentReminderPostgres::new(Arc::clone(&db_pool))),
+            Arc::new(FileReminderNotifier::new(path.clone())),
+            ReminderSchedule::default(),
+            10_000,
+            300,
+        );
+
+        Self {
+            db_pool,
+            appointment_reminder,
+            path,
+        }
+    }
+
+    /// Books an appointment in a new slot of a new doctor starting at
+    /// `start_time`.
+    async fn book(&self, start_time: DateTime<Utc>) -> (Uuid, DateTime<Utc>) {
+        let slot_ops =
+            SlotOpsUseCase::new(Arc::new(SlotOpsPostgres::new(Arc::clone(&self.db_pool))));
+        let appointment_ops = AppointmentOpsUseCase::new(Arc::new(AppointmentOpsPostgres::new(
+            Arc::clone(&self.db_pool),
+            common::LENIENT_BOOKING_POLICY,
+        )));
+
+        let slot_id = slot_ops
+            .add(common::unique_id(), common::add_slot_dto_at(start_time, 1))
+            .await
+            .unwrap();
+        let appointment_id = appointment_ops
+            .add(
+                common::add_appointment_dto(slot_id),
+                common::unique_id(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        (appointment_id, start_time.trunc_subsecs(0))
+    }
+
+    /// `(appointment_id, offset_minutes)` of every reminder written so far for
+    /// `appointment_ids`, other appointments may be due in the same database.
+    fn sent(&self, appointment_ids: &[Uuid]) -> Vec<(Uuid, i32)> {
+        let mut sent = std::fs::read_to_string(&self.path)
+            .unwrap_or_default()
+            .lines()
+            .map(|line| serde_json::from_str::<AppointmentReminderModel>(line).unwrap())
+            .filter(|reminder| appointment_ids.contains(&reminder.appointment_id))
+            .map(|reminder| (reminder.appointment_id, reminder.offset_minutes))
+            .collect::<Vec<_>>();
+        sent.sort();
+        sent
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[tokio::test]
+#[ignore = "needs a Postgres database at DATABASE_URL"]
+async fn each_reminder_is_sent_once() {
+    let fixture = Fixture::new().await;
+    let now = Utc::now();
+    let (soon_appointment_id, _) = fixture.book(now + TimeDelta::minutes(30)).await;
+    let (later_appointment_id, _) = fixture.book(now + TimeDelta::hours(3)).await;
+    let appointment_ids = [soon_appointment_id, later_appointment_id];
+
+    for _ in 0..2 {
+        let report = fixture.appointment_reminder.send_due().await.unwrap();
+        assert!(
+            report
+                .reclaimed
+                .iter()
+                .all(|(appointment_id, _)| !appointment_ids.contains(appointment_id))
+        );
+    }
+
+    let mut expected = vec![(soon_appointment_id, 60), (later_appointment_id, 24 * 60)];
+    expected.sort();
+    assert_eq!(fixture.sent(&appointment_ids), expected);
+}
+
+#[tokio::test]
+#[ignore = "needs a Postgres database at DATABASE_URL"]
+async fn reminder_is_sent_again_once_its_lease_ran_out() {
+    let fixture = Fixture::new().await;
+    let appointment_reminder_repository =
+        AppointmentReminderPostgres::new(Arc::clone(&fixture.db_pool));
+    let now = Utc::now();
+    let (stale_appointment_id, stale_start_time) = fixture.book(now + TimeDelta::minutes(30)).await;
+    let (leased_appointment_id, leased_start_time) =
+        fixture.book(now + TimeDelta::minutes(40)).await;
+    let appointment_ids = [stale_appointment_id, leased_appointment_id];
+
+    // Claimed by passes that stopped before sending, one of them long ago.
+    let claimed_at = (now - TimeDelta::minutes(10)).naive_utc();
+    for (appointment_id, start_time, lease_until) in [
+        (
+            stale_appointment_id,
+            stale_start_time,
+            now - TimeDelta::minutes(5),
+        ),
+        (
+            leased_appointment_id,
+            leased_start_time,
+            now + TimeDelta::minutes(5),
+        ),
+    ] {
+        appointment_reminder_repository
+            .claim(AddAppointmentReminderEntity {
+                appointment_id,
+                offset_minutes: 60,
+                start_time,
+                created_at: claimed_at,
+                lease_until: lease_until.naive_utc(),
+            })
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    let report = fixture.appointment_reminder.send_due().await.unwrap();
+    assert!(report.reclaimed.contains(&(stale_appointment_id, 60)));
+    assert!(!report.reclaimed.contains(&(leased_appointment_id, 60)));
+
+    fixture.appointment_reminder.send_due().await.unwrap();
+    assert_eq!(
+        fixture.sent(&appointment_ids),
+        vec![(stale_appointment_id, 60)]
+    );
+}
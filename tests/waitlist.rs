@@ -0,0 +1,102 @@
+//! A freed place goes to the first patient on the waitlist who may still book it.
+
+mod common;
+
+use std::sync::Arc;
+
+use medbook_bookingservice::{
+    application::usecases::{
+        appointment_ops::AppointmentOpsUseCase, schedule_viewing::ScheduleViewingUseCase,
+        slot_ops::SlotOpsUseCase, waitlist::WaitlistUseCase,
+    },
+    domain::value_objects::booking_policy::BookingPolicy,
+    infrastructure::postgres::repositories::{
+        appointment_ops::AppointmentOpsPostgres, schedule_viewing::ScheduleViewingPostgres,
+        slot_ops::SlotOpsPostgres, waitlist::WaitlistPostgres,
+    },
+};
+
+#[tokio::test]
+#[ignore = "needs a Postgres database at DATABASE_URL"]
+async fn promotion_skips_patients_the_booking_policy_refuses() {
+    let booking_policy = BookingPolicy {
+        one_active_appointment_per_doctor: true,
+        ..common::LENIENT_BOOKING_POLICY
+    };
+    let db_pool = common::db_pool().await;
+    let slot_ops = SlotOpsUseCase::new(Arc::new(SlotOpsPostgres::new(Arc::clone(&db_pool))));
+    let appointment_ops = AppointmentOpsUseCase::new(Arc::new(AppointmentOpsPostgres::new(
+        Arc::clone(&db_pool),
+        booking_policy,
+    )));
+    let waitlist = WaitlistUseCase::new(Arc::new(WaitlistPostgres::new(
+        Arc::clone(&db_pool),
+        booking_policy,
+    )));
+    let schedule_viewing =
+        ScheduleViewingUseCase::new(Arc::new(ScheduleViewingPostgres::new(db_pool)));
+
+    let doctor_id = common::unique_id();
+    let full_slot_id = slot_ops
+        .add(doctor_id, common::add_slot_dto(10, 1))
+        .await
+        .unwrap();
+    let other_slot_id = slot_ops
+        .add(doctor_id, common::add_slot_dto(11, 1))
+        .await
+        .unwrap();
+    let (booked_patient_id, first_patient_id, second_patient_id) = (
+        common::unique_id(),
+        common::unique_id(),
+        common::unique_id(),
+    );
+
+    let appointment_id = appointment_ops
+        .add(
+            common::add_appointment_dto(full_slot_id),
+            booked_patient_id,
+            None,
+        )
+        .await
+        .unwrap();
+    waitlist
+        .join(common::add_appointment_dto(full_slot_id), first_patient_id)
+        .await
+        .unwrap();
+    waitlist
+        .join(common::add_appointment_dto(full_slot_id), second_patient_id)
+        .await
+        .unwrap();
+    // The first patient in the queue now holds an appointment with the doctor.
+    appointment_ops
+        .add(
+            common::add_appointment_dto(other_slot_id),
+            first_patient_id,
+            None,
+        )
+        .await
+        .unwrap();
+
+    appointment_ops
+        .remove(appointment_id, booked_patient_id)
+        .await
+        .unwrap();
+
+    let schedules = schedule_viewing
+        .get_doctor_schedules(doctor_id, Default::default())
+        .await
+        .unwrap();
+    let promoted_patient_ids = schedules
+        .iter()
+        .filter(|schedule| schedule.slot_id == full_slot_id)
+        .map(|schedule| schedule.patient_id)
+        .collect::<Vec<_>>();
+    assert_eq!(promoted_patient_ids, vec![second_patient_id]);
+
+    let first_patient_waitlist = waitlist
+        .get_patient_waitlist(first_patient_id)
+        .await
+        .unwrap();
+    assert_eq!(first_patient_waitlist.len(), 1);
+    assert_eq!(first_patient_waitlist[0].position, 1);
+}
@@ -0,0 +1,144 @@
+//! A webhook subscription only receives events about the doctors it asked for,
+//! and only while its API key may read appointments.
+
+mod common;
+
+use std::sync::Arc;
+
+use medbook_bookingservice::{
+    application::usecases::{
+        appointment_ops::AppointmentOpsUseCase, outbox_dispatch::OutboxDispatchUseCase,
+        service_api_key::ServiceApiKeyUseCase, slot_ops::SlotOpsUseCase,
+        webhook_subscription::WebhookSubscriptionUseCase,
+    },
+    domain::value_objects::{
+        outbox_model::OutboxRetryPolicy,
+        service_api_key_model::ServiceScope,
+        webhook_model::{AddWebhookSubscriptionDto, GetWebhookDeliveriesQuery},
+    },
+    infrastructure::{
+        outbox::log_sink::LogOutboxSink,
+        postgres::repositories::{
+            appointment_ops::AppointmentOpsPostgres,
+            outbox::OutboxPostgres,
+            service_api_key::ServiceApiKeyPostgres,
+            slot_ops::SlotOpsPostgres,
+            webhook::{WebhookFanOutSink, WebhookSubscriptionPostgres},
+        },
+    },
+};
+use uuid::Uuid;
+
+fn add_subscription_dto(doctor_ids: Vec<i32>) -> AddWebhookSubscriptionDto {
+    AddWebhookSubscriptionDto {
+        url: "https://example.com/medbook".to_string(),
+        event_types: vec!["AppointmentBooked".to_string()],
+        doctor_ids,
+        secret: None,
+    }
+}
+
+#[tokio::test]
+#[ignore = "needs a Postgres database at DATABASE_URL"]
+async fn events_only_reach_subscriptions_of_their_doctor() {
+    let db_pool = common::db_pool().await;
+    let service_api_key =
+        ServiceApiKeyUseCase::new(Arc::new(ServiceApiKeyPostgres::new(Arc::clone(&db_pool))));
+    let webhook_subscription = WebhookSubscriptionUseCase::new(Arc::new(
+        WebhookSubscriptionPostgres::new(Arc::clone(&db_pool)),
+    ));
+    let slot_ops = SlotOpsUseCase::new(Arc::new(SlotOpsPostgres::new(Arc::clone(&db_pool))));
+    let appointment_ops = AppointmentOpsUseCase::new(Arc::new(AppointmentOpsPostgres::new(
+        Arc::clone(&db_pool),
+        common::LENIENT_BOOKING_POLICY,
+    )));
+    let outbox_dispatch = OutboxDispatchUseCase::new(
+        Arc::new(OutboxPostgres::new(Arc::clone(&db_pool))),
+        Arc::new(WebhookFanOutSink::new(Arc::clone(&db_pool), LogOutboxSink)),
+        OutboxRetryPolicy::default(),
+        100,
+        60,
+    );
+
+    let reader_key_id = service_api_key
+        .create(
+            "webhook-test",
+            vec![ServiceScope::WebhooksWrite, ServiceScope::AppointmentsRead],
+        )
+        .await
+        .unwrap()
+        .id;
+    let writer_key_id = service_api_key
+        .create("webhook-test", vec![ServiceScope::WebhooksWrite])
+        .await
+        .unwrap()
+        .id;
+
+    let doctor_id = common::unique_id();
+    let other_doctor_id = common::unique_id();
+    let add = |service_api_key_id: i32, doctor_ids: Vec<i32>| {
+        webhook_subscription.add(service_api_key_id, add_subscription_dto(doctor_ids))
+    };
+    let doctor_subscription_id = add(reader_key_id, vec![doctor_id])
+        .await
+        .unwrap()
+        .subscription
+        .id;
+    let other_doctor_subscription_id = add(reader_key_id, vec![other_doctor_id])
+        .await
+        .unwrap()
+        .subscription
+        .id;
+    let unreadable_subscription_id = add(writer_key_id, vec![doctor_id])
+        .await
+        .unwrap()
+        .subscription
+        .id;
+
+    let slot_id = slot_ops
+        .add(doctor_id, common::add_slot_dto(10, 1))
+        .await
+        .unwrap();
+    appointment_ops
+        .add(
+            common::add_appointment_dto(slot_id),
+            common::unique_id(),
+            None,
+        )
+        .await
+        .unwrap();
+
+    loop {
+        let report = outbox_dispatch.dispatch_due().await.unwrap();
+        if !outbox_dispatch.is_full_batch(&report) {
+            break;
+        }
+    }
+
+    let delivery_count = |subscription_id: Uuid, service_api_key_id: i32| {
+        let webhook_subscription = &webhook_subscription;
+        async move {
+            webhook_subscription
+                .get_deliveries(
+                    subscription_id,
+                    service_api_key_id,
+                    GetWebhookDeliveriesQuery::default(),
+                )
+                .await
+                .unwrap()
+                .len()
+        }
+    };
+    assert_eq!(
+        delivery_count(doctor_subscription_id, reader_key_id).await,
+        1
+    );
+    assert_eq!(
+        delivery_count(other_doctor_subscription_id, reader_key_id).await,
+        0
+    );
+    assert_eq!(
+        delivery_count(unreadable_subscription_id, writer_key_id).await,
+        0
+    );
+}